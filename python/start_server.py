@@ -31,6 +31,12 @@ def parse_args(argv: list[str] | None = None) -> argparse.Namespace:
     """Parse command-line arguments."""
     parser = argparse.ArgumentParser(description="Qwen3-TTS FastAPI server")
     parser.add_argument("--port", type=int, default=8000, help="Port to listen on")
+    parser.add_argument(
+        "--host",
+        type=str,
+        default="0.0.0.0",
+        help="Address to bind (e.g. 127.0.0.1 or ::1)",
+    )
     parser.add_argument(
         "--models",
         nargs="+",
@@ -79,7 +85,7 @@ def main(argv: list[str] | None = None) -> None:
     uvicorn.run(
         "server.app:create_app",
         factory=True,
-        host="0.0.0.0",
+        host=args.host,
         port=args.port,
         log_level="info",
     )
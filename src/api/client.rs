@@ -1,5 +1,8 @@
-use anyhow::{Context, Result};
+use std::fmt;
+use std::time::{Duration, Instant};
+
 use reqwest::multipart;
+use serde::de::DeserializeOwned;
 
 use super::types::{
     CancelResponse, CapabilitiesResponse, CloneRequest, CloneResponse, CustomVoiceRequest,
@@ -7,79 +10,229 @@ use super::types::{
     ReferenceAudio, RenameRequest, RenameResponse, TaskStatusResponse, VoiceDesignRequest,
 };
 
+/// Await `fut` and return its output with the elapsed round-trip time.
+pub async fn timed<F: Future>(fut: F) -> (F::Output, Duration) {
+    let started = Instant::now();
+    let output = fut.await;
+    (output, started.elapsed())
+}
+
+/// Request timeout used by [`ApiClient::new`].
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Minimum timeout for audio byte downloads, which can take much longer
+/// than the JSON endpoints for long clips.
+pub const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Upper bound on each attempt of a retried GET, so a hung server is
+/// given up on quickly instead of blocking the UI for the full timeout.
+pub const RETRY_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often, and how patiently, idempotent GETs are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first. `1` disables retrying.
+    pub attempts: u32,
+    /// Delay before the first retry; doubled for each retry after it.
+    pub initial_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Policy that sends each request exactly once.
+    pub const NONE: Self = Self {
+        attempts: 1,
+        initial_backoff: Duration::ZERO,
+    };
+
+    /// Delay before retry number `retry` (0-based).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff.saturating_mul(2u32.saturating_pow(retry))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Run `op` until it succeeds, fails with a non-retryable error, or
+/// `policy.attempts` is used up, sleeping with exponential backoff between
+/// attempts. Returns the last error.
+pub async fn with_retry<T, F, Fut>(policy: RetryPolicy, mut op: F) -> Result<T, ApiError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ApiError>>,
+{
+    let mut retry = 0;
+    loop {
+        match op().await {
+            Err(e) if e.is_retryable() && retry + 1 < policy.attempts => {
+                tokio::time::sleep(policy.backoff(retry)).await;
+                retry += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// A failed backend request, classified so callers can tell an unreachable
+/// server from a rejected request or a malformed response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// The server could not be reached or the connection dropped.
+    Network(String),
+    /// No response arrived within the client's timeout.
+    Timeout,
+    /// The server answered with a non-success HTTP status.
+    Status(u16),
+    /// The response body did not match the expected shape.
+    Decode(String),
+}
+
+impl ApiError {
+    /// Whether sending the same request again may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Network(_) | Self::Timeout => true,
+            Self::Status(code) => *code >= 500,
+            Self::Decode(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network(msg) => write!(f, "server not reachable: {msg}"),
+            Self::Timeout => write!(f, "request timed out"),
+            Self::Status(code) => write!(f, "server returned HTTP {code}"),
+            Self::Decode(msg) => write!(f, "invalid server response: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Self::Timeout
+        } else if let Some(status) = e.status() {
+            Self::Status(status.as_u16())
+        } else if e.is_decode() {
+            Self::Decode(error_chain(&e))
+        } else {
+            Self::Network(error_chain(&e))
+        }
+    }
+}
+
+/// `e` and its sources joined with `: `, since reqwest keeps the useful
+/// part (e.g. "connection refused") in the source.
+fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut msg = e.to_string();
+    let mut source = e.source();
+    while let Some(cause) = source {
+        msg.push_str(": ");
+        msg.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    msg
+}
+
 /// HTTP client for the Qwen3-TTS Python backend.
 #[derive(Debug, Clone)]
 pub struct ApiClient {
     client: reqwest::Client,
     base_url: String,
+    timeout: Duration,
+    download_timeout: Duration,
+    retry: RetryPolicy,
 }
 
 impl ApiClient {
     pub fn new(base_url: &str) -> Self {
+        Self::with_timeout(base_url, DEFAULT_TIMEOUT)
+    }
+
+    /// Client whose requests fail after `timeout`. Audio downloads get at
+    /// least [`DOWNLOAD_TIMEOUT`].
+    pub fn with_timeout(base_url: &str, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
         Self {
-            client: reqwest::Client::new(),
+            client,
             base_url: base_url.trim_end_matches('/').to_owned(),
+            timeout,
+            download_timeout: timeout.max(DOWNLOAD_TIMEOUT),
+            retry: RetryPolicy::default(),
         }
     }
 
+    /// Replace the retry policy used for the idempotent GET endpoints.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     fn url(&self, path: &str) -> String {
         format!("{}{path}", self.base_url)
     }
 
+    /// GET `path` and decode the JSON body, retrying per the client's
+    /// [`RetryPolicy`]. Each attempt is capped at [`RETRY_ATTEMPT_TIMEOUT`].
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, ApiError> {
+        let url = self.url(path);
+        let attempt_timeout = self.timeout.min(RETRY_ATTEMPT_TIMEOUT);
+        with_retry(self.retry, || async {
+            let request = self.client.get(&url).timeout(attempt_timeout);
+            decode_json(self.send(request).await?).await
+        })
+        .await
+    }
+
+    /// Send `request` and fail on a non-success status. With debug logging
+    /// on, the method, URL, status and any in-memory body are logged;
+    /// multipart bodies are streamed, so callers log their metadata.
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, ApiError> {
+        let request = request.build()?;
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            let body = request
+                .body()
+                .and_then(reqwest::Body::as_bytes)
+                .map_or_else(|| "<streamed>".to_owned(), body_preview);
+            tracing::debug!(method = %request.method(), url = %request.url(), %body, "request");
+        }
+        let response = self.client.execute(request).await?;
+        tracing::debug!(status = %response.status(), url = %response.url(), "response");
+        Ok(response.error_for_status()?)
+    }
+
     // ─── Server Management ──────────────────────────────────────
 
-    pub async fn health(&self) -> Result<HealthResponse> {
-        self.client
-            .get(self.url("/health"))
-            .send()
-            .await
-            .context("health request failed")?
-            .error_for_status()
-            .context("health returned error status")?
-            .json()
-            .await
-            .context("failed to parse health response")
+    pub async fn health(&self) -> Result<HealthResponse, ApiError> {
+        self.get_json("/health").await
     }
 
-    pub async fn capabilities(&self) -> Result<CapabilitiesResponse> {
-        self.client
-            .get(self.url("/capabilities"))
-            .send()
-            .await
-            .context("capabilities request failed")?
-            .error_for_status()
-            .context("capabilities returned error status")?
-            .json()
-            .await
-            .context("failed to parse capabilities response")
+    pub async fn capabilities(&self) -> Result<CapabilitiesResponse, ApiError> {
+        self.get_json("/capabilities").await
     }
 
-    pub async fn languages(&self) -> Result<LanguagesResponse> {
-        self.client
-            .get(self.url("/languages"))
-            .send()
-            .await
-            .context("languages request failed")?
-            .error_for_status()
-            .context("languages returned error status")?
-            .json()
-            .await
-            .context("failed to parse languages response")
+    pub async fn languages(&self) -> Result<LanguagesResponse, ApiError> {
+        self.get_json("/languages").await
     }
 
     // ─── Reference Audio ────────────────────────────────────────
 
-    pub async fn references(&self) -> Result<Vec<ReferenceAudio>> {
-        self.client
-            .get(self.url("/references"))
-            .send()
-            .await
-            .context("references request failed")?
-            .error_for_status()
-            .context("references returned error status")?
-            .json()
-            .await
-            .context("failed to parse references response")
+    pub async fn references(&self) -> Result<Vec<ReferenceAudio>, ApiError> {
+        self.get_json("/references").await
     }
 
     pub async fn upload_reference(
@@ -87,91 +240,58 @@ impl ApiClient {
         file_bytes: Vec<u8>,
         filename: String,
         ref_text: Option<&str>,
-    ) -> Result<ReferenceAudio> {
+    ) -> Result<ReferenceAudio, ApiError> {
+        tracing::debug!(file = %filename, bytes = file_bytes.len(), "multipart upload");
         let file_part = multipart::Part::bytes(file_bytes)
-            .file_name(filename)
-            .mime_str("audio/wav")
-            .context("invalid mime type")?;
+            .file_name(safe_upload_filename(&filename))
+            .mime_str(audio_mime(&filename))?;
 
         let mut form = multipart::Form::new().part("file", file_part);
         if let Some(text) = ref_text {
             form = form.text("ref_text", text.to_owned());
         }
 
-        self.client
+        let request = self
+            .client
             .post(self.url("/upload-reference"))
-            .multipart(form)
-            .send()
-            .await
-            .context("upload-reference request failed")?
-            .error_for_status()
-            .context("upload-reference returned error status")?
-            .json()
-            .await
-            .context("failed to parse upload-reference response")
+            .multipart(form);
+        decode_json(self.send(request).await?).await
     }
 
-    pub async fn reference_audio(&self, audio_id: &str) -> Result<Vec<u8>> {
-        self.client
+    pub async fn reference_audio(&self, audio_id: &str) -> Result<Vec<u8>, ApiError> {
+        let request = self
+            .client
             .get(self.url(&format!("/references/{audio_id}/audio")))
-            .send()
-            .await
-            .context("reference audio request failed")?
-            .error_for_status()
-            .context("reference audio returned error status")?
-            .bytes()
-            .await
-            .context("failed to read reference audio bytes")
-            .map(|b| b.to_vec())
+            .timeout(self.download_timeout);
+        decode_bytes(self.send(request).await?).await
     }
 
-    pub async fn delete_reference(&self, audio_id: &str) -> Result<DeleteResponse> {
-        self.client
-            .delete(self.url(&format!("/references/{audio_id}")))
-            .send()
-            .await
-            .context("delete reference request failed")?
-            .error_for_status()
-            .context("delete reference returned error status")?
-            .json()
-            .await
-            .context("failed to parse delete reference response")
+    pub async fn delete_reference(&self, audio_id: &str) -> Result<DeleteResponse, ApiError> {
+        let request = self
+            .client
+            .delete(self.url(&format!("/references/{audio_id}")));
+        decode_json(self.send(request).await?).await
     }
 
     pub async fn rename_reference(
         &self,
         audio_id: &str,
         name: &str,
-    ) -> Result<RenameResponse> {
-        self.client
+    ) -> Result<RenameResponse, ApiError> {
+        let request = self
+            .client
             .put(self.url(&format!("/references/{audio_id}/name")))
             .json(&RenameRequest {
                 name: name.to_owned(),
-            })
-            .send()
-            .await
-            .context("rename reference request failed")?
-            .error_for_status()
-            .context("rename reference returned error status")?
-            .json()
-            .await
-            .context("failed to parse rename reference response")
+            });
+        decode_json(self.send(request).await?).await
     }
 
     // ─── Voice Generation ───────────────────────────────────────
 
-    pub async fn clone_voice(&self, request: &CloneRequest) -> Result<CloneResponse> {
-        self.client
-            .post(self.url("/clone"))
-            .json(request)
-            .send()
-            .await
-            .context("clone request failed")?
-            .error_for_status()
-            .context("clone returned error status")?
-            .json()
-            .await
-            .context("failed to parse clone response")
+    pub async fn clone_voice(&self, request: &CloneRequest) -> Result<CloneResponse, ApiError> {
+        let request = self.client.post(self.url("/clone")).json(request);
+        decode_json(self.send(request).await?).await
     }
 
     pub async fn clone_with_upload(
@@ -181,11 +301,11 @@ impl ApiClient {
         text: &str,
         ref_text: Option<&str>,
         language: Option<&str>,
-    ) -> Result<CloneResponse> {
+    ) -> Result<CloneResponse, ApiError> {
+        tracing::debug!(file = %filename, bytes = file_bytes.len(), "multipart upload");
         let file_part = multipart::Part::bytes(file_bytes)
-            .file_name(filename)
-            .mime_str("audio/wav")
-            .context("invalid mime type")?;
+            .file_name(safe_upload_filename(&filename))
+            .mime_str(audio_mime(&filename))?;
 
         let mut form = multipart::Form::new()
             .part("file", file_part)
@@ -198,132 +318,156 @@ impl ApiClient {
             form = form.text("language", lang.to_owned());
         }
 
-        self.client
+        let request = self
+            .client
             .post(self.url("/clone-with-upload"))
-            .multipart(form)
-            .send()
-            .await
-            .context("clone-with-upload request failed")?
-            .error_for_status()
-            .context("clone-with-upload returned error status")?
-            .json()
-            .await
-            .context("failed to parse clone-with-upload response")
+            .multipart(form);
+        decode_json(self.send(request).await?).await
     }
 
     pub async fn clone_multi_speaker(
         &self,
         request: &MultiSpeakerRequest,
-    ) -> Result<CloneResponse> {
-        self.client
+    ) -> Result<CloneResponse, ApiError> {
+        let request = self
+            .client
             .post(self.url("/clone-multi-speaker"))
-            .json(request)
-            .send()
-            .await
-            .context("clone-multi-speaker request failed")?
-            .error_for_status()
-            .context("clone-multi-speaker returned error status")?
-            .json()
-            .await
-            .context("failed to parse clone-multi-speaker response")
+            .json(request);
+        decode_json(self.send(request).await?).await
     }
 
-    pub async fn voice_design(&self, request: &VoiceDesignRequest) -> Result<CloneResponse> {
-        self.client
-            .post(self.url("/voice-design"))
-            .json(request)
-            .send()
-            .await
-            .context("voice-design request failed")?
-            .error_for_status()
-            .context("voice-design returned error status")?
-            .json()
-            .await
-            .context("failed to parse voice-design response")
+    pub async fn voice_design(
+        &self,
+        request: &VoiceDesignRequest,
+    ) -> Result<CloneResponse, ApiError> {
+        let request = self.client.post(self.url("/voice-design")).json(request);
+        decode_json(self.send(request).await?).await
     }
 
-    pub async fn custom_voice(&self, request: &CustomVoiceRequest) -> Result<CloneResponse> {
-        self.client
-            .post(self.url("/custom-voice"))
-            .json(request)
-            .send()
-            .await
-            .context("custom-voice request failed")?
-            .error_for_status()
-            .context("custom-voice returned error status")?
-            .json()
-            .await
-            .context("failed to parse custom-voice response")
+    pub async fn custom_voice(
+        &self,
+        request: &CustomVoiceRequest,
+    ) -> Result<CloneResponse, ApiError> {
+        let request = self.client.post(self.url("/custom-voice")).json(request);
+        decode_json(self.send(request).await?).await
     }
 
     // ─── Task Management ────────────────────────────────────────
 
-    pub async fn task_status(&self, task_id: &str) -> Result<TaskStatusResponse> {
-        self.client
-            .get(self.url(&format!("/tasks/{task_id}")))
-            .send()
-            .await
-            .context("task status request failed")?
-            .error_for_status()
-            .context("task status returned error status")?
-            .json()
-            .await
-            .context("failed to parse task status response")
+    pub async fn task_status(&self, task_id: &str) -> Result<TaskStatusResponse, ApiError> {
+        let request = self.client.get(self.url(&format!("/tasks/{task_id}")));
+        decode_json(self.send(request).await?).await
     }
 
-    pub async fn cancel_task(&self, task_id: &str) -> Result<CancelResponse> {
-        self.client
-            .post(self.url(&format!("/tasks/{task_id}/cancel")))
-            .send()
-            .await
-            .context("cancel task request failed")?
-            .error_for_status()
-            .context("cancel task returned error status")?
-            .json()
-            .await
-            .context("failed to parse cancel task response")
+    pub async fn cancel_task(&self, task_id: &str) -> Result<CancelResponse, ApiError> {
+        let request = self
+            .client
+            .post(self.url(&format!("/tasks/{task_id}/cancel")));
+        decode_json(self.send(request).await?).await
     }
 
-    pub async fn task_audio(&self, task_id: &str) -> Result<Vec<u8>> {
-        self.client
+    pub async fn task_audio(&self, task_id: &str) -> Result<Vec<u8>, ApiError> {
+        let request = self
+            .client
             .get(self.url(&format!("/tasks/{task_id}/audio")))
-            .send()
-            .await
-            .context("task audio request failed")?
-            .error_for_status()
-            .context("task audio returned error status")?
-            .bytes()
-            .await
-            .context("failed to read task audio bytes")
-            .map(|b| b.to_vec())
+            .timeout(self.download_timeout);
+        decode_bytes(self.send(request).await?).await
     }
 
     // ─── Generated Audio ────────────────────────────────────────
 
-    pub async fn generated_list(&self) -> Result<Vec<GeneratedAudio>> {
-        self.client
-            .get(self.url("/generated"))
-            .send()
-            .await
-            .context("generated list request failed")?
-            .error_for_status()
-            .context("generated list returned error status")?
-            .json()
-            .await
-            .context("failed to parse generated list response")
+    pub async fn generated_list(&self) -> Result<Vec<GeneratedAudio>, ApiError> {
+        let request = self.client.get(self.url("/generated"));
+        decode_json(self.send(request).await?).await
     }
 
-    pub async fn delete_generated(&self, audio_id: &str) -> Result<DeleteResponse> {
-        self.client
-            .delete(self.url(&format!("/generated/{audio_id}")))
-            .send()
-            .await
-            .context("delete generated request failed")?
-            .error_for_status()
-            .context("delete generated returned error status")?
-            .json()
-            .await
-            .context("failed to parse delete generated response")
+    pub async fn delete_generated(&self, audio_id: &str) -> Result<DeleteResponse, ApiError> {
+        let request = self
+            .client
+            .delete(self.url(&format!("/generated/{audio_id}")));
+        decode_json(self.send(request).await?).await
+    }
+}
+
+/// Read `response` as JSON, logging a preview of the body.
+async fn decode_json<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, ApiError> {
+    let bytes = response.bytes().await?;
+    tracing::debug!(body = %body_preview(&bytes), "response body");
+    serde_json::from_slice(&bytes).map_err(|e| ApiError::Decode(e.to_string()))
+}
+
+/// Read `response` as raw bytes (audio), logging only its size.
+async fn decode_bytes(response: reqwest::Response) -> Result<Vec<u8>, ApiError> {
+    let bytes = response.bytes().await?;
+    tracing::debug!(bytes = bytes.len(), "response body");
+    Ok(bytes.to_vec())
+}
+
+/// Longest body text, in characters, written to the log.
+const LOG_BODY_CHARS: usize = 500;
+
+/// A loggable preview of a request or response body: the text cut to
+/// [`LOG_BODY_CHARS`], or just the size for binary data.
+fn body_preview(bytes: &[u8]) -> String {
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return format!("<{} bytes of binary data>", bytes.len());
+    };
+    if text.chars().count() <= LOG_BODY_CHARS {
+        return text.to_owned();
+    }
+    let cut: String = text.chars().take(LOG_BODY_CHARS).collect();
+    format!("{cut}... ({} bytes)", bytes.len())
+}
+
+/// MIME type for an audio upload, from its file extension. Unknown
+/// extensions are sent as WAV, which is what the backend expects by default.
+pub fn audio_mime(filename: &str) -> &'static str {
+    let ext = filename
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "ogg" | "oga" => "audio/ogg",
+        "m4a" | "mp4" => "audio/mp4",
+        _ => "audio/wav",
+    }
+}
+
+/// Reduce an upload's filename to a portable ASCII name.
+///
+/// Directory components are dropped, anything outside `[A-Za-z0-9_-]` in the
+/// stem becomes `_`, and the extension is kept (lowercased). The original name
+/// is only used for display; the backend stores its own id-based filename.
+pub fn safe_upload_filename(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or(name);
+
+    let (stem, ext) = match base.rsplit_once('.') {
+        Some((stem, ext))
+            if !stem.is_empty()
+                && !ext.is_empty()
+                && ext.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            (stem, Some(ext))
+        }
+        _ => (base, None),
+    };
+
+    let mut clean = String::with_capacity(stem.len());
+    for c in stem.chars() {
+        if c.is_ascii_alphanumeric() || c == '-' {
+            clean.push(c);
+        } else if !clean.ends_with('_') {
+            clean.push('_');
+        }
+    }
+    let clean = clean.trim_matches('_');
+    let stem = if clean.is_empty() { "upload" } else { clean };
+
+    match ext {
+        Some(ext) => format!("{stem}.{}", ext.to_ascii_lowercase()),
+        None => stem.to_owned(),
     }
 }
 
@@ -333,6 +477,94 @@ mod tests {
     use wiremock::matchers::{body_json, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
+    #[test]
+    fn body_preview_truncates_text_and_hides_binary() {
+        assert_eq!(body_preview(br#"{"status":"healthy"}"#), r#"{"status":"healthy"}"#);
+
+        let long = "a".repeat(LOG_BODY_CHARS + 10);
+        let preview = body_preview(long.as_bytes());
+        assert!(preview.starts_with(&long[..LOG_BODY_CHARS]));
+        assert!(preview.ends_with(&format!("... ({} bytes)", long.len())));
+
+        assert_eq!(body_preview(&[0xFF, 0xFE, 0x00]), "<3 bytes of binary data>");
+    }
+
+    #[tokio::test]
+    async fn timed_measures_elapsed() {
+        let (value, elapsed) = timed(async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            42
+        })
+        .await;
+        assert_eq!(value, 42);
+        assert!(elapsed >= Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn timeout_is_reported() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({
+                        "status": "ok",
+                        "voice_cloner_loaded": true,
+                        "loaded_models": []
+                    }))
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = ApiClient::with_timeout(&server.uri(), Duration::from_millis(50));
+        let err = client.health().await.expect_err("should time out");
+        assert_eq!(err, ApiError::Timeout);
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn download_timeout_is_at_least_default() {
+        let client = ApiClient::with_timeout("http://localhost", Duration::from_secs(5));
+        assert_eq!(client.download_timeout, DOWNLOAD_TIMEOUT);
+        let client = ApiClient::with_timeout("http://localhost", Duration::from_secs(600));
+        assert_eq!(client.download_timeout, Duration::from_secs(600));
+    }
+
+    #[test]
+    fn audio_mime_from_extension() {
+        assert_eq!(audio_mime("voice.wav"), "audio/wav");
+        assert_eq!(audio_mime("voice.MP3"), "audio/mpeg");
+        assert_eq!(audio_mime("a.b.flac"), "audio/flac");
+        assert_eq!(audio_mime("clip.ogg"), "audio/ogg");
+        assert_eq!(audio_mime("clip.m4a"), "audio/mp4");
+        assert_eq!(audio_mime("recording"), "audio/wav");
+    }
+
+    #[test]
+    fn safe_upload_filename_emoji() {
+        assert_eq!(safe_upload_filename("🎤 my voice 🎶.wav"), "my_voice.wav");
+    }
+
+    #[test]
+    fn safe_upload_filename_non_ascii_script() {
+        assert_eq!(safe_upload_filename("日本語の声.WAV"), "upload.wav");
+        assert_eq!(safe_upload_filename("voix_française.mp3"), "voix_fran_aise.mp3");
+    }
+
+    #[test]
+    fn safe_upload_filename_without_extension() {
+        assert_eq!(safe_upload_filename("recording"), "recording");
+        assert_eq!(safe_upload_filename(".hidden"), "hidden");
+    }
+
+    #[test]
+    fn safe_upload_filename_strips_path_traversal() {
+        assert_eq!(safe_upload_filename("../../etc/passwd"), "passwd");
+        assert_eq!(safe_upload_filename("..\\..\\evil.wav"), "evil.wav");
+        assert_eq!(safe_upload_filename(".."), "upload");
+    }
+
     #[tokio::test]
     async fn health_success() {
         let server = MockServer::start().await;
@@ -363,7 +595,123 @@ mod tests {
             .await;
 
         let client = ApiClient::new(&server.uri());
-        assert!(client.health().await.is_err());
+        let err = client.health().await.expect_err("should fail");
+        assert_eq!(err, ApiError::Status(500));
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn health_not_found_is_not_retryable() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = ApiClient::new(&server.uri());
+        let err = client.health().await.expect_err("should fail");
+        assert_eq!(err, ApiError::Status(404));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn retry_backoff_doubles() {
+        let policy = RetryPolicy {
+            attempts: 4,
+            initial_backoff: Duration::from_millis(100),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn health_retries_after_server_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "healthy",
+                "voice_cloner_loaded": false,
+                "loaded_models": []
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ApiClient::new(&server.uri());
+        let resp = client.health().await.expect("retry should succeed");
+        assert_eq!(resp.status, "healthy");
+    }
+
+    #[tokio::test]
+    async fn references_gives_up_after_policy_attempts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/references"))
+            .respond_with(ResponseTemplate::new(502))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = ApiClient::new(&server.uri()).with_retry_policy(RetryPolicy {
+            attempts: 2,
+            initial_backoff: Duration::from_millis(1),
+        });
+        let err = client.references().await.expect_err("should fail");
+        assert_eq!(err, ApiError::Status(502));
+    }
+
+    #[tokio::test]
+    async fn languages_client_error_is_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/languages"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ApiClient::new(&server.uri());
+        let err = client.languages().await.expect_err("should fail");
+        assert_eq!(err, ApiError::Status(404));
+    }
+
+    #[tokio::test]
+    async fn health_bad_json_is_decode_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+            .mount(&server)
+            .await;
+
+        let client = ApiClient::new(&server.uri());
+        let err = client.health().await.expect_err("should fail");
+        assert!(matches!(err, ApiError::Decode(_)), "{err:?}");
+        assert!(!err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn health_unreachable_is_network_error() {
+        // Bind then drop a listener so the port is free but closed.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|l| l.local_addr())
+            .expect("bind")
+            .port();
+
+        let client = ApiClient::new(&format!("http://127.0.0.1:{port}"));
+        let err = client.health().await.expect_err("should fail");
+        assert!(matches!(err, ApiError::Network(_)), "{err:?}");
+        assert!(err.is_retryable());
     }
 
     #[tokio::test]
@@ -432,6 +780,7 @@ mod tests {
             ref_audio_id: "uuid-1".to_owned(),
             ref_text: None,
             language: "auto".to_owned(),
+            batch_line: None,
         };
 
         Mock::given(method("POST"))
@@ -8,6 +8,10 @@ pub struct HealthResponse {
     pub status: String,
     pub voice_cloner_loaded: bool,
     pub loaded_models: Vec<String>,
+    /// Models the server failed to load, with the reason. Older servers
+    /// omit it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_load_errors: Option<Vec<String>>,
 }
 
 /// Response from `GET /capabilities`.
@@ -36,6 +40,14 @@ pub struct ReferenceAudio {
     pub created_at: String,
 }
 
+impl ReferenceAudio {
+    /// Name shown in pickers and lists: the user-given name, else the
+    /// uploaded file's name.
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.original_name)
+    }
+}
+
 /// Request body for `PUT /references/{audio_id}/name`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RenameRequest {
@@ -60,6 +72,10 @@ pub struct CloneRequest {
     pub ref_text: Option<String>,
     #[serde(default = "default_language")]
     pub language: String,
+    /// The Clone tab batch line this request was sent for; never sent to
+    /// the server or saved.
+    #[serde(skip)]
+    pub batch_line: Option<u64>,
 }
 
 /// A single segment in a `POST /clone-multi-speaker` request.
@@ -187,12 +203,20 @@ mod tests {
             status: "healthy".to_owned(),
             voice_cloner_loaded: true,
             loaded_models: vec!["base".to_owned(), "voice_design".to_owned()],
+            model_load_errors: Some(vec!["custom_voice: out of memory".to_owned()]),
         };
         let json = serde_json::to_string(&original).expect("serialize");
         let decoded: HealthResponse = serde_json::from_str(&json).expect("deserialize");
         assert_eq!(original, decoded);
     }
 
+    #[test]
+    fn health_response_without_load_errors() {
+        let json = r#"{"status":"healthy","voice_cloner_loaded":false,"loaded_models":[]}"#;
+        let decoded: HealthResponse = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(decoded.model_load_errors, None);
+    }
+
     #[test]
     fn capabilities_response_round_trip() {
         let original = CapabilitiesResponse {
@@ -251,6 +275,7 @@ mod tests {
             ref_audio_id: "uuid-1".to_owned(),
             ref_text: Some("reference".to_owned()),
             language: "auto".to_owned(),
+            batch_line: None,
         };
         let json = serde_json::to_string(&original).expect("serialize");
         let decoded: CloneRequest = serde_json::from_str(&json).expect("deserialize");
@@ -264,6 +289,7 @@ mod tests {
             ref_audio_id: "uuid-1".to_owned(),
             ref_text: None,
             language: "auto".to_owned(),
+            batch_line: None,
         };
         let json = serde_json::to_string(&req).expect("serialize");
         assert!(!json.contains("ref_text"));
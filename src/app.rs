@@ -1,23 +1,57 @@
-use std::time::Duration;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, Stream};
 use iced::widget::{button, center, column, container, progress_bar, row, scrollable, text};
 use iced::{Element, Length, Subscription, Task, Theme};
 
-use crate::api::client::ApiClient;
+use crate::api::client::{ApiClient, ApiError, timed};
 use crate::api::types::{
     CloneRequest, CustomVoiceRequest, GeneratedAudio, MultiSpeakerRequest, MultiSpeakerSegment,
-    ReferenceAudio, TaskStatus, VoiceDesignRequest,
+    ReferenceAudio, TaskStatus, TaskStatusResponse, VoiceDesignRequest,
 };
-use crate::audio::player::{AudioPlayer, PlaybackState};
+use crate::audio::player::{AudioPlayer, PlaybackState, PlaybackStatus, seek_position};
 use crate::audio::recorder::{Recorder, RecordingState};
 use crate::config::AppConfig;
-use crate::message::{ActiveTask, Message, TabId};
+use crate::history::HistoryEntry;
+use crate::message::{
+    ActiveTask, BusyReason, Message, PendingDelete, TabId, TaskRequest, UploadSnapshot,
+    UserAction, is_action_allowed,
+};
+use crate::preset::{PRESET_EXTENSION, Preset, PresetVoice, save_preset};
 use crate::server::manager::ServerManager;
-use crate::views::clone_tab::CloneTabState;
+use crate::transcribe::whisper::WhisperModel;
+use crate::views::clone_tab::{BatchLineStatus, CloneTabState};
 use crate::views::custom_tab::CustomTabState;
 use crate::views::design_tab::DesignTabState;
 use crate::views::multispeaker_tab::MultiSpeakerTabState;
 use crate::views::upload_tab::UploadTabState;
+use crate::views::waveform::WAVEFORM_COLUMNS;
+
+/// Target RMS for consistent-loudness playback (about -20 dBFS).
+const LOUDNESS_TARGET_RMS: f32 = 0.1;
+
+/// Peak level for normalized reference previews.
+const PREVIEW_PEAK_DB: f32 = -1.0;
+
+/// API address used before a server has been spawned.
+const DEFAULT_BASE_URL: &str = "http://127.0.0.1:8000";
+
+/// Seconds between checks, once ready, that the server is still alive.
+const SERVER_WATCH_SECS: u64 = 5;
+
+/// Generations allowed to run at once; further requests wait in the queue.
+const MAX_CONCURRENT_TASKS: usize = 3;
+
+/// How long a deleted generation can be restored before the server
+/// deletes it.
+const UNDO_DELETE_SECS: u64 = 8;
+
+/// How long an error stays in the banner list unless it is sticky.
+const ERROR_EXPIRE_SECS: u64 = 10;
 
 // ─── Screen state ───────────────────────────────────────────────
 
@@ -28,28 +62,100 @@ enum Screen {
     Main,
 }
 
+/// One row of the error banner list.
+#[derive(Debug, Clone)]
+struct ErrorEntry {
+    id: u64,
+    raised: Instant,
+    text: String,
+    /// Re-sends the request that failed, for transient API errors.
+    retry: Option<Message>,
+    /// Kept until dismissed instead of expiring after `ERROR_EXPIRE_SECS`.
+    sticky: bool,
+}
+
 // ─── Application state ─────────────────────────────────────────
 
 #[derive(Debug)]
 pub struct Qvox {
     screen: Screen,
     server: Option<ServerManager>,
+    /// Shared HTTP client for the current server address; cloned into tasks
+    /// so requests reuse one connection pool.
+    api: ApiClient,
     app_config: AppConfig,
     edit_config: AppConfig,
+    /// Where qvox keeps its files (`config::data_dir()`); tests point it at
+    /// a scratch directory.
+    data_dir: std::path::PathBuf,
     settings_dirty: bool,
+    /// Port field text while it isn't a valid port.
+    port_input: Option<String>,
+    /// Best device on this machine, once the startup probe has run.
+    detected_device: Option<&'static str>,
+    /// Device strings offered by the settings picker.
+    device_options: Vec<String>,
+    diagnostics: Option<Vec<crate::diagnostics::DiagnosticResult>>,
+    diagnostics_running: bool,
+    /// Disk usage shown in settings, rescanned when the tab opens.
+    storage: crate::views::settings::StorageUsage,
+    /// Bytes downloaded and total of the Whisper model being fetched.
+    model_download: Option<(u64, u64)>,
+    /// Set to stop the running transcription.
+    transcription_cancel: Option<Arc<AtomicBool>>,
     elapsed_secs: u64,
+    /// Loading has passed `slow_start_secs` without the server being ready.
+    slow_start: bool,
+    /// The server died after becoming ready and that has been reported.
+    server_lost: bool,
     loading_status: String,
-    error: Option<String>,
+    /// Model load failures the server reported while starting up.
+    model_load_errors: Vec<String>,
+    /// Server log panel expanded on the loading screen.
+    show_server_logs: bool,
+    /// Errors shown in the banner list, oldest first.
+    errors: Vec<ErrorEntry>,
+    next_error_id: u64,
+    /// Blocking operation in progress, if any.
+    busy: Option<BusyReason>,
+    /// Launched with `--safe-mode`: no server, generation disabled.
+    safe_mode: bool,
 
     // ─── Main screen state ──────────────────────────────────
     active_tab: TabId,
+    previous_tab: Option<TabId>,
+    /// `app_config` holds a tab switch not yet written to disk; it goes out
+    /// with the next save, or on exit.
+    active_tab_unsaved: bool,
     references: Vec<ReferenceAudio>,
+    /// Unsaved rename edits, keyed by reference id.
+    reference_name_drafts: HashMap<String, String>,
     languages: Vec<String>,
     available_models: Vec<String>,
+    /// Models the server reported as loaded by its health check; `None`
+    /// until it answers.
+    loaded_models: Option<Vec<String>>,
 
     // ─── Clone tab ──────────────────────────────────────────
     clone_tab: CloneTabState,
-    active_task: Option<ActiveTask>,
+    /// Running tasks, plus each tab's most recent finished one.
+    active_tasks: Vec<ActiveTask>,
+    /// Audio of the last completed generation from each tab, kept while
+    /// the tab starts new ones so it can still be replayed.
+    last_results: BTreeMap<TabId, Vec<u8>>,
+    /// Tabs whose requests have been sent but whose `task_id` hasn't arrived
+    /// yet, one entry per request.
+    submitting: Vec<TabId>,
+    /// Inputs of the Upload generation being submitted, moved onto its task
+    /// once the server answers.
+    submitting_upload: Option<UploadSnapshot>,
+    /// Round-trip time of the most recent generation submit.
+    last_submit_latency: Option<Duration>,
+    /// Requests waiting for a free task slot.
+    pending_requests: Vec<TaskRequest>,
+    /// Queue left over from the last session, awaiting Resume/Discard.
+    restored_requests: Vec<TaskRequest>,
+    queue_checked: bool,
 
     // ─── Upload tab ───────────────────────────────────────
     upload_tab: UploadTabState,
@@ -64,48 +170,160 @@ pub struct Qvox {
     // ─── Multi-Speaker tab ───────────────────────────────
     multi_tab: MultiSpeakerTabState,
 
+    // ─── History ─────────────────────────────────────────
+    /// Completed generations, oldest first (`history.json`).
+    history: Vec<HistoryEntry>,
+
     // ─── Generated list ──────────────────────────────────
     generated_list: Vec<GeneratedAudio>,
+    /// RMS level per generated clip id, computed on first playback.
+    loudness_cache: HashMap<String, f32>,
+    /// Case-insensitive query narrowing the generated list.
+    generated_filter: String,
+    generated_sort: crate::views::generated_list::SortMode,
+    /// Generated clip ids checked for a batch delete.
+    selected_generated: HashSet<String>,
+    /// Generated clip ids whose full text is shown.
+    expanded_generated: HashSet<String>,
+    /// Deletion awaiting confirmation.
+    pending_delete: Option<PendingDelete>,
+    /// Deleted clip hidden from the list but kept on the server until its
+    /// undo window (`UNDO_DELETE_SECS`) runs out.
+    last_deleted: Option<GeneratedAudio>,
+    /// Sequence number of the latest deletion, so an undo timer left over
+    /// from an earlier one is ignored.
+    delete_seq: u64,
+    last_export: Option<(usize, std::path::PathBuf)>,
 
     // ─── Audio playback / recording ─────────────────────
     player: Option<AudioPlayer>,
+    /// Repeat clips until stopped; survives the player being reopened.
+    loop_playback: bool,
+    /// Generated clip being played, so its row can show it.
+    playing_id: Option<String>,
     recorder: Option<Recorder>,
+    /// Input device names found at startup.
+    input_devices: Vec<String>,
+    /// Output device names found at startup.
+    output_devices: Vec<String>,
 }
 
 impl Default for Qvox {
     fn default() -> Self {
-        let config = crate::config::load();
+        let data_dir = crate::config::data_dir();
+        let config = crate::config::load(&data_dir);
+        let history = crate::history::load_history(&data_dir);
+        Self::with_data(data_dir, config, history)
+    }
+}
+
+impl Qvox {
+    /// Build the initial state around an already loaded config and history,
+    /// touching nothing on disk.
+    fn with_data(
+        data_dir: std::path::PathBuf,
+        config: AppConfig,
+        history: Vec<HistoryEntry>,
+    ) -> Self {
         Self {
             screen: Screen::Loading,
             server: None,
+            api: ApiClient::new(DEFAULT_BASE_URL),
             edit_config: config.clone(),
             app_config: config,
+            data_dir,
             settings_dirty: false,
+            port_input: None,
+            detected_device: None,
+            device_options: crate::server::manager::device_options(0),
+            diagnostics: None,
+            diagnostics_running: false,
+            storage: crate::views::settings::StorageUsage::default(),
+            model_download: None,
+            transcription_cancel: None,
             elapsed_secs: 0,
+            slow_start: false,
+            server_lost: false,
             loading_status: "Starting server...".to_owned(),
-            error: None,
+            model_load_errors: Vec::new(),
+            show_server_logs: false,
+            errors: Vec::new(),
+            next_error_id: 0,
+            busy: None,
+            safe_mode: false,
             active_tab: TabId::Clone,
+            previous_tab: None,
+            active_tab_unsaved: false,
             references: Vec::new(),
+            reference_name_drafts: HashMap::new(),
             languages: vec!["auto".to_owned()],
             available_models: Vec::new(),
+            loaded_models: None,
             clone_tab: CloneTabState::new(),
-            active_task: None,
+            active_tasks: Vec::new(),
+            last_results: BTreeMap::new(),
+            submitting: Vec::new(),
+            submitting_upload: None,
+            last_submit_latency: None,
+            pending_requests: Vec::new(),
+            restored_requests: Vec::new(),
+            queue_checked: false,
             upload_tab: UploadTabState::new(),
             design_tab: DesignTabState::new(),
             custom_tab: CustomTabState::new(),
             speakers: Vec::new(),
             multi_tab: MultiSpeakerTabState::new(),
+            history,
             generated_list: Vec::new(),
+            loudness_cache: HashMap::new(),
+            generated_filter: String::new(),
+            generated_sort: crate::views::generated_list::SortMode::default(),
+            selected_generated: HashSet::new(),
+            expanded_generated: HashSet::new(),
+            pending_delete: None,
+            last_deleted: None,
+            delete_seq: 0,
+            last_export: None,
             player: None,
+            loop_playback: false,
+            playing_id: None,
             recorder: None,
+            input_devices: Vec::new(),
+            output_devices: Vec::new(),
         }
     }
-}
 
-impl Qvox {
-    pub fn new() -> (Self, Task<Message>) {
-        let app = Self::default();
-        (app, Task::done(Message::ServerSpawned))
+    pub fn new(safe_mode: bool) -> (Self, Task<Message>) {
+        let mut app = Self::default();
+        app.set_input_devices(Recorder::list_input_devices());
+        app.output_devices = AudioPlayer::list_output_devices();
+        app.refresh_storage();
+        // `nvidia-smi` can take seconds, so probe off the UI thread.
+        let probe = Task::batch([
+            Task::perform(
+                async {
+                    tokio::task::spawn_blocking(crate::server::manager::detect_best_device)
+                        .await
+                        .ok()
+                },
+                Message::DeviceDetected,
+            ),
+            Task::perform(
+                async {
+                    tokio::task::spawn_blocking(crate::server::manager::detect_devices)
+                        .await
+                        .unwrap_or_else(|_| crate::server::manager::device_options(0))
+                },
+                Message::DevicesDetected,
+            ),
+        ]);
+        if safe_mode {
+            app.safe_mode = true;
+            app.screen = Screen::Main;
+            app.active_tab = TabId::Settings;
+            return (app, probe);
+        }
+        (app, Task::batch([probe, Task::done(Message::ServerSpawned)]))
     }
 
     #[allow(clippy::unused_self)]
@@ -122,99 +340,246 @@ impl Qvox {
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        if let Some(action) = message.user_action()
+            && !self.action_allowed(action)
+        {
+            return Task::none();
+        }
+
         match message {
             // ─── Server lifecycle ───────────────────────────
             Message::ServerSpawned
+            | Message::ExistingServerProbed(_)
             | Message::HealthCheck(_)
             | Message::ServerReady
             | Message::ServerError(_)
-            | Message::Tick => self.update_server(message),
+            | Message::ServerLogsToggled
+            | Message::ServerRestart
+            | Message::Tick
+            | Message::ServerWatchTick
+            | Message::AttachedServerProbed(_) => self.update_server(message),
 
             // ─── Data loading ───────────────────────────────
             Message::CapabilitiesLoaded(_)
+            | Message::HealthLoaded(_)
             | Message::ReferencesLoaded(_)
             | Message::LanguagesLoaded(_) => self.update_data(message),
 
+            // ─── Reference management ───────────────────────
+            Message::ReferenceNameChanged(_, _)
+            | Message::ReferenceRename(_, _)
+            | Message::ReferenceRenamed(_)
+            | Message::ReferenceDelete(_)
+            | Message::ReferenceDeleted(_)
+            | Message::ReferenceTranscribe(_)
+            | Message::ReferenceTranscribeLoaded(_) => self.update_references(message),
+
             // ─── Tab navigation ─────────────────────────────
             Message::TabSelected(tab) => {
-                self.active_tab = tab;
+                self.switch_tab(tab);
+                Task::none()
+            }
+            Message::TabBack => {
+                if let Some(prev) = self.previous_tab {
+                    self.switch_tab(prev);
+                }
                 Task::none()
             }
 
             // ─── Clone tab inputs ───────────────────────────
-            Message::CloneTextChanged(_)
+            Message::CloneTextAction(_)
             | Message::CloneRefSelected(_)
             | Message::CloneLanguageSelected(_)
-            | Message::CloneGenerate => self.update_clone(message),
+            | Message::CloneGenerate
+            | Message::CloneClear
+            | Message::CloneBatchToggled(_)
+            | Message::CloneBatchStop => self.update_clone(message),
 
             // ─── Design tab inputs ────────────────────────────
-            Message::DesignTextChanged(_)
+            Message::DesignTextAction(_)
             | Message::DesignInstructChanged(_)
             | Message::DesignLanguageSelected(_)
-            | Message::DesignGenerate => self.update_design(message),
+            | Message::DesignGenerate
+            | Message::DesignClear => self.update_design(message),
 
             // ─── Custom Voice tab inputs ─────────────────────
-            Message::CustomTextChanged(_)
+            Message::CustomTextAction(_)
             | Message::CustomSpeakerSelected(_)
             | Message::CustomLanguageSelected(_)
             | Message::CustomInstructChanged(_)
-            | Message::CustomGenerate => self.update_custom(message),
+            | Message::CustomGenerate
+            | Message::CustomClear => self.update_custom(message),
 
             // ─── Multi-Speaker tab inputs ─────────────────────
             Message::MultiAddSegment
             | Message::MultiRemoveSegment(_)
+            | Message::MultiMoveSegmentUp(_)
+            | Message::MultiMoveSegmentDown(_)
+            | Message::MultiDuplicateSegment(_)
+            | Message::MultiImportScript
+            | Message::MultiScriptLoaded(_)
             | Message::MultiRefSelected(_, _)
             | Message::MultiTextChanged(_, _)
             | Message::MultiLanguageSelected(_, _)
             | Message::MultiGenerate => self.update_multi(message),
 
             // ─── Task lifecycle ─────────────────────────────
-            Message::TaskCreated(_)
+            Message::TaskCreated(_, _, _)
             | Message::TaskPollTick
-            | Message::TaskProgress(_)
-            | Message::TaskAudioLoaded(_) => self.update_task(message),
+            | Message::TaskProgress(_, _)
+            | Message::TaskCancel(_)
+            | Message::TaskCancelled(_)
+            | Message::TaskAudioLoaded(_, _)
+            | Message::QueueResume
+            | Message::QueueDiscard => self.update_task(message),
 
             // ─── Upload tab inputs ─────────────────────────
             Message::UploadPickFile
             | Message::UploadFileSelected(_, _, _)
-            | Message::UploadTextChanged(_)
+            | Message::UploadTextAction(_)
             | Message::UploadLanguageSelected(_)
             | Message::UploadGenerate
+            | Message::UploadClear
+            | Message::UploadSaveReference
+            | Message::ReferenceUploaded(_)
             | Message::RecordStart
             | Message::RecordStop
             | Message::RecordTick
+            | Message::RecordDeviceSelected(_)
+            | Message::DownloadWhisperModel
             | Message::ModelDownloadProgress(_, _)
+            | Message::TranscriptionProgress(_)
             | Message::ModelDownloaded(_)
-            | Message::TranscriptionDone(_) => self.update_upload(message),
+            | Message::TranscriptionDone(..)
+            | Message::RetryTranscription
+            | Message::CancelTranscription
+            | Message::UploadRefTextChanged(_)
+            | Message::TranscriptionCacheSaved(_) => self.update_upload(message),
 
             // ─── Playback ─────────────────────────────────
             Message::PlayGenerated
             | Message::PlayReference(_)
+            | Message::PlayRecording
             | Message::ReferenceAudioFetched(_)
+            | Message::ReferencePreviewOffset(_, _)
             | Message::PlaybackPause
+            | Message::PlaybackSeek(_)
+            | Message::PlaybackToggleLoop
             | Message::PlaybackResume
             | Message::PlaybackStop
             | Message::PlaybackTick => self.update_playback(message),
 
+            // ─── Keyboard ──────────────────────────────────
+            Message::GenerateShortcut => match self.generate_shortcut() {
+                Some(generate) => self.update(generate),
+                None => Task::none(),
+            },
+            Message::EscapePressed => {
+                self.stop_playback();
+                self.errors.clear();
+                let running = self
+                    .task_for(self.active_tab)
+                    .filter(|t| t.status == TaskStatus::Processing)
+                    .map(|t| t.task_id.clone());
+                match running {
+                    Some(task_id) => self.update(Message::TaskCancel(task_id)),
+                    None => Task::none(),
+                }
+            }
+
+            Message::WindowCloseRequested(id) => self.close_window(id),
+
+            // ─── History ───────────────────────────────────
+            Message::HistoryRerun(index) => {
+                self.rerun_history(index);
+                Task::none()
+            }
+
             // ─── Generated list ─────────────────────────────
             Message::GeneratedListLoaded(_)
             | Message::RefreshGeneratedList
             | Message::GeneratedPlay(_)
+            | Message::GeneratedFilterChanged(_)
+            | Message::GeneratedSortChanged(_)
+            | Message::GeneratedToggleSelect(_)
+            | Message::GeneratedSelectAllVisible
+            | Message::GeneratedDeleteSelected
+            | Message::GeneratedToggleExpand(_)
             | Message::GeneratedAudioFetched(_)
+            | Message::GeneratedRegenerate(_)
             | Message::GeneratedDelete(_)
-            | Message::GeneratedDeleted(_) => self.update_generated(message),
+            | Message::DeleteConfirm
+            | Message::DeleteCancel
+            | Message::GeneratedDeleteCommit(_)
+            | Message::GeneratedUndoDelete
+            | Message::GeneratedDeleted(_)
+            | Message::GeneratedExport(_)
+            | Message::GeneratedExportAll
+            | Message::ExportIncludeReferenceToggled(_)
+            | Message::ExportNormalizeToggled(_)
+            | Message::ExportPeakChanged(_)
+            | Message::ExportDone(_)
+            | Message::ExportAllDone(..) => self.update_generated(message),
+
+            // ─── Presets ───────────────────────────────────────
+            Message::PresetImport
+            | Message::PresetLoaded(_)
+            | Message::PresetExport(_)
+            | Message::PresetSaved(_)
+            | Message::PresetReferenceUploaded(_) => self.update_preset(message),
 
             // ─── Settings ──────────────────────────────────────
             Message::SettingsModelToggled(_)
             | Message::SettingsDeviceChanged(_)
             | Message::SettingsPortChanged(_)
+            | Message::SettingsUseExistingServerToggled(_)
             | Message::SettingsScriptPathChanged(_)
+            | Message::SettingsPythonPathChanged(_)
             | Message::SettingsDarkModeToggled(_)
-            | Message::SettingsSave => self.update_settings(message),
+            | Message::SettingsConsistentLoudnessToggled(_)
+            | Message::SettingsNormalizePreviewToggled(_)
+            | Message::SettingsConvertUploadsToggled(_)
+            | Message::SettingsEnterToGenerateToggled(_)
+            | Message::SettingsStopOnTabSwitchToggled(_)
+            | Message::SettingsAutoNormalizeToggled(_)
+            | Message::SettingsAutoTrimSilenceToggled(_)
+            | Message::SettingsSilenceThresholdChanged(_)
+            | Message::SettingsTrimLongReferencesToggled(_)
+            | Message::SettingsMaxReferenceSecsChanged(_)
+            | Message::ConfigSliderReleased
+            | Message::SettingsWhisperModelSelected(_)
+            | Message::SettingsOutputDeviceSelected(_)
+            | Message::SettingsModelSizeChanged(_)
+            | Message::SettingsSave
+            | Message::SettingsReset
+            | Message::ClearTranscriptionCache
+            | Message::DeleteModels
+            | Message::DeleteModelsConfirm
+            | Message::DeleteModelsCancel
+            | Message::OpenSettings
+            | Message::RevealPath(_)
+            | Message::CopyToClipboard(_)
+            | Message::DiagnosticsRun
+            | Message::DiagnosticsDone(_)
+            | Message::DeviceDetected(_)
+            | Message::DevicesDetected(_) => self.update_settings(message),
 
             // ─── Error ─────────────────────────────────────────
-            Message::ErrorDismiss => {
-                self.error = None;
+            Message::ErrorDismiss(id) => {
+                self.errors.retain(|e| e.id != id);
+                Task::none()
+            }
+            Message::ErrorRetry(id) => {
+                let Some(pos) = self.errors.iter().position(|e| e.id == id) else {
+                    return Task::none();
+                };
+                match self.errors.remove(pos).retry {
+                    Some(retry) => Task::done(retry),
+                    None => Task::none(),
+                }
+            }
+            Message::ErrorExpireTick => {
+                self.expire_errors(Instant::now());
                 Task::none()
             }
         }
@@ -228,19 +593,38 @@ impl Qvox {
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        let is_loading = matches!(&self.screen, Screen::Loading) && self.error.is_none();
+        let is_loading = matches!(&self.screen, Screen::Loading) && self.errors.is_empty();
         let is_task_polling = self
-            .active_task
-            .as_ref()
-            .is_some_and(|t| t.status == TaskStatus::Processing);
+            .active_tasks
+            .iter()
+            .any(|t| t.status == TaskStatus::Processing);
         let is_recording = self.recording_state() == RecordingState::Recording;
-        let is_playing = self.playback_state() == PlaybackState::Playing;
+        let is_playing = self.playback_state() == PlaybackState::Playing
+            || self.player.as_ref().is_some_and(AudioPlayer::loop_pending);
 
-        let mut subs = Vec::new();
+        let mut subs = vec![
+            iced::keyboard::on_key_press(|key, modifiers| {
+                use iced::keyboard::{Key, key::Named};
+                match key {
+                    Key::Named(Named::Enter) if modifiers.command() => {
+                        Some(Message::GenerateShortcut)
+                    }
+                    Key::Named(Named::Escape) => Some(Message::EscapePressed),
+                    _ => None,
+                }
+            }),
+            iced::window::close_requests().map(Message::WindowCloseRequested),
+        ];
 
         if is_loading {
             subs.push(iced::time::every(Duration::from_secs(1)).map(|_| Message::Tick));
         }
+        if matches!(self.screen, Screen::Main) && self.server.is_some() && !self.server_lost {
+            subs.push(
+                iced::time::every(Duration::from_secs(SERVER_WATCH_SECS))
+                    .map(|_| Message::ServerWatchTick),
+            );
+        }
         if is_task_polling {
             subs.push(iced::time::every(Duration::from_secs(1)).map(|_| Message::TaskPollTick));
         }
@@ -250,6 +634,9 @@ impl Qvox {
         if is_playing {
             subs.push(iced::time::every(Duration::from_millis(250)).map(|_| Message::PlaybackTick));
         }
+        if self.errors.iter().any(|e| !e.sticky) {
+            subs.push(iced::time::every(Duration::from_secs(1)).map(|_| Message::ErrorExpireTick));
+        }
 
         Subscription::batch(subs)
     }
@@ -258,19 +645,37 @@ impl Qvox {
 
     fn update_server(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::ServerSpawned => match ServerManager::spawn(&self.app_config.to_server_config()) {
-                Ok(mgr) => {
-                    self.server = Some(mgr);
-                    "Waiting for server...".clone_into(&mut self.loading_status);
-                    self.poll_health()
+            Message::ServerSpawned => {
+                if !self.app_config.server.use_existing_server {
+                    return self.spawn_server();
                 }
-                Err(e) => {
-                    self.error = Some(e.to_string());
-                    self.loading_status = format!("Error: {e}");
-                    Task::none()
+                "Looking for a running server...".clone_into(&mut self.loading_status);
+                let hosts =
+                    crate::server::manager::loopback_candidates(&self.app_config.server.bind_host);
+                let port = self.app_config.server.port;
+                Task::perform(
+                    async move { crate::server::manager::probe_health(&hosts, port).await },
+                    |answer| Message::ExistingServerProbed(answer.map(|(host, _)| host)),
+                )
+            }
+            Message::ExistingServerProbed(Some(host)) => {
+                self.server = Some(ServerManager::attach(&host, self.app_config.server.port));
+                self.refresh_api_client();
+                "Connected to a running server...".clone_into(&mut self.loading_status);
+                self.poll_health()
+            }
+            Message::ExistingServerProbed(None) => self.spawn_server(),
+            Message::HealthCheck(answer) => {
+                let ready = answer.as_ref().is_some_and(|(_, health)| health.voice_cloner_loaded);
+                if let Some((_, health)) = &answer {
+                    self.model_load_errors = health.model_load_errors.clone().unwrap_or_default();
+                }
+                if let (Some((host, _)), Some(mgr)) = (answer, &mut self.server)
+                    && mgr.host() != host
+                {
+                    mgr.set_host(host);
+                    self.refresh_api_client();
                 }
-            },
-            Message::HealthCheck(ready) => {
                 if ready {
                     Task::done(Message::ServerReady)
                 } else {
@@ -283,26 +688,77 @@ impl Qvox {
                 }
             }
             Message::ServerReady => {
+                // Only on launch; a restart from settings stays where it is.
+                if matches!(self.screen, Screen::Loading)
+                    && self.previous_tab.is_none()
+                    && self.active_tab == TabId::Clone
+                {
+                    self.active_tab = self.app_config.ui.active_tab;
+                }
                 self.screen = Screen::Main;
                 "Ready".clone_into(&mut self.loading_status);
                 self.load_initial_data()
             }
             Message::ServerError(e) => {
-                self.error = Some(e.clone());
+                self.push_sticky_error(e.clone());
                 self.loading_status = format!("Error: {e}");
                 Task::none()
             }
+            Message::ServerRestart => self.restart_server(),
+            Message::ServerWatchTick => {
+                if self.server_lost {
+                    return Task::none();
+                }
+                let Some(mgr) = &mut self.server else {
+                    return Task::none();
+                };
+                if mgr.is_attached() {
+                    let hosts = mgr.health_hosts();
+                    let port = mgr.port();
+                    return Task::perform(
+                        async move { crate::server::manager::probe_health(&hosts, port).await },
+                        |answer| Message::AttachedServerProbed(answer.is_some()),
+                    );
+                }
+                if !mgr.is_running() {
+                    self.server_lost = true;
+                    self.push_sticky_error(
+                        "Server process exited unexpectedly. Restart it from Settings.".to_owned(),
+                    );
+                }
+                Task::none()
+            }
+            Message::AttachedServerProbed(answered) => {
+                if !answered && !self.server_lost && self.server.is_some() {
+                    self.server_lost = true;
+                    self.push_sticky_error(
+                        "The running server stopped answering. Restart it from Settings."
+                            .to_owned(),
+                    );
+                }
+                Task::none()
+            }
+            Message::ServerLogsToggled => {
+                self.show_server_logs = !self.show_server_logs;
+                Task::none()
+            }
             Message::Tick => {
                 self.elapsed_secs += 1;
+                if !self.slow_start && self.elapsed_secs >= self.app_config.server.slow_start_secs {
+                    self.slow_start = true;
+                    self.show_server_logs = true;
+                }
                 if let Some(ref mut mgr) = self.server {
-                    if self.error.is_none() {
+                    if self.errors.is_empty() {
                         if mgr.is_running() {
                             self.poll_health()
                         } else {
-                            self.error = Some(
-                                "Server process exited unexpectedly. Check the terminal for details.".to_owned(),
+                            self.push_sticky_error(
+                                "Server process exited unexpectedly. See the server log below."
+                                    .to_owned(),
                             );
                             self.loading_status = "Error: server crashed".to_owned();
+                            self.show_server_logs = true;
                             Task::none()
                         }
                     } else {
@@ -316,27 +772,172 @@ impl Qvox {
         }
     }
 
+    /// Start a server of our own with `app_config` and poll it until ready.
+    fn spawn_server(&mut self) -> Task<Message> {
+        match ServerManager::spawn(&self.app_config.to_server_config()) {
+            Ok(mgr) => {
+                self.server = Some(mgr);
+                self.refresh_api_client();
+                "Waiting for server...".clone_into(&mut self.loading_status);
+                self.poll_health()
+            }
+            Err(e) => {
+                self.push_sticky_error(e.to_string());
+                self.loading_status = format!("Error: {e}");
+                Task::none()
+            }
+        }
+    }
+
     fn update_data(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::CapabilitiesLoaded(Ok(caps)) => {
                 self.available_models = caps.models;
                 self.speakers = caps.speakers;
             }
+            Message::HealthLoaded(Ok(health)) => {
+                self.loaded_models = Some(health.loaded_models);
+            }
             Message::ReferencesLoaded(Ok(refs)) => {
                 self.references = refs;
+                self.clear_missing_selections();
+                if !self.queue_checked {
+                    self.queue_checked = true;
+                    self.restore_queue();
+                }
             }
             Message::LanguagesLoaded(Ok(langs)) => {
                 self.languages = langs.languages;
+                self.restore_languages();
             }
             _ => {}
         }
         Task::none()
     }
 
+    fn update_references(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::ReferenceTranscribe(id) => {
+                let Some(name) = self
+                    .references
+                    .iter()
+                    .find(|r| r.id == id)
+                    .map(|r| r.display_name().to_owned())
+                else {
+                    return Task::none();
+                };
+                let client = self.api.clone();
+                Task::perform(
+                    async move {
+                        client
+                            .reference_audio(&id)
+                            .await
+                            .map(|bytes| (name, bytes))
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::ReferenceTranscribeLoaded,
+                )
+            }
+            Message::ReferenceTranscribeLoaded(Ok((name, bytes))) => {
+                self.select_tab(TabId::Upload);
+                self.select_upload_audio(name.clone().into(), bytes, name, false)
+            }
+            Message::ReferenceTranscribeLoaded(Err(e)) => {
+                self.push_error(format!("Failed to load the reference: {e}"));
+                Task::none()
+            }
+            Message::ReferenceNameChanged(id, name) => {
+                self.reference_name_drafts.insert(id, name);
+                Task::none()
+            }
+            Message::ReferenceRename(id, name) => {
+                let name = name.trim().to_owned();
+                if name.is_empty() {
+                    self.push_error("Reference name cannot be empty".to_owned());
+                    return Task::none();
+                }
+                let client = self.api.clone();
+                Task::perform(
+                    async move {
+                        client
+                            .rename_reference(&id, &name)
+                            .await
+                            .map(|resp| (id, resp.name))
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::ReferenceRenamed,
+                )
+            }
+            Message::ReferenceRenamed(Ok((id, name))) => {
+                self.reference_name_drafts.remove(&id);
+                self.apply_reference_rename(&id, name);
+                Task::none()
+            }
+            Message::ReferenceRenamed(Err(e)) => {
+                self.push_error(format!("Failed to rename reference: {e}"));
+                Task::none()
+            }
+            Message::ReferenceDelete(id) => {
+                self.pending_delete = Some(PendingDelete::Reference(id));
+                Task::none()
+            }
+            Message::ReferenceDeleted(Ok(id)) => {
+                self.references.retain(|r| r.id != id);
+                self.reference_name_drafts.remove(&id);
+                self.clear_missing_selections();
+                Task::none()
+            }
+            Message::ReferenceDeleted(Err(e)) => {
+                self.push_error(format!("Failed to delete reference: {e}"));
+                Task::none()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Rename reference `id` in place, carrying over any clone or
+    /// multi-speaker selections that pointed at its old name.
+    fn apply_reference_rename(&mut self, id: &str, name: String) {
+        let Some(reference) = self.references.iter_mut().find(|r| r.id == id) else {
+            return;
+        };
+        let old = reference.display_name().to_owned();
+        reference.name = Some(name.clone());
+
+        if self.clone_tab.selected_ref.as_ref() == Some(&old) {
+            self.clone_tab.selected_ref = Some(name.clone());
+        }
+        for seg in &mut self.multi_tab.segments {
+            if seg.selected_ref.as_ref() == Some(&old) {
+                seg.selected_ref = Some(name.clone());
+            }
+        }
+    }
+
+    /// Clear clone and multi-speaker selections whose reference no longer
+    /// exists, so generation never submits a dangling id.
+    fn clear_missing_selections(&mut self) {
+        let names: Vec<&str> = self
+            .references
+            .iter()
+            .map(ReferenceAudio::display_name)
+            .collect();
+        let missing = |sel: Option<&str>| sel.is_some_and(|s| !names.contains(&s));
+
+        if missing(self.clone_tab.selected_ref.as_deref()) {
+            self.clone_tab.selected_ref = None;
+        }
+        for seg in &mut self.multi_tab.segments {
+            if missing(seg.selected_ref.as_deref()) {
+                seg.selected_ref = None;
+            }
+        }
+    }
+
     fn update_clone(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::CloneTextChanged(t) => {
-                self.clone_tab.text = t;
+            Message::CloneTextAction(action) => {
+                self.clone_tab.editor.perform(action);
                 Task::none()
             }
             Message::CloneRefSelected(name) => {
@@ -344,18 +945,31 @@ impl Qvox {
                 Task::none()
             }
             Message::CloneLanguageSelected(lang) => {
+                self.remember_language(TabId::Clone, &lang);
                 self.clone_tab.selected_language = lang;
                 Task::none()
             }
             Message::CloneGenerate => self.start_clone_generation(),
+            Message::CloneClear => {
+                self.clone_tab.clear();
+                Task::none()
+            }
+            Message::CloneBatchToggled(on) => {
+                self.clone_tab.batch_mode = on;
+                Task::none()
+            }
+            Message::CloneBatchStop => {
+                self.stop_clone_batch();
+                Task::none()
+            }
             _ => Task::none(),
         }
     }
 
     fn update_design(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::DesignTextChanged(t) => {
-                self.design_tab.text = t;
+            Message::DesignTextAction(action) => {
+                self.design_tab.editor.perform(action);
                 Task::none()
             }
             Message::DesignInstructChanged(t) => {
@@ -363,18 +977,23 @@ impl Qvox {
                 Task::none()
             }
             Message::DesignLanguageSelected(lang) => {
+                self.remember_language(TabId::VoiceDesign, &lang);
                 self.design_tab.selected_language = lang;
                 Task::none()
             }
             Message::DesignGenerate => self.start_design_generation(),
+            Message::DesignClear => {
+                self.design_tab.clear();
+                Task::none()
+            }
             _ => Task::none(),
         }
     }
 
     fn update_custom(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::CustomTextChanged(t) => {
-                self.custom_tab.text = t;
+            Message::CustomTextAction(action) => {
+                self.custom_tab.editor.perform(action);
                 Task::none()
             }
             Message::CustomSpeakerSelected(s) => {
@@ -382,6 +1001,7 @@ impl Qvox {
                 Task::none()
             }
             Message::CustomLanguageSelected(lang) => {
+                self.remember_language(TabId::CustomVoice, &lang);
                 self.custom_tab.selected_language = lang;
                 Task::none()
             }
@@ -390,6 +1010,10 @@ impl Qvox {
                 Task::none()
             }
             Message::CustomGenerate => self.start_custom_generation(),
+            Message::CustomClear => {
+                self.custom_tab.clear();
+                Task::none()
+            }
             _ => Task::none(),
         }
     }
@@ -408,8 +1032,70 @@ impl Qvox {
                 }
                 Task::none()
             }
+            Message::MultiMoveSegmentUp(i) => {
+                if i > 0 && i < self.multi_tab.segments.len() {
+                    self.multi_tab.segments.swap(i - 1, i);
+                }
+                Task::none()
+            }
+            Message::MultiMoveSegmentDown(i) => {
+                if i + 1 < self.multi_tab.segments.len() {
+                    self.multi_tab.segments.swap(i, i + 1);
+                }
+                Task::none()
+            }
+            Message::MultiImportScript => {
+                let ref_names: Vec<String> = self
+                    .references
+                    .iter()
+                    .map(|r| r.display_name().to_owned())
+                    .collect();
+                Task::perform(
+                    async move {
+                        let Some(file) = rfd::AsyncFileDialog::new()
+                            .add_filter("Text", &["txt"])
+                            .set_title("Import dialogue script")
+                            .pick_file()
+                            .await
+                        else {
+                            return Err(String::new());
+                        };
+                        let contents = String::from_utf8(file.read().await)
+                            .map_err(|_| "Script is not valid UTF-8 text".to_owned())?;
+                        let segments =
+                            crate::views::multispeaker_tab::parse_script(&contents, &ref_names);
+                        if segments.is_empty() {
+                            Err("Script has no lines to import".to_owned())
+                        } else {
+                            Ok(segments)
+                        }
+                    },
+                    Message::MultiScriptLoaded,
+                )
+            }
+            Message::MultiScriptLoaded(Ok(segments)) => {
+                self.multi_tab.segments = segments;
+                Task::none()
+            }
+            Message::MultiScriptLoaded(Err(e)) => {
+                if !e.is_empty() {
+                    self.push_error(format!("Script import failed: {e}"));
+                }
+                Task::none()
+            }
+            Message::MultiDuplicateSegment(i) => {
+                if let Some(seg) = self.multi_tab.segments.get(i) {
+                    let copy = crate::views::multispeaker_tab::SegmentState {
+                        text: String::new(),
+                        ..seg.clone()
+                    };
+                    self.multi_tab.segments.insert(i + 1, copy);
+                }
+                Task::none()
+            }
             Message::MultiRefSelected(i, name) => {
                 if let Some(seg) = self.multi_tab.segments.get_mut(i) {
+                    seg.unmatched_speaker = None;
                     seg.selected_ref = Some(name);
                 }
                 Task::none()
@@ -422,6 +1108,7 @@ impl Qvox {
             }
             Message::MultiLanguageSelected(i, lang) => {
                 if let Some(seg) = self.multi_tab.segments.get_mut(i) {
+                    self.remember_language(TabId::MultiSpeaker, &lang);
                     seg.selected_language = lang;
                 }
                 Task::none()
@@ -457,72 +1144,168 @@ impl Qvox {
                 },
             ),
             Message::UploadFileSelected(path, bytes, name) => {
-                let hash = crate::audio::hash::bytes_sha256(&bytes);
-
-                // Check transcription cache
-                let cached = crate::transcribe::whisper::cached_transcription(&hash);
-
-                self.upload_tab.selected_file = Some(path);
-                self.upload_tab.file_bytes = Some(bytes.clone());
-                self.upload_tab.file_name = Some(name);
-                self.upload_tab.file_hash = Some(hash.clone());
-
-                if let Some(text) = cached {
-                    self.upload_tab.ref_text = Some(text);
-                    Task::none()
-                } else {
-                    // Start transcription in background
-                    self.upload_tab.transcribing = true;
-                    self.upload_tab.ref_text = None;
-                    self.start_transcription(bytes, hash)
-                }
+                self.select_upload_audio(path, bytes, name, false)
             }
-            Message::TranscriptionDone(result) => {
+            Message::TranscriptionDone(hash, outcome) => {
+                // The worker has stopped, so another run may start.
+                self.transcription_cancel = None;
+                if self.busy == Some(BusyReason::Transcribing) {
+                    self.busy = None;
+                }
+                // A cancelled run, or one for audio that has since been replaced, changes nothing.
+                let Some(result) = outcome else {
+                    return Task::none();
+                };
+                if self.upload_tab.file_hash.as_deref() != Some(hash.as_str()) {
+                    return Task::none();
+                }
                 self.upload_tab.transcribing = false;
                 match result {
                     Ok(text) => self.upload_tab.ref_text = Some(text),
-                    Err(e) => self.error = Some(format!("Transcription failed: {e}")),
+                    Err(e) => self.push_error(format!("Transcription failed: {e}")),
+                }
+                Task::none()
+            }
+            Message::UploadRefTextChanged(text) => {
+                if self.upload_tab.transcribing {
+                    return Task::none();
+                }
+                self.upload_tab.ref_text = Some(text);
+                self.upload_tab.ref_text_edited = true;
+                Task::none()
+            }
+            Message::TranscriptionCacheSaved(result) => {
+                if let Err(e) = result {
+                    tracing::warn!("failed to save edited transcription: {e}");
+                    self.push_error(format!("Failed to save the edited transcription: {e}"));
+                }
+                Task::none()
+            }
+            Message::RetryTranscription => {
+                if self.upload_tab.transcribing
+                    || self.upload_tab.ref_text.is_some()
+                    || self.busy.is_some()
+                {
+                    return Task::none();
                 }
+                match (
+                    self.upload_tab.file_bytes.clone(),
+                    self.upload_tab.file_hash.clone(),
+                ) {
+                    (Some(bytes), Some(hash)) => self.start_transcription(bytes, hash),
+                    _ => Task::none(),
+                }
+            }
+            Message::CancelTranscription => {
+                // Stay busy until the worker notices and reports back.
+                if let Some(cancel) = &self.transcription_cancel {
+                    cancel.store(true, Ordering::Relaxed);
+                }
+                self.upload_tab.transcribing = false;
+                self.upload_tab.transcription_progress = 0;
+                self.upload_tab.ref_text = None;
+                Task::none()
+            }
+            Message::DownloadWhisperModel => {
+                if self.busy.is_some() {
+                    return Task::none();
+                }
+                self.download_whisper_model(self.app_config.audio.whisper_model)
+            }
+            Message::ModelDownloaded(result) => {
+                self.model_download = None;
+                self.refresh_storage();
+                if let Err(e) = result {
+                    self.upload_tab.transcribing = false;
+                    self.busy = None;
+                    self.push_error(format!("Whisper model download failed: {e}"));
+                    return Task::none();
+                }
+                // Only a download started by transcription resumes it.
+                match (
+                    self.upload_tab.transcribing,
+                    self.upload_tab.file_bytes.clone(),
+                    self.upload_tab.file_hash.clone(),
+                ) {
+                    (true, Some(bytes), Some(hash)) => self.start_transcription(bytes, hash),
+                    _ => {
+                        self.upload_tab.transcribing = false;
+                        self.busy = None;
+                        Task::none()
+                    }
+                }
+            }
+            Message::TranscriptionProgress(percent) => {
+                self.upload_tab.transcription_progress = percent;
                 Task::none()
             }
-            Message::ModelDownloadProgress(_, _) | Message::ModelDownloaded(_) => {
-                // Model download progress is handled silently for now;
-                // the transcription task chains download → transcribe.
+            Message::ModelDownloadProgress(downloaded, total) => {
+                self.model_download = Some((downloaded, total));
                 Task::none()
             }
-            Message::UploadTextChanged(t) => {
-                self.upload_tab.text = t;
+            Message::UploadTextAction(action) => {
+                self.upload_tab.editor.perform(action);
                 Task::none()
             }
             Message::UploadLanguageSelected(lang) => {
+                self.remember_language(TabId::Upload, &lang);
                 self.upload_tab.selected_language = lang;
                 Task::none()
             }
             Message::UploadGenerate => self.start_upload_generation(),
+            Message::UploadClear => {
+                if self.upload_tab.can_clear()
+                    && self.recording_state() == RecordingState::Idle
+                {
+                    self.upload_tab.clear();
+                }
+                Task::none()
+            }
+            Message::UploadSaveReference => self.save_upload_reference(),
+            Message::ReferenceUploaded(Ok(reference)) => {
+                self.upload_tab.saving_reference = false;
+                self.references.push(reference);
+                Task::none()
+            }
+            Message::ReferenceUploaded(Err(e)) => {
+                self.upload_tab.saving_reference = false;
+                self.push_error(format!("Failed to save reference: {e}"));
+                Task::none()
+            }
             Message::RecordStart => {
                 self.ensure_recorder();
                 if let Some(rec) = &mut self.recorder
                     && let Err(e) = rec.start()
                 {
-                    self.error = Some(format!("Recording error: {e}"));
+                    self.push_error(format!("Recording error: {e}"));
                 }
                 Task::none()
             }
             Message::RecordStop => {
                 if let Some(rec) = &mut self.recorder {
-                    let samples = rec.stop();
+                    let mut samples = rec.stop();
                     let sample_rate = rec.sample_rate();
                     if !samples.is_empty() {
+                        crate::audio::processing::clean_recording(
+                            &mut samples,
+                            sample_rate,
+                            self.app_config
+                                .ui
+                                .auto_trim_silence
+                                .then(|| self.app_config.ui.silence_threshold_db()),
+                            self.app_config.ui.auto_normalize,
+                        );
                         match crate::audio::recorder::samples_to_wav(&samples, sample_rate) {
                             Ok(wav_bytes) => {
                                 let name = "recording.wav".to_owned();
-                                return Task::done(Message::UploadFileSelected(
+                                return self.select_upload_audio(
                                     std::path::PathBuf::from(&name),
                                     wav_bytes,
                                     name,
-                                ));
+                                    true,
+                                );
                             }
-                            Err(e) => self.error = Some(format!("WAV encode error: {e}")),
+                            Err(e) => self.push_error(format!("WAV encode error: {e}")),
                         }
                     }
                 }
@@ -532,55 +1315,138 @@ impl Qvox {
                 // Just triggers a view refresh via subscription
                 Task::none()
             }
+            Message::RecordDeviceSelected(name) => {
+                if self.recording_state() == RecordingState::Recording {
+                    return Task::none();
+                }
+                self.edit_config.audio.input_device = Some(name.clone());
+                self.app_config.audio.input_device = Some(name);
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                self.upload_tab.input_device_notice = None;
+                // Reopen on the next recording with the new device.
+                self.recorder = None;
+                Task::none()
+            }
             _ => Task::none(),
         }
     }
 
     fn update_task(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::TaskCreated(result) => {
+            Message::TaskCreated(tab, request, result) => {
+                if let Some(pos) = self.submitting.iter().position(|t| *t == tab) {
+                    self.submitting.remove(pos);
+                }
+                if let Some(TaskRequest::Clone(r)) = &request
+                    && let Some(line_id) = r.batch_line
+                {
+                    let task_id = result.as_ref().ok().map(|(id, _, _)| id.clone());
+                    self.clone_tab.batch_task_created(line_id, task_id);
+                }
+                let upload = if tab == TabId::Upload {
+                    self.submitting_upload.take()
+                } else {
+                    None
+                };
                 match result {
-                    Ok(task_id) => self.active_task = Some(ActiveTask::new(task_id)),
-                    Err(e) => self.error = Some(e),
+                    Ok((task_id, estimated_secs, latency)) => {
+                        self.last_submit_latency = Some(latency);
+                        // The tab now shows this task, so drop its finished ones.
+                        self.active_tasks
+                            .retain(|t| t.tab != tab || t.status == TaskStatus::Processing);
+                        let mut task = ActiveTask::new(task_id, tab);
+                        task.request = request;
+                        task.upload = upload;
+                        task.estimated_secs = estimated_secs;
+                        self.active_tasks.push(task);
+                        Task::none()
+                    }
+                    Err(e) => {
+                        self.push_error(e);
+                        self.dispatch_next_queued()
+                    }
                 }
-                Task::none()
             }
             Message::TaskPollTick => {
-                if let Some(task) = &mut self.active_task {
-                    task.elapsed_secs += 1;
+                for task in &mut self.active_tasks {
+                    if task.status == TaskStatus::Processing {
+                        task.elapsed_secs += 1;
+                    }
                 }
-                self.poll_task()
+                self.poll_tasks()
             }
-            Message::TaskProgress(result) => match result {
-                Ok(resp) => {
-                    if let Some(task) = &mut self.active_task {
-                        task.update_progress(&resp);
-                        if resp.status == TaskStatus::Completed {
-                            return self.fetch_task_audio();
-                        }
-                    }
-                    Task::none()
+            Message::TaskProgress(task_id, result) => {
+                // Ignore polls that land after the task was cancelled or replaced.
+                if !self.is_polling(&task_id) {
+                    return Task::none();
                 }
-                Err(e) => {
-                    if let Some(task) = &mut self.active_task {
-                        task.error = Some(e);
-                    }
-                    Task::none()
+                self.apply_task_progress(&task_id, result)
+            }
+            Message::TaskCancel(task_id) => {
+                if !self.is_polling(&task_id) {
+                    return Task::none();
                 }
-            },
-            Message::TaskAudioLoaded(result) => {
-                if let Some(task) = &mut self.active_task {
+                let client = self.api.clone();
+                Task::perform(
+                    async move {
+                        client
+                            .cancel_task(&task_id)
+                            .await
+                            .map(|_| task_id)
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::TaskCancelled,
+                )
+            }
+            Message::TaskCancelled(Ok(task_id)) => {
+                if !self.is_polling(&task_id) {
+                    return Task::none();
+                }
+                if let Some(task) = self.task_mut(&task_id) {
+                    task.status = TaskStatus::Cancelled;
+                    "Generation cancelled".clone_into(&mut task.status_text);
+                }
+                self.clone_tab.batch_task_finished(&task_id, BatchLineStatus::Stopped);
+                self.dispatch_next_queued()
+            }
+            Message::TaskCancelled(Err(e)) => {
+                self.push_error(format!("Failed to cancel: {e}"));
+                Task::none()
+            }
+            Message::TaskAudioLoaded(task_id, result) => {
+                if let Some(task) = self.task_mut(&task_id) {
                     match &result {
-                        Ok(data) => task.audio_data = Some(data.clone()),
+                        Ok(data) => {
+                            let tab = task.tab;
+                            self.last_results.insert(tab, data.clone());
+                        }
                         Err(e) => task.error = Some(e.clone()),
                     }
                 }
                 if result.is_ok() {
-                    self.fetch_generated_list()
+                    self.record_history(&task_id);
+                }
+                // Fill the slot only once this task's audio has landed, so
+                // the tab keeps showing it until then.
+                let next = self.dispatch_next_queued();
+                if result.is_ok() {
+                    Task::batch([self.fetch_generated_list(), next])
                 } else {
-                    Task::none()
+                    next
                 }
             }
+            Message::QueueResume => {
+                let restored = std::mem::take(&mut self.restored_requests);
+                self.pending_requests.extend(restored);
+                self.persist_queue();
+                self.dispatch_next_queued()
+            }
+            Message::QueueDiscard => {
+                self.restored_requests.clear();
+                self.persist_queue();
+                Task::none()
+            }
             _ => Task::none(),
         }
     }
@@ -588,34 +1454,52 @@ impl Qvox {
     fn update_playback(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::PlayGenerated => {
-                if let Some(data) = self
-                    .active_task
-                    .as_ref()
-                    .and_then(|t| t.audio_data.clone())
-                {
+                if let Some(data) = self.last_results.get(&self.active_tab).cloned() {
                     self.play_audio(data);
                 }
                 Task::none()
             }
             Message::PlayReference(ref_id) => {
-                let base_url = self.api_base_url();
+                let client = self.api.clone();
                 Task::perform(
                     async move {
-                        ApiClient::new(&base_url)
+                        client
                             .reference_audio(&ref_id)
                             .await
+                            .map(|data| (ref_id, data))
                             .map_err(|e| e.to_string())
                     },
                     Message::ReferenceAudioFetched,
                 )
             }
-            Message::ReferenceAudioFetched(Ok(data)) => {
-                self.play_audio(data);
+            Message::ReferenceAudioFetched(Ok((ref_id, data))) => {
+                let start = self.reference_preview_start(&ref_id, &data);
+                let data = self.reference_preview_audio(data);
+                self.play_audio_with(data, 1.0, start);
                 Task::none()
             }
-            Message::ReferenceAudioFetched(Err(e)) => {
-                self.error = Some(e);
-                Task::none()
+            Message::ReferencePreviewOffset(ref_id, offset) => {
+                let secs = offset.as_secs_f32();
+                for config in [&mut self.app_config, &mut self.edit_config] {
+                    if secs > 0.0 {
+                        config.ui.preview_offsets.insert(ref_id.clone(), secs);
+                    } else {
+                        config.ui.preview_offsets.remove(&ref_id);
+                    }
+                }
+                Task::none()
+            }
+            Message::ReferenceAudioFetched(Err(e)) => {
+                self.push_error(e);
+                Task::none()
+            }
+            Message::PlayRecording => {
+                if self.upload_tab.recorded
+                    && let Some(data) = self.upload_tab.file_bytes.clone()
+                {
+                    self.play_audio(data);
+                }
+                Task::none()
             }
             Message::PlaybackPause => {
                 if let Some(player) = &mut self.player {
@@ -630,13 +1514,37 @@ impl Qvox {
                 Task::none()
             }
             Message::PlaybackStop => {
-                if let Some(player) = &mut self.player {
-                    player.stop();
+                self.stop_playback();
+                Task::none()
+            }
+            Message::PlaybackSeek(fraction) => {
+                if let Some(player) = &mut self.player
+                    && let Some(total) = player.total_duration()
+                    && let Err(e) = player.seek(seek_position(fraction, total))
+                {
+                    self.push_error(format!("Cannot seek in this clip: {e}"));
+                }
+                Task::none()
+            }
+            Message::PlaybackToggleLoop => {
+                self.loop_playback = !self.loop_playback;
+                if let Some(player) = &mut self.player
+                    && let Err(e) = player.set_loop(self.loop_playback)
+                {
+                    self.push_error(format!("Playback error: {e}"));
                 }
                 Task::none()
             }
             Message::PlaybackTick => {
                 // Triggers a view refresh; playback_state() detects when audio finished.
+                if let Some(player) = &mut self.player
+                    && let Err(e) = player.restart_if_looping()
+                {
+                    self.push_error(format!("Playback error: {e}"));
+                }
+                if self.playback_state() == PlaybackState::Stopped {
+                    self.playing_id = None;
+                }
                 Task::none()
             }
             _ => Task::none(),
@@ -645,61 +1553,428 @@ impl Qvox {
 
     fn update_generated(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::GeneratedListLoaded(Ok(list)) => {
+            Message::GeneratedListLoaded(Ok(mut list)) => {
+                if let Some(deleted) = &self.last_deleted {
+                    list.retain(|g| g.id != deleted.id);
+                }
+                self.selected_generated.retain(|id| list.iter().any(|g| &g.id == id));
                 self.generated_list = list;
+                crate::views::generated_list::sort_generated(
+                    &mut self.generated_list,
+                    self.generated_sort,
+                );
                 Task::none()
             }
             Message::GeneratedListLoaded(Err(e)) => {
-                self.error = Some(format!("Failed to load generated list: {e}"));
+                self.api_error("Failed to load generated list", &e, Message::RefreshGeneratedList);
                 Task::none()
             }
             Message::RefreshGeneratedList => self.fetch_generated_list(),
             Message::GeneratedPlay(audio_id) => {
-                let base_url = self.api_base_url();
+                let client = self.api.clone();
                 Task::perform(
                     async move {
-                        ApiClient::new(&base_url)
+                        client
                             .task_audio(&audio_id)
                             .await
+                            .map(|data| (audio_id, data))
                             .map_err(|e| e.to_string())
                     },
                     Message::GeneratedAudioFetched,
                 )
             }
-            Message::GeneratedAudioFetched(Ok(data)) => {
-                self.play_audio(data);
+            Message::GeneratedAudioFetched(Ok((audio_id, data))) => {
+                let gain = if self.app_config.ui.consistent_loudness {
+                    self.clip_loudness_gain(&audio_id, &data)
+                } else {
+                    1.0
+                };
+                self.play_audio_with(data, gain, Duration::ZERO);
+                if self.playback_state() == PlaybackState::Playing {
+                    self.playing_id = Some(audio_id);
+                }
                 Task::none()
             }
             Message::GeneratedAudioFetched(Err(e)) => {
-                self.error = Some(format!("Failed to fetch audio: {e}"));
+                self.push_error(format!("Failed to fetch audio: {e}"));
+                Task::none()
+            }
+            Message::GeneratedSortChanged(mode) => {
+                self.generated_sort = mode;
+                crate::views::generated_list::sort_generated(&mut self.generated_list, mode);
+                Task::none()
+            }
+            Message::GeneratedFilterChanged(query) => {
+                self.generated_filter = query;
+                Task::none()
+            }
+            Message::GeneratedToggleExpand(audio_id) => {
+                if !self.expanded_generated.remove(&audio_id) {
+                    self.expanded_generated.insert(audio_id);
+                }
                 Task::none()
             }
+            Message::GeneratedRegenerate(audio_id) => self.regenerate(&audio_id),
             Message::GeneratedDelete(audio_id) => {
-                let base_url = self.api_base_url();
-                let id = audio_id.clone();
+                self.pending_delete = Some(PendingDelete::Generated(audio_id));
+                Task::none()
+            }
+            Message::DeleteConfirm => match self.pending_delete.take() {
+                Some(PendingDelete::Generated(audio_id)) => self.delete_with_undo(audio_id),
+                Some(PendingDelete::SelectedGenerated) => {
+                    let ids = self.visible_selection();
+                    Task::batch(ids.into_iter().map(|id| self.delete_generated(id)))
+                }
+                Some(PendingDelete::Reference(id)) => self.delete_reference(id),
+                None => Task::none(),
+            },
+            Message::GeneratedDeleteCommit(seq) => {
+                if seq != self.delete_seq {
+                    // Already committed by a later deletion.
+                    return Task::none();
+                }
+                // `None` when undone.
+                self.last_deleted
+                    .take()
+                    .map_or_else(Task::none, |item| self.delete_generated(item.id))
+            }
+            Message::GeneratedUndoDelete => {
+                if let Some(item) = self.last_deleted.take() {
+                    self.generated_list.push(item);
+                    crate::views::generated_list::sort_generated(
+                        &mut self.generated_list,
+                        self.generated_sort,
+                    );
+                }
+                Task::none()
+            }
+            Message::DeleteCancel => {
+                self.pending_delete = None;
+                Task::none()
+            }
+            Message::GeneratedToggleSelect(audio_id) => {
+                if !self.selected_generated.remove(&audio_id) {
+                    self.selected_generated.insert(audio_id);
+                }
+                Task::none()
+            }
+            Message::GeneratedSelectAllVisible => {
+                let visible: Vec<String> = self
+                    .generated_list
+                    .iter()
+                    .filter(|g| {
+                        crate::views::generated_list::matches_filter(g, &self.generated_filter)
+                    })
+                    .map(|g| g.id.clone())
+                    .collect();
+                if visible.iter().all(|id| self.selected_generated.contains(id)) {
+                    for id in &visible {
+                        self.selected_generated.remove(id);
+                    }
+                } else {
+                    self.selected_generated.extend(visible);
+                }
+                Task::none()
+            }
+            Message::GeneratedDeleteSelected => {
+                if !self.visible_selection().is_empty() {
+                    self.pending_delete = Some(PendingDelete::SelectedGenerated);
+                }
+                Task::none()
+            }
+            Message::GeneratedDeleted(Ok(audio_id)) => {
+                self.generated_list.retain(|g| g.id != audio_id);
+                self.selected_generated.remove(&audio_id);
+                self.expanded_generated.remove(&audio_id);
+                Task::none()
+            }
+            Message::GeneratedDeleted(Err(e)) => {
+                self.push_error(format!("Failed to delete: {e}"));
+                // An item hidden for undo may still exist; show the server's list.
+                self.fetch_generated_list()
+            }
+            Message::GeneratedExport(audio_id) => {
+                let Some(item) = self.generated_list.iter().find(|g| g.id == audio_id) else {
+                    return Task::none();
+                };
+                let mut plan = crate::export::plan_export(
+                    std::slice::from_ref(item),
+                    &self.references,
+                    self.app_config.ui.export_include_reference,
+                );
+                let entry = plan.remove(0);
+                let peak_db = self.app_config.ui.export_peak_db();
+                let start_dir = self.app_config.ui.export_dir.clone();
+                let client = self.api.clone();
+                self.busy = Some(BusyReason::Exporting);
+                Task::perform(
+                    async move {
+                        let dialog =
+                            crate::export::export_dialog("Export generated audio", start_dir).await;
+                        let Some(handle) = dialog
+                            .add_filter("WAV", &["wav"])
+                            .set_file_name(&entry.file_name)
+                            .save_file()
+                            .await
+                        else {
+                            return Ok(Vec::new());
+                        };
+                        crate::export::export_entry(&client, &entry, handle.path(), peak_db).await
+                    },
+                    Message::ExportDone,
+                )
+            }
+            Message::GeneratedExportAll => {
+                let plan = crate::export::plan_export(
+                    &self.generated_list,
+                    &self.references,
+                    self.app_config.ui.export_include_reference,
+                );
+                let peak_db = self.app_config.ui.export_peak_db();
+                let start_dir = self.app_config.ui.export_dir.clone();
+                let client = self.api.clone();
+                self.busy = Some(BusyReason::Exporting);
+                Task::perform(
+                    async move {
+                        let dialog =
+                            crate::export::export_dialog("Export all generated audio", start_dir)
+                                .await;
+                        let Some(dir) = dialog.pick_folder().await else {
+                            return (Vec::new(), Vec::new());
+                        };
+                        // One bad clip shouldn't stop the rest.
+                        let mut written = Vec::new();
+                        let mut failed = Vec::new();
+                        for entry in &plan {
+                            let dest = dir.path().join(&entry.file_name);
+                            let result =
+                                crate::export::export_entry(&client, entry, &dest, peak_db).await;
+                            match result {
+                                Ok(paths) => written.extend(paths),
+                                Err(e) => failed.push(e),
+                            }
+                        }
+                        (written, failed)
+                    },
+                    |(written, failed)| Message::ExportAllDone(written, failed),
+                )
+            }
+            Message::ExportIncludeReferenceToggled(enabled) => {
+                self.app_config.ui.export_include_reference = enabled;
+                self.edit_config.ui.export_include_reference = enabled;
+                self.save_config();
+                Task::none()
+            }
+            Message::ExportNormalizeToggled(enabled) => {
+                self.app_config.ui.export_normalize = enabled;
+                self.edit_config.ui.export_normalize = enabled;
+                self.save_config();
+                Task::none()
+            }
+            Message::ExportPeakChanged(db) => {
+                self.app_config.ui.set_export_peak_db(db);
+                self.edit_config.ui.set_export_peak_db(db);
+                Task::none()
+            }
+            Message::ExportDone(Ok(paths)) => {
+                self.finish_export(&paths);
+                Task::none()
+            }
+            Message::ExportAllDone(paths, failed) => {
+                self.finish_export(&paths);
+                if let Some(first) = failed.first() {
+                    tracing::warn!("{} clip(s) failed to export: {failed:?}", failed.len());
+                    self.push_error(format!(
+                        "{} clip(s) failed to export; the rest were written. First error: {first}",
+                        failed.len()
+                    ));
+                }
+                Task::none()
+            }
+            Message::ExportDone(Err(e)) => {
+                self.busy = None;
+                self.push_error(format!("Export failed: {e}"));
+                Task::none()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    fn update_preset(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::PresetImport => Task::perform(
+                async {
+                    let Some(file) = rfd::AsyncFileDialog::new()
+                        .add_filter("qvox preset", &[PRESET_EXTENSION])
+                        .set_title("Import preset")
+                        .pick_file()
+                        .await
+                    else {
+                        return Err(String::new());
+                    };
+                    crate::preset::load_preset(&file.read().await).map_err(|e| e.to_string())
+                },
+                Message::PresetLoaded,
+            ),
+            Message::PresetLoaded(Ok(preset)) => self.apply_preset(preset),
+            Message::PresetLoaded(Err(e)) | Message::PresetSaved(Err(e)) => {
+                // An empty error means the dialog was cancelled.
+                if !e.is_empty() {
+                    self.push_error(format!("Preset error: {e}"));
+                }
+                Task::none()
+            }
+            Message::PresetExport(tab) => {
+                let Some(voice) = self.preset_voice(tab) else {
+                    return Task::none();
+                };
+                let language = match tab {
+                    TabId::Clone => self.clone_tab.selected_language.clone(),
+                    TabId::CustomVoice => self.custom_tab.selected_language.clone(),
+                    _ => self.design_tab.selected_language.clone(),
+                };
+                // Clone presets carry the reference clip, fetched once a file is picked.
+                let clip_id = match tab {
+                    TabId::Clone => self.selected_clone_reference().map(|r| r.id.clone()),
+                    _ => None,
+                };
+                let client = self.api.clone();
                 Task::perform(
                     async move {
-                        ApiClient::new(&base_url)
-                            .delete_generated(&id)
+                        let mut voice = voice;
+                        let Some(file) = rfd::AsyncFileDialog::new()
+                            .add_filter("qvox preset", &[PRESET_EXTENSION])
+                            .set_file_name(format!("voice.{PRESET_EXTENSION}"))
+                            .set_title("Export preset")
+                            .save_file()
+                            .await
+                        else {
+                            return Err(String::new());
+                        };
+                        let name = file.path().file_stem().map_or_else(
+                            || "preset".to_owned(),
+                            |s| s.to_string_lossy().into_owned(),
+                        );
+                        if let (PresetVoice::Clone { reference_wav, .. }, Some(id)) =
+                            (&mut voice, clip_id)
+                        {
+                            let clip = client.reference_audio(&id).await;
+                            *reference_wav = Some(clip.map_err(|e| e.to_string())?);
+                        }
+                        let preset = Preset::new(name, language, voice);
+                        tokio::fs::write(file.path(), save_preset(&preset))
                             .await
-                            .map(|_| audio_id)
                             .map_err(|e| e.to_string())
                     },
-                    Message::GeneratedDeleted,
+                    Message::PresetSaved,
                 )
             }
-            Message::GeneratedDeleted(Ok(audio_id)) => {
-                self.generated_list.retain(|g| g.id != audio_id);
+            Message::PresetReferenceUploaded(Ok(reference)) => {
+                let display = reference.display_name().to_owned();
+                self.references.push(reference);
+                self.clone_tab.selected_ref = Some(display);
                 Task::none()
             }
-            Message::GeneratedDeleted(Err(e)) => {
-                self.error = Some(format!("Failed to delete: {e}"));
+            Message::PresetReferenceUploaded(Err(e)) => {
+                self.push_error(format!("Failed to upload preset reference: {e}"));
                 Task::none()
             }
             _ => Task::none(),
         }
     }
 
+    /// Clear the busy state after an export and remember where `paths`
+    /// were written.
+    fn finish_export(&mut self, paths: &[std::path::PathBuf]) {
+        self.busy = None;
+        if let Some(first) = paths.first() {
+            self.last_export = Some((paths.len(), first.clone()));
+            let dir = first.parent().map(std::path::Path::to_path_buf);
+            if dir.is_some() && dir != self.app_config.ui.export_dir {
+                self.app_config.ui.export_dir.clone_from(&dir);
+                self.edit_config.ui.export_dir = dir;
+                self.save_config();
+            }
+        }
+    }
+
+    /// The current fields of `tab` as preset settings.
+    fn preset_voice(&self, tab: TabId) -> Option<PresetVoice> {
+        match tab {
+            TabId::VoiceDesign => Some(PresetVoice::VoiceDesign {
+                instruct: self.design_tab.instruct.clone(),
+            }),
+            TabId::CustomVoice => Some(PresetVoice::CustomVoice {
+                speaker: self.custom_tab.selected_speaker.clone()?,
+                instruct: Some(self.custom_tab.instruct.clone()).filter(|i| !i.is_empty()),
+            }),
+            TabId::Clone => Some(PresetVoice::Clone {
+                ref_text: self.selected_clone_reference()?.ref_text.clone(),
+                reference_wav: None,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The reference picked on the Clone tab, if it still exists.
+    fn selected_clone_reference(&self) -> Option<&ReferenceAudio> {
+        let name = self.clone_tab.selected_ref.as_deref()?;
+        self.references.iter().find(|r| r.display_name() == name)
+    }
+
+    /// `language` if the server offers it, else "auto" with a notice.
+    fn preset_language(&mut self, language: String) -> String {
+        if language == "auto" || self.languages.contains(&language) {
+            return language;
+        }
+        self.push_error(format!(
+            "The preset's language \"{language}\" isn't available; using auto instead"
+        ));
+        "auto".to_owned()
+    }
+
+    /// Fill the matching tab from `preset` and switch to it, uploading an
+    /// embedded reference clip for clone presets.
+    fn apply_preset(&mut self, preset: Preset) -> Task<Message> {
+        let language = self.preset_language(preset.language);
+        match preset.voice {
+            PresetVoice::VoiceDesign { instruct } => {
+                self.design_tab.instruct = instruct;
+                self.design_tab.selected_language = language;
+                self.select_tab(TabId::VoiceDesign);
+                Task::none()
+            }
+            PresetVoice::CustomVoice { speaker, instruct } => {
+                self.custom_tab.selected_speaker = Some(speaker);
+                self.custom_tab.instruct = instruct.unwrap_or_default();
+                self.custom_tab.selected_language = language;
+                self.select_tab(TabId::CustomVoice);
+                Task::none()
+            }
+            PresetVoice::Clone {
+                ref_text,
+                reference_wav,
+            } => {
+                self.clone_tab.selected_language = language;
+                self.select_tab(TabId::Clone);
+                let Some(wav) = reference_wav else {
+                    return Task::none();
+                };
+                let client = self.api.clone();
+                let filename = format!("{}.wav", preset.name);
+                Task::perform(
+                    async move {
+                        client
+                            .upload_reference(wav, filename, ref_text.as_deref())
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::PresetReferenceUploaded,
+                )
+            }
+        }
+    }
+
     fn update_settings(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::SettingsModelToggled(model) => {
@@ -717,9 +1992,23 @@ impl Qvox {
                 self.settings_dirty = self.edit_config != self.app_config;
                 Task::none()
             }
+            Message::SettingsModelSizeChanged(size) => {
+                self.edit_config.server.model_size = size;
+                self.settings_dirty = self.edit_config != self.app_config;
+                Task::none()
+            }
+            Message::SettingsUseExistingServerToggled(enabled) => {
+                self.edit_config.server.use_existing_server = enabled;
+                self.settings_dirty = self.edit_config != self.app_config;
+                Task::none()
+            }
             Message::SettingsPortChanged(s) => {
-                if let Ok(port) = s.parse::<u16>() {
-                    self.edit_config.server.port = port;
+                match crate::views::settings::parse_port(&s) {
+                    Ok(port) => {
+                        self.edit_config.server.port = port;
+                        self.port_input = None;
+                    }
+                    Err(_) => self.port_input = Some(s),
                 }
                 self.settings_dirty = self.edit_config != self.app_config;
                 Task::none()
@@ -729,140 +2018,705 @@ impl Qvox {
                 self.settings_dirty = self.edit_config != self.app_config;
                 Task::none()
             }
+            Message::SettingsPythonPathChanged(s) => {
+                self.edit_config.server.python = (!s.is_empty()).then_some(s);
+                self.settings_dirty = self.edit_config != self.app_config;
+                Task::none()
+            }
             Message::SettingsDarkModeToggled(enabled) => {
                 self.edit_config.ui.dark_mode = enabled;
                 // Apply dark mode immediately
                 self.app_config.ui.dark_mode = enabled;
                 self.settings_dirty = self.edit_config != self.app_config;
-                let _ = crate::config::save(&self.app_config);
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsConsistentLoudnessToggled(enabled) => {
+                self.edit_config.ui.consistent_loudness = enabled;
+                self.app_config.ui.consistent_loudness = enabled;
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsNormalizePreviewToggled(enabled) => {
+                self.edit_config.audio.normalize_preview = enabled;
+                self.app_config.audio.normalize_preview = enabled;
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsConvertUploadsToggled(enabled) => {
+                self.edit_config.audio.convert_uploads_to_wav = enabled;
+                self.app_config.audio.convert_uploads_to_wav = enabled;
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsEnterToGenerateToggled(enabled) => {
+                self.edit_config.ui.enter_to_generate = enabled;
+                self.app_config.ui.enter_to_generate = enabled;
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsStopOnTabSwitchToggled(enabled) => {
+                self.edit_config.ui.stop_on_tab_switch = enabled;
+                self.app_config.ui.stop_on_tab_switch = enabled;
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsAutoNormalizeToggled(enabled) => {
+                self.edit_config.ui.auto_normalize = enabled;
+                self.app_config.ui.auto_normalize = enabled;
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsAutoTrimSilenceToggled(enabled) => {
+                self.edit_config.ui.auto_trim_silence = enabled;
+                self.app_config.ui.auto_trim_silence = enabled;
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsSilenceThresholdChanged(db) => {
+                self.edit_config.ui.set_silence_threshold_db(db);
+                self.app_config.ui.set_silence_threshold_db(db);
+                self.settings_dirty = self.edit_config != self.app_config;
+                Task::none()
+            }
+            Message::SettingsTrimLongReferencesToggled(enabled) => {
+                self.edit_config.ui.trim_long_references = enabled;
+                self.app_config.ui.trim_long_references = enabled;
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsMaxReferenceSecsChanged(secs) => {
+                self.edit_config.ui.set_max_reference_secs(secs);
+                self.app_config.ui.set_max_reference_secs(secs);
+                self.settings_dirty = self.edit_config != self.app_config;
+                Task::none()
+            }
+            Message::ConfigSliderReleased => {
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsWhisperModelSelected(model) => {
+                self.edit_config.audio.whisper_model = model;
+                self.app_config.audio.whisper_model = model;
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                Task::none()
+            }
+            Message::SettingsOutputDeviceSelected(name) => {
+                self.edit_config.audio.output_device = Some(name.clone());
+                self.app_config.audio.output_device = Some(name);
+                self.settings_dirty = self.edit_config != self.app_config;
+                self.save_config();
+                // The output stream is bound to its device; reopen it on next play.
+                self.player = None;
+                self.playing_id = None;
+                Task::none()
+            }
+            Message::SettingsReset => {
+                self.edit_config = AppConfig::default();
+                self.port_input = None;
+                self.settings_dirty = self.edit_config != self.app_config;
+                Task::none()
+            }
+            Message::ClearTranscriptionCache => {
+                if let Err(e) = crate::transcribe::whisper::clear_cache(&self.data_dir) {
+                    self.push_error(format!("Failed to clear transcription cache: {e}"));
+                }
+                self.refresh_storage();
+                Task::none()
+            }
+            Message::DeleteModels => {
+                self.storage.confirm_delete_models = true;
+                Task::none()
+            }
+            Message::DeleteModelsCancel => {
+                self.storage.confirm_delete_models = false;
+                Task::none()
+            }
+            Message::DeleteModelsConfirm => {
+                if let Err(e) = crate::transcribe::whisper::delete_models(&self.data_dir) {
+                    self.push_error(format!("Failed to delete Whisper models: {e}"));
+                }
+                self.refresh_storage();
                 Task::none()
             }
             Message::SettingsSave => {
+                if self.port_input.is_some() {
+                    return Task::none();
+                }
+                if let Err(e) =
+                    crate::server::manager::check_script_path(&self.edit_config.server.script_path)
+                {
+                    // Keep the working path and save the rest. Restarting is left for
+                    // the next save: an error on the loading screen would stall it.
+                    let script_path = std::mem::take(&mut self.app_config.server.script_path);
+                    self.app_config = self.edit_config.clone();
+                    self.app_config.server.script_path = script_path;
+                    self.settings_dirty = true;
+                    self.save_config();
+                    self.push_error(format!(
+                        "{e}. The other settings were saved; fix the path and save again \
+                         to restart the server."
+                    ));
+                    return Task::none();
+                }
                 self.app_config = self.edit_config.clone();
                 self.settings_dirty = false;
-                let _ = crate::config::save(&self.app_config);
-                // Kill existing server and restart
-                if let Some(server) = &mut self.server {
-                    server.kill();
-                }
-                self.server = None;
-                self.screen = Screen::Loading;
-                self.elapsed_secs = 0;
-                self.error = None;
-                "Restarting server...".clone_into(&mut self.loading_status);
-                Task::done(Message::ServerSpawned)
+                self.save_config();
+                self.restart_server()
+            }
+            Message::DiagnosticsRun => {
+                self.diagnostics_running = true;
+                Task::perform(
+                    crate::diagnostics::run_diagnostics(
+                        self.app_config.clone(),
+                        self.api_base_url(),
+                        self.data_dir.clone(),
+                    ),
+                    Message::DiagnosticsDone,
+                )
+            }
+            Message::DiagnosticsDone(results) => {
+                self.diagnostics_running = false;
+                self.diagnostics = Some(results);
+                Task::none()
+            }
+            Message::DeviceDetected(detected) => {
+                self.detected_device = detected;
+                Task::none()
+            }
+            Message::DevicesDetected(options) => {
+                self.device_options = options;
+                Task::none()
+            }
+            Message::RevealPath(path) => {
+                if let Err(e) = crate::desktop::reveal_in_file_manager(&path) {
+                    self.push_error(format!("Could not open folder: {e}"));
+                }
+                Task::none()
             }
+            Message::OpenSettings => {
+                self.screen = Screen::Main;
+                self.select_tab(TabId::Settings);
+                Task::none()
+            }
+            Message::CopyToClipboard(contents) => iced::clipboard::write(contents),
             _ => Task::none(),
         }
     }
 
     // ─── Private helpers ────────────────────────────────────────
 
-    fn ensure_recorder(&mut self) {
-        if self.recorder.is_none() {
-            match Recorder::new() {
-                Ok(r) => self.recorder = Some(r),
-                Err(e) => self.error = Some(format!("Microphone error: {e}")),
-            }
+    /// Close the window after saving an unsaved active tab and committing
+    /// any deletion still in its undo window, so closing loses neither.
+    fn close_window(&mut self, id: iced::window::Id) -> Task<Message> {
+        if self.active_tab_unsaved {
+            self.save_config();
         }
-    }
-
-    fn recording_state(&self) -> RecordingState {
-        self.recorder
-            .as_ref()
-            .map_or(RecordingState::Idle, Recorder::state)
-    }
-
-    fn ensure_player(&mut self) -> Option<&mut AudioPlayer> {
-        if self.player.is_none() {
-            match AudioPlayer::new() {
-                Ok(p) => self.player = Some(p),
-                Err(e) => {
-                    self.error = Some(format!("Audio device error: {e}"));
-                    return None;
-                }
+        let close = iced::window::close(id);
+        let Some(item) = self.last_deleted.take() else {
+            return close;
+        };
+        let client = self.api.clone();
+        Task::future(async move {
+            if let Err(e) = client.delete_generated(&item.id).await {
+                tracing::warn!("Failed to delete {} before exiting: {e}", item.id);
             }
-        }
-        self.player.as_mut()
+        })
+        .discard()
+        .chain(close)
     }
 
-    fn play_audio(&mut self, data: Vec<u8>) {
-        if let Some(player) = self.ensure_player()
-            && let Err(e) = player.play_bytes(data)
-        {
-            self.error = Some(format!("Playback error: {e}"));
-        }
+    /// Ids of the checked generated clips the filter shows; checked clips
+    /// hidden by the filter are left alone.
+    fn visible_selection(&self) -> Vec<String> {
+        self.generated_list
+            .iter()
+            .filter(|g| self.selected_generated.contains(&g.id))
+            .filter(|g| crate::views::generated_list::matches_filter(g, &self.generated_filter))
+            .map(|g| g.id.clone())
+            .collect()
     }
 
-    fn playback_state(&self) -> PlaybackState {
-        self.player
-            .as_ref()
-            .map_or(PlaybackState::Stopped, AudioPlayer::state)
+    /// Hide a generated clip and delete it on the server once its undo
+    /// window runs out.
+    fn delete_with_undo(&mut self, audio_id: String) -> Task<Message> {
+        let Some(pos) = self.generated_list.iter().position(|g| g.id == audio_id) else {
+            return Task::none();
+        };
+        // Only the latest deletion can be undone; commit the previous one.
+        let commit_previous = self
+            .last_deleted
+            .take()
+            .map_or_else(Task::none, |prev| self.delete_generated(prev.id));
+        self.selected_generated.remove(&audio_id);
+        self.last_deleted = Some(self.generated_list.remove(pos));
+        self.delete_seq += 1;
+        let seq = self.delete_seq;
+        let commit = Task::perform(
+            tokio::time::sleep(Duration::from_secs(UNDO_DELETE_SECS)),
+            move |()| Message::GeneratedDeleteCommit(seq),
+        );
+        Task::batch([commit_previous, commit])
     }
 
-    fn api_base_url(&self) -> String {
-        self.server
-            .as_ref()
-            .map_or_else(|| "http://localhost:8000".to_owned(), ServerManager::base_url)
+    fn delete_reference(&self, id: String) -> Task<Message> {
+        let client = self.api.clone();
+        Task::perform(
+            async move {
+                client
+                    .delete_reference(&id)
+                    .await
+                    .map(|_| id)
+                    .map_err(|e| e.to_string())
+            },
+            Message::ReferenceDeleted,
+        )
     }
 
-    fn poll_health(&self) -> Task<Message> {
-        let base_url = self.api_base_url();
+    /// Ask the server to delete generated clip `audio_id`.
+    fn delete_generated(&self, audio_id: String) -> Task<Message> {
+        let client = self.api.clone();
+        let id = audio_id.clone();
         Task::perform(
             async move {
-                let client = ApiClient::new(&base_url);
-                match client.health().await {
-                    Ok(resp) => resp.voice_cloner_loaded,
-                    Err(_) => false,
-                }
+                client
+                    .delete_generated(&id)
+                    .await
+                    .map(|_| audio_id)
+                    .map_err(|e| e.to_string())
             },
-            Message::HealthCheck,
+            Message::GeneratedDeleted,
+        )
+    }
+
+    /// Save `language` as the last one picked on `tab`.
+    fn remember_language(&mut self, tab: TabId, language: &str) {
+        if self.app_config.ui.last_languages.get(&tab).map(String::as_str) == Some(language) {
+            return;
+        }
+        for config in [&mut self.app_config, &mut self.edit_config] {
+            config.ui.last_languages.insert(tab, language.to_owned());
+        }
+        self.save_config();
+    }
+
+    /// Apply each tab's saved language, if the server still offers it, to
+    /// tabs that are still on "auto".
+    fn restore_languages(&mut self) {
+        let saved = |tab: TabId| {
+            self.app_config
+                .ui
+                .last_languages
+                .get(&tab)
+                .filter(|lang| self.languages.contains(lang))
+                .cloned()
+        };
+        let restore = |current: &mut String, saved: Option<String>| {
+            if current == "auto"
+                && let Some(lang) = saved
+            {
+                *current = lang;
+            }
+        };
+        restore(&mut self.clone_tab.selected_language, saved(TabId::Clone));
+        restore(&mut self.design_tab.selected_language, saved(TabId::VoiceDesign));
+        restore(&mut self.custom_tab.selected_language, saved(TabId::CustomVoice));
+        restore(&mut self.upload_tab.selected_language, saved(TabId::Upload));
+        let multi = saved(TabId::MultiSpeaker);
+        for seg in &mut self.multi_tab.segments {
+            restore(&mut seg.selected_language, multi.clone());
+        }
+    }
+
+    /// Persist `app_config`, reporting a failure in the error banner.
+    fn save_config(&mut self) {
+        match crate::config::save(&self.data_dir, &self.app_config) {
+            Ok(()) => self.active_tab_unsaved = false,
+            Err(e) => {
+                tracing::error!("failed to save config: {e:#}");
+                self.push_error(format!("Failed to save settings: {e}"));
+            }
+        }
+    }
+
+    /// Labeled locations of the files and folders qvox reads and writes.
+    fn all_paths(&self) -> Vec<(String, std::path::PathBuf)> {
+        let mut paths =
+            vec![("Config file".to_owned(), crate::config::config_path(&self.data_dir))];
+        paths.push((
+            "Whisper models".to_owned(),
+            crate::transcribe::whisper::models_dir(&self.data_dir),
+        ));
+        paths.push((
+            "Transcription cache".to_owned(),
+            crate::transcribe::whisper::cache_dir(&self.data_dir),
+        ));
+        paths.push(("Generation queue".to_owned(), crate::queue::queue_path(&self.data_dir)));
+        paths.push((
+            "Generation history".to_owned(),
+            crate::history::history_path(&self.data_dir),
+        ));
+        paths
+    }
+
+    /// Switch to `tab` at the user's request, stopping playback first under
+    /// `[ui] stop_on_tab_switch`.
+    fn switch_tab(&mut self, tab: TabId) {
+        if self.tab_switch_stops_playback(tab) {
+            self.stop_playback();
+        }
+        self.select_tab(tab);
+    }
+
+    /// Switch to `tab`, remembering the tab we came from for `TabBack`.
+    fn select_tab(&mut self, tab: TabId) {
+        if tab != self.active_tab {
+            self.previous_tab = Some(self.active_tab);
+            self.active_tab = tab;
+            self.edit_config.ui.active_tab = tab;
+            self.app_config.ui.active_tab = tab;
+            self.active_tab_unsaved = true;
+            if tab == TabId::Settings {
+                self.refresh_storage();
+            }
+        }
+    }
+
+    /// Rescan the disk usage shown in settings, dropping any pending
+    /// confirmation.
+    fn refresh_storage(&mut self) {
+        self.storage = crate::views::settings::StorageUsage::scan(&self.data_dir);
+    }
+
+    /// Whether `action` may run now, given safe mode and any busy operation.
+    fn action_allowed(&self, action: UserAction) -> bool {
+        if self.safe_mode && action == UserAction::Generate {
+            return false;
+        }
+        is_action_allowed(self.busy, action)
+    }
+
+    /// Whether selecting `tab` should stop playback under `[ui] stop_on_tab_switch`.
+    fn tab_switch_stops_playback(&self, tab: TabId) -> bool {
+        self.app_config.ui.stop_on_tab_switch && tab != self.active_tab
+    }
+
+    /// Make `bytes` the Upload tab's audio and transcribe it unless a cached
+    /// transcription exists. `recorded` marks a fresh microphone take, which
+    /// can be previewed before generating.
+    fn select_upload_audio(
+        &mut self,
+        path: std::path::PathBuf,
+        bytes: Vec<u8>,
+        name: String,
+        recorded: bool,
+    ) -> Task<Message> {
+        let (bytes, trimmed_from_secs) = self.trim_reference(bytes);
+        let hash = crate::audio::hash::bytes_sha256(&bytes);
+
+        // Check transcription cache
+        let cached = crate::transcribe::whisper::cached_transcription(
+            &self.data_dir,
+            &hash,
+            self.app_config.audio.whisper_model,
+            crate::transcribe::whisper::whisper_language_code(&self.upload_tab.selected_language),
+        );
+
+        self.upload_tab.selected_file = Some(path);
+        self.upload_tab.file_bytes = Some(bytes.clone());
+        self.upload_tab.file_name = Some(name);
+        self.upload_tab.file_hash = Some(hash.clone());
+        let analysis = crate::audio::wav::is_wav(&bytes)
+            .then(|| crate::audio::wav::analyze(&bytes, WAVEFORM_COLUMNS).ok())
+            .flatten();
+        let (wav_info, waveform) = analysis.unzip();
+        self.upload_tab.wav_info = wav_info;
+        self.upload_tab.waveform = waveform.unwrap_or_default();
+        self.upload_tab.recorded = recorded;
+        self.upload_tab.trimmed_from_secs = trimmed_from_secs;
+        self.upload_tab.ref_text_edited = false;
+
+        if let Some(text) = cached {
+            self.upload_tab.ref_text = Some(text);
+            Task::none()
+        } else {
+            // Start transcription in background
+            self.upload_tab.ref_text = None;
+            self.start_transcription(bytes, hash)
+        }
+    }
+
+    /// Cut a WAV reference longer than `[ui] max_reference_secs` when
+    /// trimming is on, returning the bytes to use and, if it was cut, the
+    /// original length. Only this copy changes; the picked file is untouched.
+    fn trim_reference(&self, bytes: Vec<u8>) -> (Vec<u8>, Option<f32>) {
+        let Some(max_secs) = self.app_config.ui.reference_trim_secs() else {
+            return (bytes, None);
+        };
+        if !crate::audio::wav::is_wav(&bytes) {
+            return (bytes, None);
+        }
+        let Ok(info) = crate::audio::wav::wav_info(&bytes) else {
+            return (bytes, None);
+        };
+        if info.duration_secs <= max_secs {
+            return (bytes, None);
+        }
+        match crate::audio::wav::trim_wav(&bytes, max_secs) {
+            Ok(Some(trimmed)) => (trimmed, Some(info.duration_secs)),
+            Ok(None) | Err(_) => (bytes, None),
+        }
+    }
+
+    /// Record the available input devices and fall back to the default one,
+    /// with a notice, when the saved device is gone.
+    fn set_input_devices(&mut self, devices: Vec<String>) {
+        self.input_devices = devices;
+        if let Some(name) = &self.app_config.audio.input_device
+            && !self.input_devices.contains(name)
+        {
+            self.upload_tab.input_device_notice = Some(format!(
+                "Input device \"{name}\" is not available; using the default microphone."
+            ));
+        }
+    }
+
+    /// The saved input device, if it is currently connected.
+    fn selected_input_device(&self) -> Option<&String> {
+        self.app_config
+            .audio
+            .input_device
+            .as_ref()
+            .filter(|name| self.input_devices.contains(name))
+    }
+
+    fn ensure_recorder(&mut self) {
+        if self.recorder.is_none() {
+            let recorder = match self.selected_input_device() {
+                Some(name) => Recorder::with_device(name),
+                None => Recorder::new(),
+            };
+            match recorder {
+                Ok(r) => self.recorder = Some(r),
+                Err(e) => self.push_error(format!("Microphone error: {e}")),
+            }
+        }
+    }
+
+    fn recording_state(&self) -> RecordingState {
+        self.recorder
+            .as_ref()
+            .map_or(RecordingState::Idle, Recorder::state)
+    }
+
+    fn ensure_player(&mut self) -> Option<&mut AudioPlayer> {
+        if self.player.is_none() {
+            // A saved device that has gone away falls back to the default.
+            let player = match &self.app_config.audio.output_device {
+                Some(name) => AudioPlayer::with_device(name).or_else(|_| AudioPlayer::new()),
+                None => AudioPlayer::new(),
+            };
+            match player {
+                Ok(mut p) => {
+                    // A new player has nothing queued, so this can't fail.
+                    let _ = p.set_loop(self.loop_playback);
+                    self.player = Some(p);
+                }
+                Err(e) => {
+                    self.push_error(format!("Audio device error: {e}"));
+                    return None;
+                }
+            }
+        }
+        self.player.as_mut()
+    }
+
+    fn play_audio(&mut self, data: Vec<u8>) {
+        self.play_audio_with(data, 1.0, Duration::ZERO);
+    }
+
+    fn play_audio_with(&mut self, data: Vec<u8>, gain: f32, start: Duration) {
+        self.playing_id = None;
+        if let Some(player) = self.ensure_player() {
+            player.set_volume(gain);
+            if let Err(e) = player.play_bytes_from(data, start) {
+                self.push_error(format!("Playback error: {e}"));
+            }
+        }
+    }
+
+    fn stop_playback(&mut self) {
+        if let Some(player) = &mut self.player {
+            player.stop();
+        }
+        self.playing_id = None;
+    }
+
+    /// Where to start previewing a reference: its stored offset, clamped to
+    /// the clip duration when the WAV header can be read.
+    fn reference_preview_start(&self, ref_id: &str, data: &[u8]) -> Duration {
+        let offset = self
+            .app_config
+            .ui
+            .preview_offsets
+            .get(ref_id)
+            .copied()
+            .unwrap_or(0.0);
+        let clip_secs = crate::audio::wav::wav_info(data)
+            .ok()
+            .map(|info| info.duration_secs);
+        clamp_preview_offset(offset, clip_secs)
+    }
+
+    /// Peak-normalize a reference preview when `[audio] normalize_preview`
+    /// is on. The stored reference is never modified.
+    fn reference_preview_audio(&self, data: Vec<u8>) -> Vec<u8> {
+        if !self.app_config.audio.normalize_preview {
+            return data;
+        }
+        crate::audio::wav::normalize_wav(&data, PREVIEW_PEAK_DB).unwrap_or(data)
+    }
+
+    /// Playback gain that level-matches a generated clip, caching its RMS by id.
+    fn clip_loudness_gain(&mut self, audio_id: &str, data: &[u8]) -> f32 {
+        let rms = if let Some(rms) = self.loudness_cache.get(audio_id) {
+            *rms
+        } else {
+            let Ok(info) = crate::audio::wav::wav_info(data) else {
+                return 1.0;
+            };
+            self.loudness_cache.insert(audio_id.to_owned(), info.rms);
+            info.rms
+        };
+        crate::audio::processing::loudness_gain(rms, LOUDNESS_TARGET_RMS)
+    }
+
+    fn playback_status(&self) -> PlaybackStatus {
+        let status = self
+            .player
+            .as_ref()
+            .map_or(PlaybackStatus::STOPPED, AudioPlayer::status);
+        PlaybackStatus {
+            looping: self.loop_playback,
+            ..status
+        }
+    }
+
+    fn playback_state(&self) -> PlaybackState {
+        self.player
+            .as_ref()
+            .map_or(PlaybackState::Stopped, AudioPlayer::state)
+    }
+
+    fn api_base_url(&self) -> String {
+        self.server
+            .as_ref()
+            .map_or_else(|| DEFAULT_BASE_URL.to_owned(), ServerManager::base_url)
+    }
+
+    /// Show `err` in the error banner, offering `retry` when the failure
+    /// is transient. Retryable errors stay until acted on.
+    fn api_error(&mut self, context: &str, err: &ApiError, retry: Message) {
+        let retry = err.is_retryable().then_some(retry);
+        let sticky = retry.is_some();
+        self.add_error(format!("{context}: {err}"), retry, sticky);
+    }
+
+    /// Show `text` in the error banner until it expires or is dismissed.
+    fn push_error(&mut self, text: String) {
+        self.add_error(text, None, false);
+    }
+
+    /// Show `text` in the error banner until it is dismissed.
+    fn push_sticky_error(&mut self, text: String) {
+        self.add_error(text, None, true);
+    }
+
+    /// Append an error, or refresh an identical one already shown so
+    /// repeated failures don't flood the list.
+    fn add_error(&mut self, text: String, retry: Option<Message>, sticky: bool) {
+        if let Some(existing) = self.errors.iter_mut().find(|e| e.text == text) {
+            existing.raised = Instant::now();
+            existing.retry = retry;
+            existing.sticky |= sticky;
+            return;
+        }
+        self.errors.push(ErrorEntry {
+            id: self.next_error_id,
+            raised: Instant::now(),
+            text,
+            retry,
+            sticky,
+        });
+        self.next_error_id += 1;
+    }
+
+    /// Drop non-sticky errors older than `ERROR_EXPIRE_SECS` at `now`.
+    fn expire_errors(&mut self, now: Instant) {
+        let ttl = Duration::from_secs(ERROR_EXPIRE_SECS);
+        self.errors
+            .retain(|e| e.sticky || now.saturating_duration_since(e.raised) < ttl);
+    }
+
+    /// Rebuild the shared client after the server address changes.
+    fn refresh_api_client(&mut self) {
+        self.api = ApiClient::new(&self.api_base_url());
+    }
+
+    fn poll_health(&self) -> Task<Message> {
+        let (hosts, port) = self.server.as_ref().map_or_else(
+            || (vec![self.app_config.server.bind_host.clone()], self.app_config.server.port),
+            |mgr| (mgr.health_hosts(), mgr.port()),
+        );
+        Task::perform(
+            async move { crate::server::manager::probe_health(&hosts, port).await },
+            Message::HealthCheck,
         )
     }
 
     fn load_initial_data(&self) -> Task<Message> {
-        let url = self.api_base_url();
-        let url2 = url.clone();
-        let url3 = url.clone();
-        let url4 = url.clone();
+        let client = self.api.clone();
+        let client2 = self.api.clone();
+        let client3 = self.api.clone();
+        let client4 = self.api.clone();
+        let client5 = self.api.clone();
 
         Task::batch([
             Task::perform(
                 async move {
-                    ApiClient::new(&url)
-                        .capabilities()
-                        .await
-                        .map_err(|e| e.to_string())
+                    client.capabilities().await.map_err(|e| e.to_string())
                 },
                 Message::CapabilitiesLoaded,
             ),
             Task::perform(
                 async move {
-                    ApiClient::new(&url2)
-                        .references()
-                        .await
-                        .map_err(|e| e.to_string())
+                    client2.references().await.map_err(|e| e.to_string())
                 },
                 Message::ReferencesLoaded,
             ),
             Task::perform(
                 async move {
-                    ApiClient::new(&url3)
-                        .languages()
-                        .await
-                        .map_err(|e| e.to_string())
+                    client3.languages().await.map_err(|e| e.to_string())
                 },
                 Message::LanguagesLoaded,
             ),
             Task::perform(
-                async move {
-                    ApiClient::new(&url4)
-                        .generated_list()
-                        .await
-                        .map_err(|e| e.to_string())
-                },
+                async move { client4.generated_list().await },
                 Message::GeneratedListLoaded,
             ),
+            Task::perform(
+                async move { client5.health().await.map_err(|e| e.to_string()) },
+                Message::HealthLoaded,
+            ),
         ])
     }
 
@@ -874,34 +2728,108 @@ impl Qvox {
         let ref_audio = self
             .references
             .iter()
-            .find(|r| r.name.as_deref().unwrap_or(&r.original_name) == ref_name.as_str());
+            .find(|r| r.display_name() == ref_name.as_str());
 
         let Some(ref_audio) = ref_audio else {
             return Task::none();
         };
 
+        let lines: Vec<(Option<u64>, String)> = if self.clone_tab.batch_mode {
+            let texts = self.clone_tab.batch_lines();
+            if self.clone_tab.batch_running() || texts.is_empty() {
+                return Task::none();
+            }
+            self.clone_tab.start_batch(texts);
+            self.clone_tab.batch.iter().map(|l| (Some(l.id), l.text.clone())).collect()
+        } else {
+            vec![(None, self.clone_tab.editor.text())]
+        };
+        let requests: Vec<TaskRequest> = lines
+            .into_iter()
+            .map(|(batch_line, text)| {
+                TaskRequest::Clone(CloneRequest {
+                    text,
+                    ref_audio_id: ref_audio.id.clone(),
+                    ref_text: ref_audio.ref_text.clone(),
+                    language: self.clone_tab.selected_language.clone(),
+                    batch_line,
+                })
+            })
+            .collect();
+        Task::batch(requests.into_iter().map(|r| self.submit_request(r)))
+    }
+
+    /// Take the clone batch's unsent lines back out of the queue and mark
+    /// them stopped. Lines already sent are left to finish.
+    fn stop_clone_batch(&mut self) {
+        for line in &mut self.clone_tab.batch {
+            if line.status != BatchLineStatus::Waiting {
+                continue;
+            }
+            let queued = self.pending_requests.iter().position(|r| {
+                matches!(r, TaskRequest::Clone(c) if c.batch_line == Some(line.id))
+            });
+            if let Some(pos) = queued {
+                self.pending_requests.remove(pos);
+                line.status = BatchLineStatus::Stopped;
+            }
+        }
+        self.persist_queue();
+    }
+
+    /// Clone a generated item's text again with the same reference. The
+    /// server doesn't record the language, so it comes from the newest
+    /// history entry for the same text and reference, else "auto".
+    fn regenerate(&mut self, audio_id: &str) -> Task<Message> {
+        let Some(item) = self.generated_list.iter().find(|g| g.id == audio_id) else {
+            return Task::none();
+        };
+        let ref_audio = item
+            .ref_audio_id
+            .as_ref()
+            .and_then(|ref_id| self.references.iter().find(|r| &r.id == ref_id));
+        let Some(ref_audio) = ref_audio else {
+            self.push_error(
+                "Can't regenerate: the reference audio it was cloned from no longer exists"
+                    .to_owned(),
+            );
+            return Task::none();
+        };
+        let language = self
+            .history
+            .iter()
+            .rev()
+            .find_map(|entry| match &entry.request {
+                Some(TaskRequest::Clone(r))
+                    if r.text == item.generated_text && r.ref_audio_id == ref_audio.id =>
+                {
+                    Some(r.language.clone())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| "auto".to_owned());
         let request = CloneRequest {
-            text: self.clone_tab.text.clone(),
+            text: item.generated_text.clone(),
             ref_audio_id: ref_audio.id.clone(),
             ref_text: ref_audio.ref_text.clone(),
-            language: self.clone_tab.selected_language.clone(),
+            language,
+            batch_line: None,
         };
-
-        let base_url = self.api_base_url();
-
-        Task::perform(
-            async move {
-                ApiClient::new(&base_url)
-                    .clone_voice(&request)
-                    .await
-                    .map(|resp| resp.task_id)
-                    .map_err(|e| e.to_string())
-            },
-            Message::TaskCreated,
-        )
+        self.submit_request(TaskRequest::Clone(request))
     }
 
     fn start_upload_generation(&mut self) -> Task<Message> {
+        // Uploads aren't queued, so a second click can't wait its turn.
+        if self.submitting.contains(&TabId::Upload) {
+            return Task::none();
+        }
+        if self.running_tasks() >= MAX_CONCURRENT_TASKS {
+            self.push_error(format!(
+                "{MAX_CONCURRENT_TASKS} generations are already running. \
+                 Try again when one finishes."
+            ));
+            return Task::none();
+        }
         let Some(file_bytes) = self.upload_tab.file_bytes.clone() else {
             return Task::none();
         };
@@ -909,48 +2837,123 @@ impl Qvox {
             return Task::none();
         };
 
-        let text = self.upload_tab.text.clone();
+        let text = self.upload_tab.editor.text();
         let language = self.upload_tab.selected_language.clone();
         let ref_text = self.upload_tab.ref_text.clone();
-        let base_url = self.api_base_url();
+        let client = self.api.clone();
+        let convert = self.app_config.audio.convert_uploads_to_wav;
+        self.submitting.push(TabId::Upload);
+        self.submitting_upload = Some(UploadSnapshot {
+            text: text.clone(),
+            language: language.clone(),
+            file_name: file_name.clone(),
+        });
+        let cache_edit = self.cache_edited_ref_text();
+
+        let generate = Task::perform(
+            async move {
+                let (file_bytes, file_name) = tokio::task::spawn_blocking(move || {
+                    crate::audio::wav::prepare_upload(file_bytes, file_name, convert)
+                })
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| format!("Failed to convert audio to WAV: {e}"))?;
+                let (response, latency) = timed(client.clone_with_upload(
+                    file_bytes,
+                    file_name,
+                    &text,
+                    ref_text.as_deref(),
+                    Some(&language),
+                ))
+                .await;
+                response
+                    .map(|resp| (resp.task_id, resp.estimated_time, latency))
+                    .map_err(|e| e.to_string())
+            },
+            |result| Message::TaskCreated(TabId::Upload, None, result),
+        );
+        Task::batch([cache_edit, generate])
+    }
 
+    /// Cache the Upload tab's transcript if it was edited since it was
+    /// transcribed, so the same audio comes back with the edit. Runs off the
+    /// UI thread.
+    fn cache_edited_ref_text(&mut self) -> Task<Message> {
+        let (Some(hash), Some(text)) = (&self.upload_tab.file_hash, &self.upload_tab.ref_text)
+        else {
+            return Task::none();
+        };
+        if !std::mem::take(&mut self.upload_tab.ref_text_edited) {
+            return Task::none();
+        }
+        let (data_dir, hash, text) = (self.data_dir.clone(), hash.clone(), text.clone());
+        let model = self.app_config.audio.whisper_model;
+        let language =
+            crate::transcribe::whisper::whisper_language_code(&self.upload_tab.selected_language);
         Task::perform(
             async move {
-                ApiClient::new(&base_url)
-                    .clone_with_upload(
-                        file_bytes,
-                        file_name,
+                tokio::task::spawn_blocking(move || {
+                    crate::transcribe::whisper::save_transcription_cache(
+                        &data_dir,
+                        &hash,
+                        model,
+                        language,
                         &text,
-                        ref_text.as_deref(),
-                        Some(&language),
                     )
-                    .await
-                    .map(|resp| resp.task_id)
-                    .map_err(|e| e.to_string())
+                })
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())
             },
-            Message::TaskCreated,
+            Message::TranscriptionCacheSaved,
         )
     }
 
-    fn start_design_generation(&mut self) -> Task<Message> {
-        let request = VoiceDesignRequest {
-            text: self.design_tab.text.clone(),
-            instruct: self.design_tab.instruct.clone(),
-            language: self.design_tab.selected_language.clone(),
+    /// Upload the Upload tab's file with its transcription as a new reference.
+    fn save_upload_reference(&mut self) -> Task<Message> {
+        if !self.upload_tab.can_save_reference() {
+            return Task::none();
+        }
+        let (Some(file_bytes), Some(file_name)) = (
+            self.upload_tab.file_bytes.clone(),
+            self.upload_tab.file_name.clone(),
+        ) else {
+            return Task::none();
         };
 
-        let base_url = self.api_base_url();
+        let ref_text = self.upload_tab.ref_text.clone();
+        let client = self.api.clone();
+        let convert = self.app_config.audio.convert_uploads_to_wav;
+        self.upload_tab.saving_reference = true;
+        let cache_edit = self.cache_edited_ref_text();
 
-        Task::perform(
+        let upload = Task::perform(
             async move {
-                ApiClient::new(&base_url)
-                    .voice_design(&request)
+                let (file_bytes, file_name) = tokio::task::spawn_blocking(move || {
+                    crate::audio::wav::prepare_upload(file_bytes, file_name, convert)
+                })
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| format!("Failed to convert audio to WAV: {e}"))?;
+
+                client
+                    .upload_reference(file_bytes, file_name, ref_text.as_deref())
                     .await
-                    .map(|resp| resp.task_id)
                     .map_err(|e| e.to_string())
             },
-            Message::TaskCreated,
-        )
+            Message::ReferenceUploaded,
+        );
+        Task::batch([cache_edit, upload])
+    }
+
+    fn start_design_generation(&mut self) -> Task<Message> {
+        let request = VoiceDesignRequest {
+            text: self.design_tab.editor.text(),
+            instruct: self.design_tab.instruct.clone(),
+            language: self.design_tab.selected_language.clone(),
+        };
+
+        self.submit_request(TaskRequest::VoiceDesign(request))
     }
 
     fn start_custom_generation(&mut self) -> Task<Message> {
@@ -965,24 +2968,13 @@ impl Qvox {
         };
 
         let request = CustomVoiceRequest {
-            text: self.custom_tab.text.clone(),
+            text: self.custom_tab.editor.text(),
             speaker,
             language: self.custom_tab.selected_language.clone(),
             instruct,
         };
 
-        let base_url = self.api_base_url();
-
-        Task::perform(
-            async move {
-                ApiClient::new(&base_url)
-                    .custom_voice(&request)
-                    .await
-                    .map(|resp| resp.task_id)
-                    .map_err(|e| e.to_string())
-            },
-            Message::TaskCreated,
-        )
+        self.submit_request(TaskRequest::CustomVoice(request))
     }
 
     fn start_multi_generation(&mut self) -> Task<Message> {
@@ -992,9 +2984,8 @@ impl Qvox {
             .iter()
             .filter_map(|seg| {
                 let ref_name = seg.selected_ref.as_ref()?;
-                let ref_audio = self.references.iter().find(|r| {
-                    r.name.as_deref().unwrap_or(&r.original_name) == ref_name.as_str()
-                })?;
+                let ref_audio =
+                    self.references.iter().find(|r| r.display_name() == ref_name.as_str())?;
                 Some(MultiSpeakerSegment {
                     text: seg.text.clone(),
                     ref_audio_id: ref_audio.id.clone(),
@@ -1008,96 +2999,385 @@ impl Qvox {
             return Task::none();
         }
 
-        let request = MultiSpeakerRequest { segments };
-        let base_url = self.api_base_url();
-
-        Task::perform(
-            async move {
-                ApiClient::new(&base_url)
-                    .clone_multi_speaker(&request)
-                    .await
-                    .map(|resp| resp.task_id)
-                    .map_err(|e| e.to_string())
-            },
-            Message::TaskCreated,
-        )
+        self.submit_request(TaskRequest::MultiSpeaker(MultiSpeakerRequest { segments }))
     }
 
-    #[allow(clippy::unused_self)]
-    fn start_transcription(&self, wav_bytes: Vec<u8>, hash: String) -> Task<Message> {
-        Task::perform(
-            async move {
-                // Ensure model is downloaded
-                if !crate::transcribe::whisper::model_exists() {
-                    crate::transcribe::whisper::download_model(|_, _| {})
-                        .await
-                        .map_err(|e| e.to_string())?;
-                }
+    /// Generations being submitted or still processing, across all tabs.
+    fn running_tasks(&self) -> usize {
+        self.submitting.len()
+            + self
+                .active_tasks
+                .iter()
+                .filter(|t| t.status == TaskStatus::Processing)
+                .count()
+    }
 
-                // Run transcription in a blocking thread
-                tokio::task::spawn_blocking(move || {
-                    let result = crate::transcribe::whisper::transcribe(&wav_bytes)
-                        .map_err(|e| e.to_string())?;
+    /// Whether `tab` has a generation being submitted or still processing.
+    fn is_generating(&self, tab: TabId) -> bool {
+        self.submitting.contains(&tab)
+            || self
+                .active_tasks
+                .iter()
+                .any(|t| t.tab == tab && t.status == TaskStatus::Processing)
+    }
 
-                    // Cache the result
-                    let _ = crate::transcribe::whisper::save_transcription_cache(&hash, &result);
+    /// The task `tab` shows: the most recent one started from it.
+    fn task_for(&self, tab: TabId) -> Option<&ActiveTask> {
+        self.active_tasks.iter().rev().find(|t| t.tab == tab)
+    }
 
-                    Ok(result)
-                })
-                .await
-                .map_err(|e| e.to_string())?
-            },
-            Message::TranscriptionDone,
-        )
+    fn task_mut(&mut self, task_id: &str) -> Option<&mut ActiveTask> {
+        self.active_tasks.iter_mut().find(|t| t.task_id == task_id)
     }
 
-    fn poll_task(&self) -> Task<Message> {
-        let Some(task) = &self.active_task else {
-            return Task::none();
-        };
+    /// Whether the server offers `model` and, once the health check has
+    /// answered, reports it loaded.
+    fn has_model(&self, model: &str) -> bool {
+        self.available_models.iter().any(|m| m == model)
+            && self
+                .loaded_models
+                .as_ref()
+                .is_none_or(|loaded| loaded.iter().any(|m| m == model))
+    }
 
-        let base_url = self.api_base_url();
-        let task_id = task.task_id.clone();
+    /// Whether `tab` can generate with the models the server has loaded.
+    fn tab_model_available(&self, tab: TabId) -> bool {
+        crate::views::common::required_model(tab).is_some_and(|model| self.has_model(model))
+    }
 
-        Task::perform(
-            async move {
-                ApiClient::new(&base_url)
-                    .task_status(&task_id)
-                    .await
-                    .map_err(|e| e.to_string())
+    /// The Generate message for the active tab, when its Generate button
+    /// would be enabled and the tab isn't generating yet.
+    fn generate_shortcut(&self) -> Option<Message> {
+        if self.is_generating(self.active_tab) {
+            return None;
+        }
+        let model_available = self.tab_model_available(self.active_tab);
+        let (ready, generate) = match self.active_tab {
+            TabId::Clone => (
+                !self.clone_tab.editor.is_empty()
+                    && self.clone_tab.selected_ref.is_some()
+                    && model_available,
+                Message::CloneGenerate,
+            ),
+            TabId::Upload => (
+                !self.upload_tab.editor.is_empty()
+                    && self.upload_tab.file_bytes.is_some()
+                    && !self.upload_tab.transcribing
+                    && self.recording_state() == RecordingState::Idle
+                    && model_available,
+                Message::UploadGenerate,
+            ),
+            TabId::MultiSpeaker => (
+                !self.multi_tab.segments.is_empty()
+                    && self
+                        .multi_tab
+                        .segments
+                        .iter()
+                        .all(|s| !s.text.is_empty() && s.selected_ref.is_some())
+                    && model_available,
+                Message::MultiGenerate,
+            ),
+            TabId::VoiceDesign => (
+                !self.design_tab.editor.is_empty()
+                    && !self.design_tab.instruct.is_empty()
+                    && model_available,
+                Message::DesignGenerate,
+            ),
+            TabId::CustomVoice => (
+                !self.custom_tab.editor.is_empty()
+                    && self.custom_tab.selected_speaker.is_some()
+                    && model_available,
+                Message::CustomGenerate,
+            ),
+            TabId::Settings => return None,
+        };
+        ready.then_some(generate)
+    }
+
+    /// Send `request` now, or queue it when `MAX_CONCURRENT_TASKS`
+    /// generations are already running.
+    fn submit_request(&mut self, request: TaskRequest) -> Task<Message> {
+        if self.running_tasks() >= MAX_CONCURRENT_TASKS {
+            self.pending_requests.push(request);
+            self.persist_queue();
+            Task::none()
+        } else {
+            self.dispatch_request(request)
+        }
+    }
+
+    /// Send queued requests, oldest first, until the free task slots are
+    /// used up.
+    fn dispatch_next_queued(&mut self) -> Task<Message> {
+        let free = MAX_CONCURRENT_TASKS.saturating_sub(self.running_tasks());
+        let count = free.min(self.pending_requests.len());
+        if count == 0 {
+            return Task::none();
+        }
+        let requests: Vec<TaskRequest> = self.pending_requests.drain(..count).collect();
+        self.persist_queue();
+        Task::batch(requests.into_iter().map(|r| self.dispatch_request(r)))
+    }
+
+    fn dispatch_request(&mut self, request: TaskRequest) -> Task<Message> {
+        let client = self.api.clone();
+        let tab = request.tab();
+        self.submitting.push(tab);
+
+        Task::perform(
+            async move {
+                let (response, latency) = timed(async {
+                    match &request {
+                        TaskRequest::Clone(r) => client.clone_voice(r).await,
+                        TaskRequest::VoiceDesign(r) => client.voice_design(r).await,
+                        TaskRequest::CustomVoice(r) => client.custom_voice(r).await,
+                        TaskRequest::MultiSpeaker(r) => client.clone_multi_speaker(r).await,
+                    }
+                })
+                .await;
+                let result = response
+                    .map(|resp| (resp.task_id, resp.estimated_time, latency))
+                    .map_err(|e| e.to_string());
+                (request, result)
             },
-            Message::TaskProgress,
+            move |(request, result)| Message::TaskCreated(tab, Some(request), result),
         )
     }
 
-    fn fetch_task_audio(&self) -> Task<Message> {
-        let Some(task) = &self.active_task else {
+    /// Add task `task_id`, which just finished, to the history and save it.
+    /// Upload tasks carry no request, so they are described from the tab.
+    fn record_history(&mut self, task_id: &str) {
+        let Some(task) = self.active_tasks.iter().find(|t| t.task_id == task_id) else {
+            return;
+        };
+        let entry = match (&task.request, &task.upload) {
+            (Some(request), _) => {
+                HistoryEntry::from_request(request, &self.references, task.elapsed_secs)
+            }
+            (None, Some(upload)) => HistoryEntry::from_upload(
+                upload.text.clone(),
+                upload.language.clone(),
+                Some(upload.file_name.clone()),
+                task.elapsed_secs,
+            ),
+            (None, None) => return,
+        };
+        crate::history::push_entry(&mut self.history, entry);
+        if let Err(e) = crate::history::save_history(&self.data_dir, &self.history) {
+            tracing::error!("failed to save generation history: {e:#}");
+            self.push_error(format!("Failed to save the generation history: {e}"));
+        }
+    }
+
+    /// Refill the tab a history entry came from and switch to it. The user
+    /// reviews the inputs and presses Generate.
+    fn rerun_history(&mut self, index: usize) {
+        let Some(entry) = self.history.get(index).cloned() else {
+            return;
+        };
+        let ref_name = |this: &Self, id: &str| {
+            this.references
+                .iter()
+                .find(|r| r.id == id)
+                .map(|r| r.display_name().to_owned())
+        };
+        match entry.request {
+            Some(TaskRequest::Clone(r)) => {
+                self.clone_tab.selected_ref = ref_name(self, &r.ref_audio_id);
+                self.clone_tab.set_text(&r.text);
+                self.clone_tab.selected_language = r.language;
+            }
+            Some(TaskRequest::VoiceDesign(r)) => {
+                self.design_tab.set_text(&r.text);
+                self.design_tab.instruct = r.instruct;
+                self.design_tab.selected_language = r.language;
+            }
+            Some(TaskRequest::CustomVoice(r)) => {
+                self.custom_tab.set_text(&r.text);
+                self.custom_tab.selected_speaker = Some(r.speaker);
+                self.custom_tab.instruct = r.instruct.unwrap_or_default();
+                self.custom_tab.selected_language = r.language;
+            }
+            Some(TaskRequest::MultiSpeaker(r)) => {
+                self.multi_tab.segments = r
+                    .segments
+                    .into_iter()
+                    .map(|s| crate::views::multispeaker_tab::SegmentState {
+                        selected_ref: ref_name(self, &s.ref_audio_id),
+                        text: s.text,
+                        selected_language: s.language,
+                        unmatched_speaker: None,
+                    })
+                    .collect();
+            }
+            None => {
+                self.upload_tab.set_text(&entry.text);
+                self.upload_tab.selected_language = entry.language;
+            }
+        }
+        self.select_tab(entry.tab);
+    }
+
+    /// Write the pending and not-yet-resumed queue to disk, reporting a
+    /// failure in the error banner.
+    fn persist_queue(&mut self) {
+        let all: Vec<TaskRequest> = self
+            .pending_requests
+            .iter()
+            .chain(&self.restored_requests)
+            .cloned()
+            .collect();
+        let result = if all.is_empty() {
+            crate::queue::clear_queue(&self.data_dir)
+        } else {
+            crate::queue::save_queue(&self.data_dir, &all)
+        };
+        if let Err(e) = result {
+            tracing::error!("failed to persist generation queue: {e:#}");
+            self.push_error(format!("Failed to save the generation queue: {e}"));
+        }
+    }
+
+    /// Load the queue saved by the last session, dropping entries whose
+    /// references no longer exist.
+    fn restore_queue(&mut self) {
+        let saved = crate::queue::load_queue(&self.data_dir);
+        if saved.is_empty() {
+            return;
+        }
+        let (kept, skipped) = crate::queue::drop_stale(saved, &self.references);
+        if skipped > 0 {
+            self.push_error(format!(
+                "Skipped {skipped} queued generation(s) whose reference audio was deleted"
+            ));
+        }
+        self.restored_requests = kept;
+        self.persist_queue();
+    }
+
+    /// Transcribe `wav_bytes`, downloading the Whisper model first if needed.
+    ///
+    /// A missing model is fetched on its own and `ModelDownloaded` restarts
+    /// transcription, so the busy indicator can tell the two phases apart.
+    fn start_transcription(&mut self, wav_bytes: Vec<u8>, hash: String) -> Task<Message> {
+        self.upload_tab.transcribing = true;
+        let model = self.app_config.audio.whisper_model;
+        let language =
+            crate::transcribe::whisper::whisper_language_code(&self.upload_tab.selected_language);
+
+        if !crate::transcribe::whisper::model_exists(&self.data_dir, model) {
+            return self.download_whisper_model(model);
+        }
+
+        self.busy = Some(BusyReason::Transcribing);
+        self.upload_tab.transcription_progress = 0;
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.transcription_cancel = Some(Arc::clone(&cancel));
+        Task::stream(transcription_stream(
+            wav_bytes,
+            self.data_dir.clone(),
+            hash,
+            model,
+            language,
+            cancel,
+        ))
+    }
+
+    /// Fetch `model`, reporting progress through `ModelDownloadProgress` and
+    /// finishing with `ModelDownloaded`.
+    fn download_whisper_model(&mut self, model: WhisperModel) -> Task<Message> {
+        self.busy = Some(BusyReason::DownloadingModel);
+        self.model_download = Some((0, 0));
+        Task::stream(model_download_stream(self.data_dir.clone(), model))
+    }
+
+    /// Whether `task_id` is a known task that is still being polled.
+    fn is_polling(&self, task_id: &str) -> bool {
+        self.active_tasks
+            .iter()
+            .any(|t| t.task_id == task_id && t.status == TaskStatus::Processing)
+    }
+
+    fn apply_task_progress(
+        &mut self,
+        task_id: &str,
+        result: Result<TaskStatusResponse, String>,
+    ) -> Task<Message> {
+        let Some(task) = self.task_mut(task_id) else {
             return Task::none();
         };
+        match result {
+            Ok(resp) => {
+                task.update_progress(&resp);
+                let batch_status = match resp.status {
+                    TaskStatus::Processing => None,
+                    TaskStatus::Completed => Some(BatchLineStatus::Done),
+                    TaskStatus::Failed => Some(BatchLineStatus::Failed),
+                    TaskStatus::Cancelled => Some(BatchLineStatus::Stopped),
+                };
+                if let Some(status) = batch_status {
+                    self.clone_tab.batch_task_finished(task_id, status);
+                }
+                match resp.status {
+                    TaskStatus::Processing => Task::none(),
+                    TaskStatus::Completed => self.fetch_task_audio(task_id.to_owned()),
+                    TaskStatus::Failed | TaskStatus::Cancelled => self.dispatch_next_queued(),
+                }
+            }
+            Err(e) => {
+                // The task can't be followed any more; free its slot.
+                task.status = TaskStatus::Failed;
+                "Lost contact with the generation".clone_into(&mut task.status_text);
+                task.error = Some(e);
+                self.clone_tab.batch_task_finished(task_id, BatchLineStatus::Failed);
+                self.dispatch_next_queued()
+            }
+        }
+    }
+
+    /// Poll every task that is still processing.
+    fn poll_tasks(&self) -> Task<Message> {
+        let polls: Vec<Task<Message>> = self
+            .active_tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Processing)
+            .map(|task| {
+                let client = self.api.clone();
+                let task_id = task.task_id.clone();
+                Task::perform(
+                    async move {
+                        let result = client
+                            .task_status(&task_id)
+                            .await
+                            .map_err(|e| e.to_string());
+                        (task_id, result)
+                    },
+                    |(task_id, result)| Message::TaskProgress(task_id, result),
+                )
+            })
+            .collect();
+        Task::batch(polls)
+    }
 
-        let base_url = self.api_base_url();
-        let task_id = task.task_id.clone();
+    fn fetch_task_audio(&self, task_id: String) -> Task<Message> {
+        let client = self.api.clone();
 
         Task::perform(
             async move {
-                ApiClient::new(&base_url)
+                let result = client
                     .task_audio(&task_id)
                     .await
-                    .map_err(|e| e.to_string())
+                    .map_err(|e| e.to_string());
+                (task_id, result)
             },
-            Message::TaskAudioLoaded,
+            |(task_id, result)| Message::TaskAudioLoaded(task_id, result),
         )
     }
 
     fn fetch_generated_list(&self) -> Task<Message> {
-        let base_url = self.api_base_url();
+        let client = self.api.clone();
         Task::perform(
-            async move {
-                ApiClient::new(&base_url)
-                    .generated_list()
-                    .await
-                    .map_err(|e| e.to_string())
-            },
+            async move { client.generated_list().await },
             Message::GeneratedListLoaded,
         )
     }
@@ -1118,7 +3398,12 @@ impl Qvox {
             text(format!("Models: {}", self.app_config.server.models.join(", "))).size(12);
         let device_text = text(format!("Device: {}", self.app_config.server.device)).size(12);
 
-        let mut col = column![title, progress_bar(0.0..=100.0, 0.0), status, elapsed,]
+        let pulse = if self.errors.is_empty() {
+            loading_pulse(self.elapsed_secs)
+        } else {
+            0.0
+        };
+        let mut col = column![title, progress_bar(0.0..=100.0, pulse), status, elapsed,]
             .spacing(12)
             .padding(40)
             .width(Length::Fixed(400.0))
@@ -1126,83 +3411,283 @@ impl Qvox {
 
         col = col.push(models_text).push(device_text);
 
-        if let Some(err) = &self.error {
-            col = col.push(text(err).size(14));
+        if !self.model_load_errors.is_empty() {
+            col = col.push(text("The server could not load some models:").size(14));
+            for err in &self.model_load_errors {
+                col = col.push(text(err).size(12).style(text::danger));
+            }
+        }
+
+        if self.slow_start && self.errors.is_empty() {
+            col = col.push(
+                text("Still loading. Large models can take a while; check the server log below.")
+                    .size(12),
+            );
+        }
+
+        if !self.errors.is_empty() {
+            for err in &self.errors {
+                col = col.push(text(&err.text).size(14));
+            }
+            col = col.push(
+                row![
+                    button(text("Restart Server")).on_press(Message::ServerRestart),
+                    button(text("Open Settings")).on_press(Message::OpenSettings),
+                ]
+                .spacing(8),
+            );
+        }
+
+        if let Some(mgr) = &self.server {
+            col = col.push(self.view_server_logs(mgr.recent_logs()));
         }
 
         center(container(col).center_x(Length::Fill)).into()
     }
 
+    /// Kill the current server, if any, and spawn a new one with `app_config`.
+    fn restart_server(&mut self) -> Task<Message> {
+        // An adopted server wasn't started with these settings and can't be
+        // restarted from here; once it's gone, look for or spawn one again.
+        if !self.server_lost && self.server.as_ref().is_some_and(ServerManager::is_attached) {
+            self.push_error(
+                "Server settings apply only to a server qvox spawned. \
+                 Restart the running server yourself to apply them."
+                    .to_owned(),
+            );
+            return Task::none();
+        }
+        self.safe_mode = false;
+        if let Some(server) = &mut self.server {
+            server.kill();
+        }
+        self.server = None;
+        self.refresh_api_client();
+        self.screen = Screen::Loading;
+        self.elapsed_secs = 0;
+        self.slow_start = false;
+        self.server_lost = false;
+        self.model_load_errors.clear();
+        self.errors.clear();
+        "Restarting server...".clone_into(&mut self.loading_status);
+        Task::done(Message::ServerSpawned)
+    }
+
+    /// Collapsible panel with the server's recent stdout/stderr.
+    fn view_server_logs(&self, logs: Vec<String>) -> Element<'_, Message> {
+        let label = if self.show_server_logs {
+            "Hide server log"
+        } else {
+            "Show server log"
+        };
+        let toggle = button(text(label).size(12)).on_press(Message::ServerLogsToggled);
+        if !self.show_server_logs {
+            return toggle.into();
+        }
+
+        let body = if logs.is_empty() {
+            "No output yet.".to_owned()
+        } else {
+            logs.join("\n")
+        };
+        column![
+            toggle,
+            scrollable(text(body).size(11).font(iced::Font::MONOSPACE))
+                .height(Length::Fixed(240.0))
+                .anchor_bottom(),
+        ]
+        .spacing(4)
+        .into()
+    }
+
     fn view_main(&self) -> Element<'_, Message> {
         let tab_bar = self.view_tab_bar();
 
-        let models = &self.available_models;
         let tab_content = match self.active_tab {
             TabId::Clone => crate::views::clone_tab::view(
                 &self.clone_tab,
                 &self.references,
+                &self.app_config.ui.preview_offsets,
                 &self.languages,
-                self.active_task.as_ref(),
-                self.playback_state(),
-                models.iter().any(|m| m == "base"),
+                self.task_for(TabId::Clone),
+                self.last_results.contains_key(&TabId::Clone),
+                self.playback_status(),
+                self.tab_model_available(TabId::Clone),
+                self.app_config.ui.enter_to_generate,
+                self.app_config.ui.text_soft_limit,
             ),
             TabId::Upload => crate::views::upload_tab::view(
                 &self.upload_tab,
                 &self.languages,
-                self.active_task.as_ref(),
-                self.playback_state(),
+                self.task_for(TabId::Upload),
+                self.submitting.contains(&TabId::Upload),
+                self.last_results.contains_key(&TabId::Upload),
+                self.playback_status(),
                 self.recording_state(),
                 self.recorder.as_ref().map_or(0.0, Recorder::elapsed_secs),
-                models.iter().any(|m| m == "base"),
+                self.recorder.as_ref().map_or(0.0, Recorder::current_level),
+                &self.input_devices,
+                self.selected_input_device(),
+                self.tab_model_available(TabId::Upload),
+                self.app_config.ui.enter_to_generate,
+                self.app_config.ui.text_soft_limit,
             ),
             TabId::VoiceDesign => crate::views::design_tab::view(
                 &self.design_tab,
                 &self.languages,
-                self.active_task.as_ref(),
-                self.playback_state(),
-                models.iter().any(|m| m == "voice_design"),
+                self.task_for(TabId::VoiceDesign),
+                self.last_results.contains_key(&TabId::VoiceDesign),
+                self.playback_status(),
+                self.tab_model_available(TabId::VoiceDesign),
+                self.app_config.ui.enter_to_generate,
+                self.app_config.ui.text_soft_limit,
             ),
             TabId::CustomVoice => crate::views::custom_tab::view(
                 &self.custom_tab,
                 &self.speakers,
                 &self.languages,
-                self.active_task.as_ref(),
-                self.playback_state(),
-                models.iter().any(|m| m == "custom_voice"),
+                self.task_for(TabId::CustomVoice),
+                self.last_results.contains_key(&TabId::CustomVoice),
+                self.playback_status(),
+                self.tab_model_available(TabId::CustomVoice),
+                self.app_config.ui.enter_to_generate,
+                self.app_config.ui.text_soft_limit,
             ),
             TabId::MultiSpeaker => crate::views::multispeaker_tab::view(
                 &self.multi_tab,
                 &self.references,
                 &self.languages,
-                self.active_task.as_ref(),
-                self.playback_state(),
-                models.iter().any(|m| m == "base"),
+                self.task_for(TabId::MultiSpeaker),
+                self.last_results.contains_key(&TabId::MultiSpeaker),
+                self.playback_status(),
+                self.tab_model_available(TabId::MultiSpeaker),
+                self.app_config.ui.enter_to_generate,
             ),
             TabId::Settings => crate::views::settings::view(
                 &self.edit_config,
+                self.port_input.as_deref(),
                 self.settings_dirty,
+                self.detected_device.and_then(|detected| {
+                    crate::server::manager::device_suggestion(
+                        detected,
+                        &self.edit_config.server.device,
+                    )
+                }),
+                &self.device_options,
+                self.diagnostics.as_deref(),
+                self.diagnostics_running,
+                self.all_paths(),
+                &self.output_devices,
+                &self.storage,
+                self.model_download,
             ),
         };
 
-        let generated = crate::views::generated_list::view(&self.generated_list);
+        let references = if self.active_tab == TabId::Clone {
+            crate::views::references::view(
+                &self.references,
+                &self.reference_name_drafts,
+                self.pending_delete.as_ref(),
+            )
+        } else {
+            column![].into()
+        };
+
+        let generated = crate::views::generated_list::view(
+            &self.generated_list,
+            &self.expanded_generated,
+            &self.generated_filter,
+            self.generated_sort,
+            &self.selected_generated,
+            self.pending_delete.as_ref(),
+            self.last_deleted.as_ref(),
+            self.app_config.ui.export_include_reference,
+            self.app_config.ui.export_peak_db(),
+            self.last_export.as_ref(),
+            self.playing_id.as_deref(),
+        );
 
         let mut main_col = column![tab_bar].spacing(0).width(Length::Fill);
 
-        // Error banner
-        if let Some(err) = &self.error {
+        if self.safe_mode {
+            main_col = main_col.push(
+                text(
+                    "Safe mode: the server was not started and generation is disabled. \
+                     Fix your settings, then Save & Restart.",
+                )
+                .size(13)
+                .width(Length::Fill),
+            );
+        }
+
+        if !self.restored_requests.is_empty() {
             main_col = main_col.push(
                 row![
-                    text(err).size(13),
-                    button(text("Dismiss")).on_press(Message::ErrorDismiss),
+                    text(format!(
+                        "{} queued generation(s) from the last session",
+                        self.restored_requests.len()
+                    ))
+                    .size(13),
+                    button(text("Resume")).on_press(Message::QueueResume),
+                    button(text("Discard")).on_press(Message::QueueDiscard),
                 ]
                 .spacing(8)
                 .padding(8),
             );
         }
+        if !self.pending_requests.is_empty() {
+            main_col = main_col.push(
+                text(format!("{} generation(s) queued", self.pending_requests.len()))
+                    .size(12),
+            );
+        }
+        if let Some(loaded) = &self.loaded_models {
+            let names = if loaded.is_empty() {
+                "none".to_owned()
+            } else {
+                loaded.join(", ")
+            };
+            main_col = main_col.push(text(format!("Loaded models: {names}")).size(11));
+        }
+        if let Some(latency) = self.last_submit_latency {
+            main_col = main_col.push(
+                text(format!(
+                    "Last submit: {}",
+                    crate::views::common::format_latency(latency)
+                ))
+                .size(11),
+            );
+        }
+        if let Some(busy) = self.busy {
+            let percent = match (busy, self.model_download) {
+                (BusyReason::DownloadingModel, Some((downloaded, total))) if total > 0 => {
+                    format!(" {}%", downloaded * 100 / total)
+                }
+                _ => String::new(),
+            };
+            main_col = main_col
+                .push(text(format!("Working: {}...{percent}", busy.label())).size(12));
+        }
+
+        // Error banners, one per error
+        for err in &self.errors {
+            let mut banner = row![text(&err.text).size(13)].spacing(8).padding(8);
+            if err.retry.is_some() {
+                banner =
+                    banner.push(button(text("Retry")).on_press(Message::ErrorRetry(err.id)));
+            }
+            banner = banner.push(button(text("Dismiss")).on_press(Message::ErrorDismiss(err.id)));
+            main_col = main_col.push(banner);
+        }
 
         main_col = main_col.push(
             scrollable(
-                column![tab_content, generated]
+                column![
+                    tab_content,
+                    references,
+                    generated,
+                    crate::views::history::view(&self.history),
+                ]
                     .spacing(16)
                     .width(Length::Fill),
             )
@@ -1222,7 +3707,12 @@ impl Qvox {
             ("Settings", TabId::Settings),
         ];
 
-        let mut tab_row = row![].spacing(0);
+        let mut back_btn = button(text("Back").size(13));
+        if self.previous_tab.is_some() {
+            back_btn = back_btn.on_press(Message::TabBack);
+        }
+
+        let mut tab_row = row![back_btn].spacing(0);
         for (label, id) in tabs {
             let btn = if self.active_tab == id {
                 button(text(label).size(13))
@@ -1236,3 +3726,1448 @@ impl Qvox {
     }
     // LCOV_EXCL_STOP
 }
+
+/// Seconds one sweep of the indeterminate loading bar takes.
+const LOADING_PULSE_SECS: u64 = 5;
+
+/// Loading bar value that sweeps 0 to 100 every `LOADING_PULSE_SECS`, so a
+/// long model load visibly isn't frozen.
+fn loading_pulse(elapsed_secs: u64) -> f32 {
+    #[allow(clippy::cast_precision_loss)]
+    let step = (elapsed_secs % LOADING_PULSE_SECS + 1) as f32;
+    #[allow(clippy::cast_precision_loss)]
+    let steps = LOADING_PULSE_SECS as f32;
+    step * 100.0 / steps
+}
+
+/// Clamp a preview offset (seconds) to `[0, clip_secs]`.
+fn clamp_preview_offset(offset_secs: f32, clip_secs: Option<f32>) -> Duration {
+    let max = clip_secs.unwrap_or(f32::MAX);
+    Duration::from_secs_f32(offset_secs.clamp(0.0, max.max(0.0)))
+}
+
+/// Download `model`, reporting progress each time another percent (or,
+/// without a known size, another MiB) arrives, then `ModelDownloaded`.
+fn model_download_stream(
+    data_dir: std::path::PathBuf,
+    model: WhisperModel,
+) -> impl Stream<Item = Message> {
+    iced::stream::channel(16, move |mut output: mpsc::Sender<Message>| async move {
+        let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let handle = tokio::spawn(async move {
+            let last_step = std::cell::Cell::new(None);
+            crate::transcribe::whisper::download_model(&data_dir, model, move |downloaded, total| {
+                let step = if total > 0 { downloaded * 100 / total } else { downloaded >> 20 };
+                if last_step.replace(Some(step)) != Some(step) {
+                    let _ = progress_tx.send((downloaded, total));
+                }
+            })
+            .await
+            .map_err(|e| e.to_string())
+        });
+
+        // The progress sender is dropped with the download, ending this loop.
+        while let Some((downloaded, total)) = progress_rx.recv().await {
+            let _ = output
+                .send(Message::ModelDownloadProgress(downloaded, total))
+                .await;
+        }
+        let result = handle.await.map_err(|e| e.to_string()).and_then(|r| r);
+        let _ = output.send(Message::ModelDownloaded(result)).await;
+    })
+}
+
+/// Transcribe `wav_bytes` on a blocking thread, yielding
+/// `TranscriptionProgress` updates followed by `TranscriptionDone`.
+fn transcription_stream(
+    wav_bytes: Vec<u8>,
+    data_dir: std::path::PathBuf,
+    hash: String,
+    model: WhisperModel,
+    language: Option<&'static str>,
+    cancel: Arc<AtomicBool>,
+) -> impl Stream<Item = Message> {
+    iced::stream::channel(16, move |mut output: mpsc::Sender<Message>| async move {
+        let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let abort = Arc::clone(&cancel);
+        let cache_key = hash.clone();
+        let handle = tokio::task::spawn_blocking(move || {
+            let result = crate::transcribe::whisper::transcribe(
+                &data_dir,
+                &wav_bytes,
+                model,
+                language,
+                abort,
+                move |percent| {
+                    let _ = progress_tx.send(percent);
+                },
+            )
+            .map_err(|e| e.to_string())?;
+
+            // Cache the result
+            let _ = crate::transcribe::whisper::save_transcription_cache(
+                &data_dir,
+                &cache_key,
+                model,
+                language,
+                &result,
+            );
+
+            Ok(result)
+        });
+
+        // The progress sender is dropped with the blocking closure, ending this loop.
+        while let Some(percent) = progress_rx.recv().await {
+            let _ = output.send(Message::TranscriptionProgress(percent)).await;
+        }
+        let result = handle.await.map_err(|e| e.to_string()).and_then(|r| r);
+        let outcome = (!cancel.load(Ordering::Relaxed)).then_some(result);
+        let _ = output.send(Message::TranscriptionDone(hash, outcome)).await;
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{generated, reference};
+
+    /// An app with default config, independent of the user's config file.
+    /// Removes a test's data dir when the test ends, pass or fail.
+    struct ScratchDir(std::path::PathBuf);
+
+    impl Drop for ScratchDir {
+        fn drop(&mut self) {
+            std::fs::remove_dir_all(&self.0).ok();
+        }
+    }
+
+    /// A default app on a fresh scratch data dir, with nothing loaded from disk.
+    fn test_app() -> (Qvox, ScratchDir) {
+        let data_dir =
+            std::env::temp_dir().join(format!("qvox_test_app_{}", uuid::Uuid::new_v4()));
+        let app = Qvox::with_data(data_dir.clone(), AppConfig::default(), Vec::new());
+        (app, ScratchDir(data_dir))
+    }
+
+    #[test]
+    fn recorded_take_is_marked_for_preview() {
+        let (mut app, _dir) = test_app();
+        let name = "recording.wav".to_owned();
+        let _ = app.select_upload_audio(name.clone().into(), vec![1, 2, 3], name, true);
+        assert!(app.upload_tab.recorded);
+        assert_eq!(app.upload_tab.file_bytes.as_deref(), Some(&[1, 2, 3][..]));
+        assert!(app.upload_tab.waveform.is_empty());
+
+        let _ = app.update(Message::UploadFileSelected(
+            "voice.wav".into(),
+            vec![4, 5, 6],
+            "voice.wav".to_owned(),
+        ));
+        assert!(!app.upload_tab.recorded);
+    }
+
+    #[test]
+    fn long_reference_is_trimmed_on_selection() {
+        let (mut app, _dir) = test_app();
+        app.app_config.ui.trim_long_references = true;
+        app.app_config.ui.max_reference_secs = 5.0;
+        let wav = crate::audio::recorder::samples_to_wav(&[0.1; 8_000 * 6], 8_000)
+            .expect("encode");
+        let name = "long.wav".to_owned();
+        let _ = app.select_upload_audio(name.clone().into(), wav.clone(), name, false);
+        assert_eq!(app.upload_tab.trimmed_from_secs, Some(6.0));
+        let info = app.upload_tab.wav_info.expect("wav info");
+        assert!((info.duration_secs - 5.0).abs() < 1e-3);
+        assert_eq!(app.upload_tab.waveform.len(), WAVEFORM_COLUMNS);
+
+        app.app_config.ui.trim_long_references = false;
+        let name = "long.wav".to_owned();
+        let _ = app.select_upload_audio(name.clone().into(), wav.clone(), name, false);
+        assert_eq!(app.upload_tab.trimmed_from_secs, None);
+        assert_eq!(app.upload_tab.file_bytes, Some(wav));
+    }
+
+    #[test]
+    fn missing_input_device_falls_back_with_notice() {
+        let (mut app, _dir) = test_app();
+        app.app_config.audio.input_device = Some("USB Microphone".to_owned());
+        app.set_input_devices(vec!["Built-in Microphone".to_owned()]);
+        assert_eq!(app.selected_input_device(), None);
+        assert!(app.upload_tab.input_device_notice.is_some());
+    }
+
+    #[test]
+    fn present_input_device_is_selected() {
+        let (mut app, _dir) = test_app();
+        app.app_config.audio.input_device = Some("USB Microphone".to_owned());
+        app.set_input_devices(vec!["USB Microphone".to_owned()]);
+        assert_eq!(app.selected_input_device().map(String::as_str), Some("USB Microphone"));
+        assert!(app.upload_tab.input_device_notice.is_none());
+    }
+
+    #[test]
+    fn server_restart_returns_to_loading_without_touching_config() {
+        let (mut app, _dir) = test_app();
+        app.screen = Screen::Main;
+        app.elapsed_secs = 42;
+        app.push_sticky_error("Server process exited unexpectedly.".to_owned());
+        app.edit_config.server.port = 9000;
+        app.settings_dirty = true;
+
+        let _ = app.update(Message::ServerRestart);
+        assert!(matches!(app.screen, Screen::Loading));
+        assert_eq!(app.elapsed_secs, 0);
+        assert!(app.errors.is_empty());
+        assert_eq!(app.app_config.server.port, 8000);
+        assert!(app.settings_dirty);
+    }
+
+    #[test]
+    fn running_server_is_adopted_instead_of_spawned() {
+        let (mut app, _dir) = test_app();
+        app.app_config.server.port = 8123;
+        app.app_config.server.use_existing_server = true;
+        let _ = app.update(Message::ServerSpawned);
+        assert!(app.server.is_none());
+        assert_eq!(app.loading_status, "Looking for a running server...");
+
+        let _ = app.update(Message::ExistingServerProbed(Some("127.0.0.1".to_owned())));
+        let server = app.server.as_ref().expect("attached");
+        assert!(server.is_attached());
+        assert_eq!(server.base_url(), "http://127.0.0.1:8123");
+    }
+
+    #[test]
+    fn attached_server_is_not_restarted_until_it_stops_answering() {
+        let (mut app, _dir) = test_app();
+        app.screen = Screen::Main;
+        app.server = Some(ServerManager::attach("127.0.0.1", 8123));
+
+        let _ = app.update(Message::ServerRestart);
+        assert!(matches!(app.screen, Screen::Main));
+        assert!(app.server.is_some());
+        assert_eq!(app.errors.len(), 1);
+
+        let _ = app.update(Message::AttachedServerProbed(true));
+        assert!(!app.server_lost);
+        let _ = app.update(Message::AttachedServerProbed(false));
+        assert!(app.server_lost);
+
+        let _ = app.update(Message::ServerRestart);
+        assert!(matches!(app.screen, Screen::Loading));
+        assert!(app.server.is_none());
+    }
+
+    #[test]
+    fn invalid_port_is_kept_as_text_and_blocks_save() {
+        let (mut app, _dir) = test_app();
+        app.screen = Screen::Main;
+        app.edit_config.server.device = "cpu".to_owned();
+        app.settings_dirty = true;
+        let _ = app.update(Message::SettingsPortChanged("80a".to_owned()));
+        assert_eq!(app.port_input.as_deref(), Some("80a"));
+        assert_eq!(app.edit_config.server.port, 8000);
+        let _ = app.update(Message::SettingsSave);
+        assert!(matches!(app.screen, Screen::Main));
+        assert_eq!(app.app_config.server.device, "auto");
+
+        let _ = app.update(Message::SettingsPortChanged("9001".to_owned()));
+        assert!(app.port_input.is_none());
+        assert_eq!(app.edit_config.server.port, 9001);
+        assert!(app.settings_dirty);
+    }
+
+    #[test]
+    fn bad_script_path_saves_the_other_settings() {
+        let (mut app, _dir) = test_app();
+        app.screen = Screen::Main;
+        let old_path = app.app_config.server.script_path.clone();
+        app.edit_config.server.script_path = "/nonexistent/start_server.py".to_owned();
+        app.edit_config.ui.text_soft_limit = 500;
+        let _ = app.update(Message::SettingsSave);
+
+        assert!(matches!(app.screen, Screen::Main));
+        assert_eq!(app.errors.len(), 1);
+        assert!(app.settings_dirty);
+        let saved = crate::config::load(&app.data_dir);
+        assert_eq!(saved.server.script_path, old_path);
+        assert_eq!(saved.ui.text_soft_limit, 500);
+        assert_eq!(app.app_config, saved);
+    }
+
+    #[test]
+    fn settings_reset_restores_defaults_without_saving() {
+        let (mut app, _dir) = test_app();
+        app.app_config.server.script_path = "broken.py".to_owned();
+        app.edit_config = app.app_config.clone();
+        app.port_input = Some("abc".to_owned());
+
+        let _ = app.update(Message::SettingsReset);
+        assert_eq!(app.edit_config, AppConfig::default());
+        assert_eq!(app.app_config.server.script_path, "broken.py");
+        assert!(app.port_input.is_none());
+        assert!(app.settings_dirty);
+    }
+
+    #[test]
+    fn active_tab_is_saved_and_restored_on_launch() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::TabSelected(TabId::MultiSpeaker));
+        assert_eq!(app.app_config.ui.active_tab, TabId::MultiSpeaker);
+        assert!(!app.settings_dirty);
+        assert!(!crate::config::config_path(&app.data_dir).exists());
+
+        let _ = app.update(Message::WindowCloseRequested(iced::window::Id::unique()));
+        let saved = crate::config::load(&app.data_dir);
+        assert_eq!(saved.ui.active_tab, TabId::MultiSpeaker);
+
+        let (mut launched, _launched_dir) = test_app();
+        launched.app_config.ui.active_tab = TabId::VoiceDesign;
+        let _ = launched.update(Message::ServerReady);
+        assert_eq!(launched.active_tab, TabId::VoiceDesign);
+    }
+
+    #[test]
+    fn loop_toggle_shows_in_playback_status() {
+        let (mut app, _dir) = test_app();
+        assert!(!app.playback_status().looping);
+        let _ = app.update(Message::PlaybackToggleLoop);
+        assert!(app.playback_status().looping);
+        let _ = app.update(Message::PlaybackToggleLoop);
+        assert!(!app.playback_status().looping);
+    }
+
+    #[test]
+    fn silence_threshold_setting_is_clamped() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::SettingsSilenceThresholdChanged(-75.0));
+        assert!((app.app_config.ui.silence_threshold_db + 60.0).abs() < f32::EPSILON);
+        assert!(!app.settings_dirty);
+
+        let _ = app.update(Message::SettingsSilenceThresholdChanged(f32::NAN));
+        assert!(
+            (app.app_config.ui.silence_threshold_db
+                - crate::audio::processing::RECORDING_SILENCE_DB)
+                .abs()
+                < f32::EPSILON
+        );
+    }
+
+    #[test]
+    fn max_reference_secs_setting_is_clamped() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::SettingsMaxReferenceSecsChanged(120.0));
+        assert!((app.app_config.ui.max_reference_secs - 60.0).abs() < f32::EPSILON);
+        let _ = app.update(Message::SettingsMaxReferenceSecsChanged(f32::NAN));
+        assert!((app.app_config.ui.max_reference_secs - 30.0).abs() < f32::EPSILON);
+        assert!(!app.settings_dirty);
+    }
+
+    #[test]
+    fn export_peak_ignores_nan() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::ExportPeakChanged(f32::NAN));
+        assert!((app.app_config.ui.export_peak_db + 1.0).abs() < f32::EPSILON);
+        assert_eq!(app.app_config, app.edit_config);
+    }
+
+    #[test]
+    fn clearing_the_cache_removes_cached_transcripts() {
+        let (mut app, _dir) = test_app();
+        crate::transcribe::whisper::save_transcription_cache(
+            &app.data_dir,
+            "abc",
+            WhisperModel::Base,
+            None,
+            "Hello",
+        )
+        .expect("save");
+        let _ = app.update(Message::ClearTranscriptionCache);
+        assert!(app.errors.is_empty());
+        assert_eq!(app.storage.cache_bytes, 0);
+    }
+
+    #[test]
+    fn deleting_models_needs_confirmation() {
+        let (mut app, _dir) = test_app();
+        let model =
+            crate::transcribe::whisper::default_model_path(&app.data_dir, WhisperModel::Tiny);
+        std::fs::create_dir_all(crate::transcribe::whisper::models_dir(&app.data_dir))
+            .expect("create dir");
+        std::fs::write(&model, "model").expect("write");
+        let _ = app.update(Message::DeleteModels);
+        assert!(app.storage.confirm_delete_models);
+        let _ = app.update(Message::DeleteModelsCancel);
+        assert!(!app.storage.confirm_delete_models);
+
+        let _ = app.update(Message::DeleteModels);
+        let _ = app.update(Message::DeleteModelsConfirm);
+        assert!(!app.storage.confirm_delete_models);
+        assert!(app.errors.is_empty());
+        assert!(!model.exists());
+    }
+
+    #[test]
+    fn settings_model_download_tracks_progress() {
+        let (mut app, _dir) = test_app();
+        app.model_download = Some((0, 0));
+        app.busy = Some(BusyReason::DownloadingModel);
+        // Ignored while another download runs.
+        let _ = app.update(Message::DownloadWhisperModel);
+
+        let _ = app.update(Message::ModelDownloadProgress(50, 200));
+        assert_eq!(app.model_download, Some((50, 200)));
+
+        let _ = app.update(Message::ModelDownloaded(Ok(std::path::PathBuf::new())));
+        assert_eq!(app.model_download, None);
+        assert_eq!(app.busy, None);
+        // A download from settings doesn't start a transcription.
+        assert!(!app.upload_tab.transcribing);
+    }
+
+    #[test]
+    fn all_paths_includes_config_file() {
+        let (app, _dir) = test_app();
+        let paths = app.all_paths();
+        let (_, config) = paths
+            .iter()
+            .find(|(label, _)| label == "Config file")
+            .expect("config path listed");
+        assert_eq!(config.file_name().and_then(|f| f.to_str()), Some("config.toml"));
+    }
+
+    #[test]
+    fn tab_switch_stops_playback_only_when_enabled() {
+        let (mut app, _dir) = test_app();
+        assert!(!app.tab_switch_stops_playback(TabId::Upload));
+
+        app.app_config.ui.stop_on_tab_switch = true;
+        assert!(app.tab_switch_stops_playback(TabId::Upload));
+        // Re-selecting the current tab is not a switch.
+        assert!(!app.tab_switch_stops_playback(TabId::Clone));
+
+        app.app_config.ui.stop_on_tab_switch = false;
+        app.playing_id = Some("gen-1".to_owned());
+        let _ = app.update(Message::TabSelected(TabId::Upload));
+        assert_eq!(app.active_tab, TabId::Upload);
+        assert_eq!(app.playing_id.as_deref(), Some("gen-1"));
+        let _ = app.update(Message::TabBack);
+        assert_eq!(app.active_tab, TabId::Clone);
+        assert_eq!(app.playing_id.as_deref(), Some("gen-1"));
+
+        app.app_config.ui.stop_on_tab_switch = true;
+        let _ = app.update(Message::TabSelected(TabId::Upload));
+        assert_eq!(app.playing_id, None);
+        app.playing_id = Some("gen-1".to_owned());
+        let _ = app.update(Message::TabBack);
+        assert_eq!(app.active_tab, TabId::Clone);
+        assert_eq!(app.playing_id, None);
+    }
+
+    #[test]
+    fn apply_design_preset_fills_tab() {
+        let (mut app, _dir) = test_app();
+        app.languages = vec!["en".to_owned()];
+        let preset = Preset::new(
+            "Narrator".to_owned(),
+            "en".to_owned(),
+            PresetVoice::VoiceDesign {
+                instruct: "A calm narrator".to_owned(),
+            },
+        );
+        let _ = app.apply_preset(preset);
+        assert_eq!(app.design_tab.instruct, "A calm narrator");
+        assert_eq!(app.design_tab.selected_language, "en");
+        assert_eq!(app.active_tab, TabId::VoiceDesign);
+        assert!(matches!(
+            app.preset_voice(TabId::VoiceDesign),
+            Some(PresetVoice::VoiceDesign { instruct }) if instruct == "A calm narrator"
+        ));
+    }
+
+    #[test]
+    fn preset_with_unknown_language_falls_back_to_auto() {
+        let (mut app, _dir) = test_app();
+        app.languages = vec!["en".to_owned()];
+        let preset = Preset::new(
+            "Narrator".to_owned(),
+            "xx".to_owned(),
+            PresetVoice::VoiceDesign {
+                instruct: "A calm narrator".to_owned(),
+            },
+        );
+        let _ = app.apply_preset(preset);
+        assert_eq!(app.design_tab.selected_language, "auto");
+        assert_eq!(app.errors.len(), 1);
+    }
+
+    #[test]
+    fn clone_preset_carries_reference_transcript() {
+        let (mut app, _dir) = test_app();
+        assert!(app.preset_voice(TabId::Clone).is_none());
+
+        app.references = vec![ReferenceAudio {
+            ref_text: Some("Hello".to_owned()),
+            ..reference("ref-1", "alice.wav")
+        }];
+        app.clone_tab.selected_ref = Some("alice.wav".to_owned());
+        assert!(matches!(
+            app.preset_voice(TabId::Clone),
+            Some(PresetVoice::Clone { ref_text: Some(t), reference_wav: None }) if t == "Hello"
+        ));
+    }
+
+    #[test]
+    fn safe_mode_disables_generation() {
+        let (mut app, _dir) = test_app();
+        assert!(app.action_allowed(UserAction::Generate));
+
+        app.safe_mode = true;
+        assert!(!app.action_allowed(UserAction::Generate));
+        assert!(app.action_allowed(UserAction::Export));
+    }
+
+    #[test]
+    fn tab_back_returns_to_previous_tab() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::TabSelected(TabId::Upload));
+        let _ = app.update(Message::TabSelected(TabId::Settings));
+        assert_eq!(app.previous_tab, Some(TabId::Upload));
+
+        let _ = app.update(Message::TabBack);
+        assert_eq!(app.active_tab, TabId::Upload);
+        assert_eq!(app.previous_tab, Some(TabId::Settings));
+    }
+
+    #[test]
+    fn reselecting_active_tab_keeps_previous() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::TabSelected(TabId::Upload));
+        let _ = app.update(Message::TabSelected(TabId::Upload));
+        assert_eq!(app.active_tab, TabId::Upload);
+        assert_eq!(app.previous_tab, Some(TabId::Clone));
+    }
+
+    #[test]
+    fn preview_offset_applied_and_clamped() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::ReferencePreviewOffset(
+            "ref-1".to_owned(),
+            Duration::from_millis(1500),
+        ));
+
+        // 2 s clip: offset applies as stored.
+        let wav = crate::audio::recorder::samples_to_wav(&[0.1; 32_000], 16_000)
+            .expect("encode");
+        assert_eq!(
+            app.reference_preview_start("ref-1", &wav),
+            Duration::from_millis(1500)
+        );
+
+        // 1 s clip: offset is clamped to the clip length.
+        let short = crate::audio::recorder::samples_to_wav(&[0.1; 16_000], 16_000)
+            .expect("encode");
+        assert_eq!(app.reference_preview_start("ref-1", &short), Duration::from_secs(1));
+
+        // Other references start at zero.
+        assert_eq!(app.reference_preview_start("ref-2", &wav), Duration::ZERO);
+    }
+
+    #[test]
+    fn slider_values_are_saved_on_release() {
+        let (mut app, _dir) = test_app();
+        let offset = Message::ReferencePreviewOffset("ref-1".to_owned(), Duration::from_secs(2));
+        let _ = app.update(offset);
+        assert!(!crate::config::config_path(&app.data_dir).exists());
+
+        let _ = app.update(Message::ConfigSliderReleased);
+        let saved = crate::config::load(&app.data_dir);
+        assert_eq!(saved.ui.preview_offsets.get("ref-1"), Some(&2.0));
+        assert!(app.errors.is_empty());
+    }
+
+    #[test]
+    fn normalize_preview_boosts_quiet_reference() {
+        let quiet = crate::audio::recorder::samples_to_wav(&[0.05, -0.02], 16_000)
+            .expect("encode");
+
+        let (mut app, _dir) = test_app();
+        assert_eq!(app.reference_preview_audio(quiet.clone()), quiet);
+
+        app.app_config.audio.normalize_preview = true;
+        let boosted = app.reference_preview_audio(quiet);
+        let (_, samples) = crate::audio::wav::decode_samples(&boosted).expect("decode");
+        let peak = 10.0_f32.powf(PREVIEW_PEAK_DB / 20.0);
+        assert!((samples[0] - peak).abs() < 1e-3);
+        assert!((samples[1] - peak * -0.4).abs() < 1e-3);
+    }
+
+    #[test]
+    fn late_poll_does_not_resurrect_cancelled_task() {
+        let (mut app, _dir) = test_app();
+        let mut task = ActiveTask::new("task-1".to_owned(), TabId::Clone);
+        task.status = TaskStatus::Cancelled;
+        app.active_tasks.push(task);
+
+        let json = r#"{"status":"processing","progress":40}"#;
+        let resp: TaskStatusResponse = serde_json::from_str(json).expect("deserialize");
+        let _ = app.update(Message::TaskProgress("task-1".to_owned(), Ok(resp)));
+
+        let task = app.task_for(TabId::Clone).expect("task kept");
+        assert_eq!(task.status, TaskStatus::Cancelled);
+        assert_eq!(task.progress, 0);
+    }
+
+    #[test]
+    fn failed_poll_fails_the_task_and_frees_its_slot() {
+        let (mut app, _dir) = test_app();
+        app.active_tasks.push(ActiveTask::new("task-1".to_owned(), TabId::VoiceDesign));
+        app.submitting = vec![TabId::VoiceDesign; MAX_CONCURRENT_TASKS - 1];
+        app.pending_requests.push(design_request("next"));
+
+        let _ = app.update(Message::TaskProgress("task-1".to_owned(), Err("gone".to_owned())));
+        let task = &app.active_tasks[0];
+        assert_eq!(task.status, TaskStatus::Failed);
+        assert_eq!(task.error.as_deref(), Some("gone"));
+        assert!(app.pending_requests.is_empty());
+        assert_eq!(app.submitting.len(), MAX_CONCURRENT_TASKS);
+    }
+
+    #[test]
+    fn save_reference_waits_for_transcription() {
+        let (mut app, _dir) = test_app();
+        app.upload_tab.file_bytes = Some(vec![0; 4]);
+        app.upload_tab.file_name = Some("voice.wav".to_owned());
+        app.upload_tab.transcribing = true;
+
+        let _ = app.update(Message::UploadSaveReference);
+        assert!(!app.upload_tab.saving_reference);
+
+        app.upload_tab.transcribing = false;
+        app.upload_tab.ref_text = Some("hello".to_owned());
+        let _ = app.update(Message::UploadSaveReference);
+        assert!(app.upload_tab.saving_reference);
+    }
+
+    #[test]
+    fn uploaded_reference_is_listed() {
+        let (mut app, _dir) = test_app();
+        app.upload_tab.saving_reference = true;
+        let reference = reference("ref-1", "voice.wav");
+
+        let _ = app.update(Message::ReferenceUploaded(Ok(reference.clone())));
+        assert!(!app.upload_tab.saving_reference);
+        assert_eq!(app.references, vec![reference]);
+    }
+
+    #[test]
+    fn finished_task_is_recorded_and_rerun_refills_tab() {
+        let (mut app, _dir) = test_app();
+        app.references = vec![reference("ref-1", "alice.wav")];
+        let mut task = ActiveTask::new("task-1".to_owned(), TabId::Clone);
+        task.elapsed_secs = 9;
+        task.request = Some(TaskRequest::Clone(CloneRequest {
+            text: "Hello there".to_owned(),
+            ref_audio_id: "ref-1".to_owned(),
+            ref_text: None,
+            language: "English".to_owned(),
+            batch_line: None,
+        }));
+        app.active_tasks.push(task);
+
+        let _ = app.update(Message::TaskAudioLoaded("task-1".to_owned(), Ok(vec![0; 4])));
+        assert_eq!(app.history.len(), 1);
+        assert_eq!(app.history[0].ref_name.as_deref(), Some("alice.wav"));
+        assert_eq!(app.history[0].generation_secs, 9);
+        assert_eq!(crate::history::load_history(&app.data_dir), app.history);
+
+        app.clone_tab.set_text("");
+        app.active_tab = TabId::Settings;
+        let _ = app.update(Message::HistoryRerun(0));
+        assert_eq!(app.active_tab, TabId::Clone);
+        assert_eq!(app.clone_tab.editor.text(), "Hello there");
+        assert_eq!(app.clone_tab.selected_language, "English");
+        assert_eq!(app.clone_tab.selected_ref.as_deref(), Some("alice.wav"));
+    }
+
+    #[test]
+    fn upload_history_keeps_submitted_inputs() {
+        let (mut app, _dir) = test_app();
+        app.submitting.push(TabId::Upload);
+        app.submitting_upload = Some(UploadSnapshot {
+            text: "Submitted text".to_owned(),
+            language: "English".to_owned(),
+            file_name: "voice.wav".to_owned(),
+        });
+        let _ = app.update(Message::TaskCreated(
+            TabId::Upload,
+            None,
+            Ok(("task-1".to_owned(), None, Duration::ZERO)),
+        ));
+
+        app.upload_tab.set_text("Edited afterwards");
+        let _ = app.update(Message::TaskAudioLoaded("task-1".to_owned(), Ok(vec![0; 4])));
+        assert_eq!(app.history.len(), 1);
+        assert_eq!(app.history[0].text, "Submitted text");
+        assert_eq!(app.history[0].language, "English");
+        assert_eq!(app.history[0].ref_name.as_deref(), Some("voice.wav"));
+    }
+
+    #[test]
+    fn failed_audio_is_not_recorded() {
+        let (mut app, _dir) = test_app();
+        app.active_tasks.push(ActiveTask::new("task-1".to_owned(), TabId::Upload));
+        let _ = app.update(Message::TaskAudioLoaded("task-1".to_owned(), Err("gone".to_owned())));
+        assert!(app.history.is_empty());
+        assert!(app.last_results.is_empty());
+    }
+
+    #[test]
+    fn last_result_is_kept_per_tab() {
+        let (mut app, _dir) = test_app();
+        app.active_tasks.push(ActiveTask::new("task-1".to_owned(), TabId::Clone));
+        app.active_tasks.push(ActiveTask::new("task-2".to_owned(), TabId::VoiceDesign));
+        let _ = app.update(Message::TaskAudioLoaded("task-1".to_owned(), Ok(vec![1; 4])));
+        let _ = app.update(Message::TaskAudioLoaded("task-2".to_owned(), Ok(vec![2; 4])));
+        for task in &mut app.active_tasks {
+            task.status = TaskStatus::Completed;
+        }
+
+        // A new Clone generation replaces the finished task but not its audio.
+        let _ = app.update(Message::TaskCreated(
+            TabId::Clone,
+            None,
+            Ok(("task-3".to_owned(), None, Duration::ZERO)),
+        ));
+        assert!(app.task_mut("task-1").is_none());
+        assert_eq!(app.last_results.get(&TabId::Clone), Some(&vec![1; 4]));
+        assert_eq!(app.last_results.get(&TabId::VoiceDesign), Some(&vec![2; 4]));
+    }
+
+    #[test]
+    fn generate_shortcut_follows_tab_preconditions() {
+        let (mut app, _dir) = test_app();
+        app.available_models = vec!["voice_design".to_owned()];
+        app.active_tab = TabId::VoiceDesign;
+        app.design_tab.set_text("Hello");
+        assert!(app.generate_shortcut().is_none());
+
+        app.design_tab.instruct = "A calm voice".to_owned();
+        assert!(matches!(app.generate_shortcut(), Some(Message::DesignGenerate)));
+
+        app.active_tab = TabId::Settings;
+        assert!(app.generate_shortcut().is_none());
+    }
+
+    #[test]
+    fn health_check_keeps_model_load_errors() {
+        let (mut app, _dir) = test_app();
+        let health = crate::api::types::HealthResponse {
+            status: "healthy".to_owned(),
+            voice_cloner_loaded: false,
+            loaded_models: Vec::new(),
+            model_load_errors: Some(vec!["base: CUDA out of memory".to_owned()]),
+        };
+        let _ = app.update(Message::HealthCheck(Some(("127.0.0.1".to_owned(), health))));
+        assert_eq!(app.model_load_errors, vec!["base: CUDA out of memory"]);
+        assert!(matches!(app.screen, Screen::Loading));
+    }
+
+    #[test]
+    fn unloaded_model_disables_tab() {
+        let (mut app, _dir) = test_app();
+        app.available_models = vec!["base".to_owned(), "custom_voice".to_owned()];
+        assert!(app.tab_model_available(TabId::CustomVoice));
+        assert!(!app.tab_model_available(TabId::VoiceDesign));
+
+        let _ = app.update(Message::HealthLoaded(Ok(crate::api::types::HealthResponse {
+            status: "healthy".to_owned(),
+            voice_cloner_loaded: true,
+            loaded_models: vec!["base".to_owned()],
+            model_load_errors: None,
+        })));
+        assert!(app.tab_model_available(TabId::Clone));
+        assert!(!app.tab_model_available(TabId::CustomVoice));
+        assert!(!app.tab_model_available(TabId::Settings));
+    }
+
+    #[test]
+    fn escape_dismisses_error() {
+        let (mut app, _dir) = test_app();
+        app.push_error("boom".to_owned());
+        app.api_error("Failed", &ApiError::Timeout, Message::RefreshGeneratedList);
+        let _ = app.update(Message::EscapePressed);
+        assert!(app.errors.is_empty());
+    }
+
+    #[test]
+    fn generate_shortcut_is_noop_while_generating() {
+        let (mut app, _dir) = test_app();
+        app.available_models = vec!["voice_design".to_owned()];
+        app.active_tab = TabId::VoiceDesign;
+        app.design_tab.set_text("Hello");
+        app.design_tab.instruct = "A calm voice".to_owned();
+        app.active_tasks.push(ActiveTask::new("task-1".to_owned(), TabId::VoiceDesign));
+        assert!(app.generate_shortcut().is_none());
+
+        app.active_tab = TabId::Clone;
+        app.clone_tab.set_text("Hi");
+        app.clone_tab.selected_ref = Some("alice.wav".to_owned());
+        app.available_models.push("base".to_owned());
+        assert!(matches!(app.generate_shortcut(), Some(Message::CloneGenerate)));
+    }
+
+    fn design_request(text: &str) -> TaskRequest {
+        TaskRequest::VoiceDesign(VoiceDesignRequest {
+            text: text.to_owned(),
+            instruct: "calm".to_owned(),
+            language: "auto".to_owned(),
+        })
+    }
+
+    #[test]
+    fn requests_run_concurrently_up_to_limit() {
+        let (mut app, _dir) = test_app();
+        for i in 0..=MAX_CONCURRENT_TASKS {
+            let _ = app.submit_request(design_request(&i.to_string()));
+        }
+        assert_eq!(app.submitting.len(), MAX_CONCURRENT_TASKS);
+        assert_eq!(app.pending_requests.len(), 1);
+
+        for i in 0..MAX_CONCURRENT_TASKS {
+            let _ = app.update(Message::TaskCreated(
+                TabId::VoiceDesign,
+                Some(design_request(&i.to_string())),
+                Ok((format!("task-{i}"), None, Duration::ZERO)),
+            ));
+        }
+        assert!(app.submitting.is_empty());
+        assert_eq!(app.active_tasks.len(), MAX_CONCURRENT_TASKS);
+        assert_eq!(app.task_for(TabId::VoiceDesign).map(|t| t.task_id.as_str()), Some("task-2"));
+
+        let _ = app.update(Message::TaskCancelled(Ok("task-0".to_owned())));
+        assert_eq!(app.submitting.len(), 1);
+        assert!(app.pending_requests.is_empty());
+    }
+
+    #[test]
+    fn edited_transcript_is_cached_on_generate_not_per_keystroke() {
+        let (mut app, _dir) = test_app();
+        app.upload_tab.file_bytes = Some(vec![0; 4]);
+        app.upload_tab.file_name = Some("take.wav".to_owned());
+        app.upload_tab.file_hash = Some("abc".to_owned());
+        let _ = app.update(Message::UploadRefTextChanged("Hello".to_owned()));
+        let _ = app.update(Message::UploadRefTextChanged("Hello there".to_owned()));
+        assert!(app.upload_tab.ref_text_edited);
+        assert!(!crate::transcribe::whisper::cache_dir(&app.data_dir).exists());
+
+        let _ = app.update(Message::UploadGenerate);
+        assert!(!app.upload_tab.ref_text_edited);
+
+        let _ = app.update(Message::TranscriptionCacheSaved(Err("disk full".to_owned())));
+        assert_eq!(app.errors.len(), 1);
+    }
+
+    #[test]
+    fn upload_generation_is_not_submitted_twice() {
+        let (mut app, _dir) = test_app();
+        app.upload_tab.file_bytes = Some(vec![0; 4]);
+        app.upload_tab.file_name = Some("take.wav".to_owned());
+        let _ = app.update(Message::UploadGenerate);
+        let _ = app.update(Message::UploadGenerate);
+        assert_eq!(app.submitting, vec![TabId::Upload]);
+    }
+
+    #[test]
+    fn upload_generation_waits_for_a_free_slot() {
+        let (mut app, _dir) = test_app();
+        app.upload_tab.file_bytes = Some(vec![0; 4]);
+        app.upload_tab.file_name = Some("take.wav".to_owned());
+        app.submitting = vec![TabId::VoiceDesign; MAX_CONCURRENT_TASKS];
+        let _ = app.update(Message::UploadGenerate);
+        assert!(!app.submitting.contains(&TabId::Upload));
+        assert_eq!(app.errors.len(), 1);
+    }
+
+    #[test]
+    fn export_remembers_its_folder() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::ExportDone(Ok(Vec::new())));
+        assert_eq!(app.app_config.ui.export_dir, None);
+
+        let dir = std::path::PathBuf::from("/tmp/takes");
+        let _ = app.update(Message::ExportDone(Ok(vec![dir.join("take.wav")])));
+        assert_eq!(app.app_config.ui.export_dir.as_ref(), Some(&dir));
+        assert_eq!(app.edit_config.ui.export_dir.as_ref(), Some(&dir));
+    }
+
+    #[test]
+    fn export_all_reports_failed_clips() {
+        let (mut app, _dir) = test_app();
+        app.busy = Some(BusyReason::Exporting);
+        let dir = std::path::PathBuf::from("/tmp/takes");
+        let written = vec![dir.join("a.wav")];
+        let failed = vec!["failed to write b.wav".to_owned(), "timed out".to_owned()];
+        let _ = app.update(Message::ExportAllDone(written, failed));
+        assert!(app.busy.is_none());
+        assert_eq!(app.last_export, Some((1, dir.join("a.wav"))));
+        assert_eq!(app.errors.len(), 1);
+        assert!(app.errors[0].text.starts_with("2 clip(s) failed"));
+    }
+
+    #[test]
+    fn clone_batch_submits_each_line_and_stops_the_rest() {
+        let (mut app, _dir) = test_app();
+        app.references = vec![reference("ref-1", "alice.wav")];
+        app.clone_tab.selected_ref = Some("alice.wav".to_owned());
+        app.clone_tab.batch_mode = true;
+        app.clone_tab.set_text("one\ntwo\n\nthree\nfour\nfive");
+
+        let _ = app.update(Message::CloneGenerate);
+        assert_eq!(app.clone_tab.batch.len(), 5);
+        assert_eq!(app.submitting.len(), MAX_CONCURRENT_TASKS);
+        assert_eq!(app.pending_requests.len(), 2);
+
+        let ids: Vec<u64> = app.clone_tab.batch.iter().map(|l| l.id).collect();
+        let clone_request = |text: &str, line: usize| {
+            TaskRequest::Clone(CloneRequest {
+                text: text.to_owned(),
+                ref_audio_id: "ref-1".to_owned(),
+                ref_text: None,
+                language: "auto".to_owned(),
+                batch_line: Some(ids[line]),
+            })
+        };
+        let _ = app.update(Message::TaskCreated(
+            TabId::Clone,
+            Some(clone_request("one", 0)),
+            Ok(("task-1".to_owned(), None, Duration::ZERO)),
+        ));
+        let _ = app.update(Message::TaskCreated(
+            TabId::Clone,
+            Some(clone_request("two", 1)),
+            Err("boom".to_owned()),
+        ));
+        let statuses = |app: &Qvox| -> Vec<BatchLineStatus> {
+            app.clone_tab.batch.iter().map(|l| l.status).collect()
+        };
+        assert_eq!(statuses(&app)[..2], [BatchLineStatus::Running, BatchLineStatus::Failed]);
+
+        // The failure freed a slot for "four"; only "five" is still queued.
+        assert_eq!(app.pending_requests.len(), 1);
+        let _ = app.update(Message::CloneBatchStop);
+        assert!(app.pending_requests.is_empty());
+        assert_eq!(
+            statuses(&app)[2..],
+            [BatchLineStatus::Waiting, BatchLineStatus::Waiting, BatchLineStatus::Stopped]
+        );
+
+        // A new batch waits until the running one has finished.
+        let _ = app.update(Message::CloneGenerate);
+        assert_eq!(app.clone_tab.batch.len(), 5);
+    }
+
+    fn segment_texts(app: &Qvox) -> Vec<&str> {
+        app.multi_tab.segments.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn move_segment_swaps_neighbours() {
+        let (mut app, _dir) = test_app();
+        for (segment, text) in app.multi_tab.segments.iter_mut().zip(["A", "B"]) {
+            segment.text = text.to_owned();
+        }
+        let _ = app.update(Message::MultiAddSegment);
+        app.multi_tab.segments[2].text = "C".to_owned();
+
+        let _ = app.update(Message::MultiMoveSegmentUp(2));
+        assert_eq!(segment_texts(&app), vec!["A", "C", "B"]);
+        let _ = app.update(Message::MultiMoveSegmentDown(0));
+        assert_eq!(segment_texts(&app), vec!["C", "A", "B"]);
+    }
+
+    #[test]
+    fn duplicate_segment_keeps_speaker_and_clears_text() {
+        let (mut app, _dir) = test_app();
+        app.multi_tab.segments[0].selected_ref = Some("alice.wav".to_owned());
+        app.multi_tab.segments[0].selected_language = "English".to_owned();
+        app.multi_tab.segments[0].text = "A".to_owned();
+        app.multi_tab.segments[1].text = "B".to_owned();
+
+        let _ = app.update(Message::MultiDuplicateSegment(0));
+        assert_eq!(segment_texts(&app), vec!["A", "", "B"]);
+        let copy = &app.multi_tab.segments[1];
+        assert_eq!(copy.selected_ref.as_deref(), Some("alice.wav"));
+        assert_eq!(copy.selected_language, "English");
+
+        let _ = app.update(Message::MultiDuplicateSegment(9));
+        assert_eq!(app.multi_tab.segments.len(), 3);
+    }
+
+    #[test]
+    fn move_segment_ignores_ends() {
+        let (mut app, _dir) = test_app();
+        for (segment, text) in app.multi_tab.segments.iter_mut().zip(["A", "B"]) {
+            segment.text = text.to_owned();
+        }
+        let _ = app.update(Message::MultiMoveSegmentUp(0));
+        let _ = app.update(Message::MultiMoveSegmentDown(1));
+        let _ = app.update(Message::MultiMoveSegmentDown(5));
+        assert_eq!(segment_texts(&app), vec!["A", "B"]);
+    }
+
+    #[test]
+    fn clear_resets_tab_but_keeps_language() {
+        let (mut app, _dir) = test_app();
+        app.clone_tab.set_text("Hello");
+        app.clone_tab.selected_ref = Some("alice.wav".to_owned());
+        app.clone_tab.selected_language = "Japanese".to_owned();
+
+        let _ = app.update(Message::CloneClear);
+        assert!(app.clone_tab.editor.is_empty());
+        assert!(app.clone_tab.selected_ref.is_none());
+        assert_eq!(app.clone_tab.selected_language, "Japanese");
+    }
+
+    #[test]
+    fn language_choice_is_saved_and_restored() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::CloneLanguageSelected("Japanese".to_owned()));
+        let _ = app.update(Message::MultiLanguageSelected(0, "English".to_owned()));
+        app.app_config
+            .ui
+            .last_languages
+            .insert(TabId::Upload, "Klingon".to_owned());
+
+        let (fresh, _restarted_dir) = test_app();
+        let mut restarted = Qvox {
+            app_config: app.app_config.clone(),
+            ..fresh
+        };
+        let _ = restarted.update(Message::LanguagesLoaded(Ok(crate::api::types::LanguagesResponse {
+            languages: vec!["auto".to_owned(), "English".to_owned(), "Japanese".to_owned()],
+        })));
+        assert_eq!(restarted.clone_tab.selected_language, "Japanese");
+        assert_eq!(restarted.design_tab.selected_language, "auto");
+        assert_eq!(restarted.upload_tab.selected_language, "auto");
+        assert!(
+            restarted
+                .multi_tab
+                .segments
+                .iter()
+                .all(|s| s.selected_language == "English")
+        );
+    }
+
+    #[test]
+    fn generated_toggle_expand() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::GeneratedToggleExpand("gen-1".to_owned()));
+        assert!(app.expanded_generated.contains("gen-1"));
+        let _ = app.update(Message::GeneratedToggleExpand("gen-1".to_owned()));
+        assert!(app.expanded_generated.is_empty());
+
+        let _ = app.update(Message::GeneratedToggleExpand("gen-2".to_owned()));
+        let _ = app.update(Message::GeneratedDeleted(Ok("gen-2".to_owned())));
+        assert!(app.expanded_generated.is_empty());
+    }
+
+    #[test]
+    fn reference_and_batch_deletes_wait_for_confirmation() {
+        let (mut app, _dir) = test_app();
+        app.references = vec![reference("ref-1", "alice.wav")];
+        app.generated_list = vec![generated("a", "Hello"), generated("b", "Bye")];
+
+        let _ = app.update(Message::ReferenceDelete("ref-1".to_owned()));
+        assert_eq!(app.pending_delete, Some(PendingDelete::Reference("ref-1".to_owned())));
+        let _ = app.update(Message::DeleteCancel);
+        assert!(app.pending_delete.is_none());
+
+        let _ = app.update(Message::GeneratedToggleSelect("a".to_owned()));
+        let _ = app.update(Message::GeneratedDeleteSelected);
+        assert_eq!(app.pending_delete, Some(PendingDelete::SelectedGenerated));
+        let _ = app.update(Message::DeleteConfirm);
+        assert!(app.pending_delete.is_none());
+        assert_eq!(app.generated_list.len(), 2);
+    }
+
+    #[test]
+    fn batch_delete_skips_selected_items_the_filter_hides() {
+        let (mut app, _dir) = test_app();
+        app.generated_list = vec![generated("a", "Hello"), generated("b", "Goodbye")];
+        app.selected_generated = HashSet::from(["a".to_owned(), "b".to_owned()]);
+        app.generated_filter = "hello".to_owned();
+        assert_eq!(app.visible_selection(), vec!["a".to_owned()]);
+
+        app.generated_filter = "nothing".to_owned();
+        let _ = app.update(Message::GeneratedDeleteSelected);
+        assert!(app.pending_delete.is_none());
+    }
+
+    #[test]
+    fn select_all_visible_respects_filter() {
+        let (mut app, _dir) = test_app();
+        app.generated_list = vec![generated("a", "Hello"), generated("b", "Goodbye")];
+        app.generated_filter = "hello".to_owned();
+
+        let _ = app.update(Message::GeneratedSelectAllVisible);
+        assert_eq!(app.selected_generated, HashSet::from(["a".to_owned()]));
+        let _ = app.update(Message::GeneratedSelectAllVisible);
+        assert!(app.selected_generated.is_empty());
+
+        let _ = app.update(Message::GeneratedToggleSelect("b".to_owned()));
+        let _ = app.update(Message::GeneratedDeleted(Ok("b".to_owned())));
+        assert!(app.selected_generated.is_empty());
+        assert_eq!(app.generated_list.len(), 1);
+    }
+
+    #[test]
+    fn generated_delete_waits_for_confirmation() {
+        let (mut app, _dir) = test_app();
+        app.generated_list = vec![generated("a", "Hello")];
+
+        let _ = app.update(Message::GeneratedDelete("a".to_owned()));
+        assert_eq!(app.pending_delete, Some(PendingDelete::Generated("a".to_owned())));
+        let _ = app.update(Message::DeleteCancel);
+        assert!(app.pending_delete.is_none());
+
+        let _ = app.update(Message::GeneratedDelete("a".to_owned()));
+        let _ = app.update(Message::DeleteConfirm);
+        assert!(app.pending_delete.is_none());
+        assert!(app.generated_list.is_empty());
+    }
+
+    #[test]
+    fn deleted_item_can_be_undone_until_committed() {
+        let (mut app, _dir) = test_app();
+        app.generated_list = vec![generated("a", "Hello"), generated("b", "Bye")];
+
+        let _ = app.update(Message::GeneratedDelete("a".to_owned()));
+        let _ = app.update(Message::DeleteConfirm);
+        assert_eq!(app.last_deleted.as_ref().map(|g| g.id.as_str()), Some("a"));
+
+        // A reload during the undo window keeps it hidden.
+        let _ = app.update(Message::GeneratedListLoaded(Ok(vec![
+            generated("a", "Hello"),
+            generated("b", "Bye"),
+        ])));
+        assert_eq!(app.generated_list.len(), 1);
+
+        let _ = app.update(Message::GeneratedUndoDelete);
+        assert!(app.last_deleted.is_none());
+        assert_eq!(app.generated_list.len(), 2);
+
+        // A commit for an undone item does nothing.
+        let _ = app.update(Message::GeneratedDeleteCommit(1));
+        assert_eq!(app.generated_list.len(), 2);
+
+        let _ = app.update(Message::GeneratedDelete("b".to_owned()));
+        let _ = app.update(Message::DeleteConfirm);
+        let _ = app.update(Message::GeneratedDeleteCommit(2));
+        assert!(app.last_deleted.is_none());
+        assert_eq!(app.generated_list.len(), 1);
+    }
+
+    #[test]
+    fn stale_undo_timer_does_not_cut_a_later_deletion_short() {
+        let (mut app, _dir) = test_app();
+        app.generated_list = vec![generated("a", "Hello")];
+
+        let _ = app.update(Message::GeneratedDelete("a".to_owned()));
+        let _ = app.update(Message::DeleteConfirm);
+        let _ = app.update(Message::GeneratedUndoDelete);
+        let _ = app.update(Message::GeneratedDelete("a".to_owned()));
+        let _ = app.update(Message::DeleteConfirm);
+
+        // The first deletion's timer fires during the second's undo window.
+        let _ = app.update(Message::GeneratedDeleteCommit(1));
+        assert_eq!(app.last_deleted.as_ref().map(|g| g.id.as_str()), Some("a"));
+    }
+
+    #[test]
+    fn closing_the_window_commits_a_pending_deletion() {
+        let (mut app, _dir) = test_app();
+        app.generated_list = vec![generated("a", "Hello")];
+        let _ = app.update(Message::GeneratedDelete("a".to_owned()));
+        let _ = app.update(Message::DeleteConfirm);
+
+        let _ = app.update(Message::WindowCloseRequested(iced::window::Id::unique()));
+        assert!(app.last_deleted.is_none());
+        assert!(app.generated_list.is_empty());
+    }
+
+    #[test]
+    fn transcribing_a_reference_opens_it_in_the_upload_tab() {
+        let (mut app, _dir) = test_app();
+        let wav = crate::audio::recorder::samples_to_wav(&[0.1; 1_600], 16_000).expect("encode");
+        let loaded = Ok(("alice.wav".to_owned(), wav.clone()));
+        let _ = app.update(Message::ReferenceTranscribeLoaded(loaded));
+        assert_eq!(app.active_tab, TabId::Upload);
+        assert_eq!(app.upload_tab.file_name.as_deref(), Some("alice.wav"));
+        assert_eq!(app.upload_tab.file_bytes, Some(wav));
+    }
+
+    #[test]
+    fn regenerate_needs_existing_reference() {
+        let (mut app, _dir) = test_app();
+        app.generated_list = vec![GeneratedAudio {
+            ref_audio_id: Some("ref-1".to_owned()),
+            ..generated("a", "Hello")
+        }];
+
+        let _ = app.update(Message::GeneratedRegenerate("a".to_owned()));
+        assert!(!app.errors.is_empty());
+        assert!(app.submitting.is_empty());
+
+        app.errors.clear();
+        app.references = vec![reference("ref-1", "alice.wav")];
+        let _ = app.update(Message::GeneratedRegenerate("a".to_owned()));
+        assert!(app.errors.is_empty());
+        assert_eq!(app.submitting, vec![TabId::Clone]);
+    }
+
+    #[test]
+    fn regenerate_reuses_the_history_language() {
+        let (mut app, _dir) = test_app();
+        app.references = vec![reference("ref-1", "alice.wav")];
+        app.generated_list = vec![GeneratedAudio {
+            ref_audio_id: Some("ref-1".to_owned()),
+            ..generated("a", "Hello")
+        }];
+        app.history = vec![HistoryEntry {
+            timestamp: 0,
+            tab: TabId::Clone,
+            text: "Hello".to_owned(),
+            language: "English".to_owned(),
+            ref_name: Some("alice.wav".to_owned()),
+            generation_secs: 1,
+            request: Some(TaskRequest::Clone(CloneRequest {
+                text: "Hello".to_owned(),
+                ref_audio_id: "ref-1".to_owned(),
+                ref_text: None,
+                language: "English".to_owned(),
+                batch_line: None,
+            })),
+        }];
+        // With every slot taken the request waits in the queue, where it can be inspected.
+        app.submitting = vec![TabId::VoiceDesign; MAX_CONCURRENT_TASKS];
+
+        let _ = app.update(Message::GeneratedRegenerate("a".to_owned()));
+        assert!(matches!(
+            app.pending_requests.first(),
+            Some(TaskRequest::Clone(r)) if r.language == "English"
+        ));
+    }
+
+    #[test]
+    fn retry_transcription_needs_failed_file() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::RetryTranscription);
+        assert!(!app.upload_tab.transcribing);
+
+        app.upload_tab.file_bytes = Some(vec![0; 4]);
+        app.upload_tab.file_hash = Some("abc".to_owned());
+        app.upload_tab.ref_text = Some("hello".to_owned());
+        let _ = app.update(Message::RetryTranscription);
+        assert!(!app.upload_tab.transcribing);
+
+        app.upload_tab.ref_text = None;
+        let _ = app.update(Message::RetryTranscription);
+        assert!(app.upload_tab.transcribing);
+    }
+
+    #[test]
+    fn cancel_transcription_stops_and_leaves_text_empty() {
+        let (mut app, _dir) = test_app();
+        let cancel = Arc::new(AtomicBool::new(false));
+        app.transcription_cancel = Some(Arc::clone(&cancel));
+        app.upload_tab.transcribing = true;
+        app.upload_tab.transcription_progress = 40;
+        app.busy = Some(BusyReason::Transcribing);
+
+        app.upload_tab.file_hash = Some("abc".to_owned());
+
+        let _ = app.update(Message::CancelTranscription);
+        assert!(cancel.load(Ordering::Relaxed));
+        assert!(!app.upload_tab.transcribing);
+        assert!(app.upload_tab.ref_text.is_none());
+        assert_eq!(app.busy, Some(BusyReason::Transcribing));
+
+        let _ = app.update(Message::TranscriptionDone("abc".to_owned(), None));
+        assert!(app.transcription_cancel.is_none());
+        assert!(app.busy.is_none());
+        assert!(app.upload_tab.ref_text.is_none());
+        assert!(app.errors.is_empty());
+    }
+
+    #[test]
+    fn transcription_for_replaced_audio_is_ignored() {
+        let (mut app, _dir) = test_app();
+        app.upload_tab.file_hash = Some("new".to_owned());
+        app.busy = Some(BusyReason::Transcribing);
+
+        let result = Some(Ok("old words".to_owned()));
+        let _ = app.update(Message::TranscriptionDone("old".to_owned(), result));
+        assert!(app.busy.is_none());
+        assert!(app.upload_tab.ref_text.is_none());
+
+        let result = Some(Ok("new words".to_owned()));
+        let _ = app.update(Message::TranscriptionDone("new".to_owned(), result));
+        assert_eq!(app.upload_tab.ref_text.as_deref(), Some("new words"));
+    }
+
+    #[test]
+    fn ref_text_edits_replace_transcription() {
+        let (mut app, _dir) = test_app();
+        app.upload_tab.ref_text = Some("helo world".to_owned());
+        let _ = app.update(Message::UploadRefTextChanged("hello world".to_owned()));
+        assert_eq!(app.upload_tab.ref_text.as_deref(), Some("hello world"));
+
+        app.upload_tab.transcribing = true;
+        let _ = app.update(Message::UploadRefTextChanged("ignored".to_owned()));
+        assert_eq!(app.upload_tab.ref_text.as_deref(), Some("hello world"));
+    }
+
+    #[test]
+    fn upload_clear_drops_audio() {
+        let (mut app, _dir) = test_app();
+        app.upload_tab.file_bytes = Some(vec![0; 4]);
+        app.upload_tab.file_name = Some("voice.wav".to_owned());
+        app.upload_tab.file_hash = Some("abc".to_owned());
+        app.upload_tab.ref_text = Some("hello".to_owned());
+        app.upload_tab.selected_language = "English".to_owned();
+
+        app.upload_tab.transcribing = true;
+        let _ = app.update(Message::UploadClear);
+        assert!(app.upload_tab.file_bytes.is_some());
+
+        app.upload_tab.transcribing = false;
+        let _ = app.update(Message::UploadClear);
+        assert!(app.upload_tab.file_bytes.is_none());
+        assert!(app.upload_tab.file_name.is_none());
+        assert!(app.upload_tab.file_hash.is_none());
+        assert!(app.upload_tab.ref_text.is_none());
+        assert_eq!(app.upload_tab.selected_language, "English");
+    }
+
+    #[test]
+    fn rename_rejects_empty_name() {
+        let (mut app, _dir) = test_app();
+        app.references = vec![reference("ref-1", "voice.wav")];
+
+        let _ = app.update(Message::ReferenceRename("ref-1".to_owned(), "   ".to_owned()));
+        assert!(!app.errors.is_empty());
+        assert_eq!(app.references[0].name, None);
+    }
+
+    #[test]
+    fn rename_updates_reference_and_selections() {
+        let (mut app, _dir) = test_app();
+        app.references = vec![reference("ref-1", "voice.wav"), reference("ref-2", "other.wav")];
+        app.clone_tab.selected_ref = Some("voice.wav".to_owned());
+        app.multi_tab.segments[0].selected_ref = Some("voice.wav".to_owned());
+        app.multi_tab.segments[1].selected_ref = Some("other.wav".to_owned());
+        app.reference_name_drafts.insert("ref-1".to_owned(), "Narrator".to_owned());
+
+        let _ = app.update(Message::ReferenceRenamed(Ok((
+            "ref-1".to_owned(),
+            "Narrator".to_owned(),
+        ))));
+
+        assert_eq!(app.references[0].name.as_deref(), Some("Narrator"));
+        assert!(app.reference_name_drafts.is_empty());
+        assert_eq!(app.clone_tab.selected_ref.as_deref(), Some("Narrator"));
+        assert_eq!(app.multi_tab.segments[0].selected_ref.as_deref(), Some("Narrator"));
+        assert_eq!(app.multi_tab.segments[1].selected_ref.as_deref(), Some("other.wav"));
+    }
+
+    #[test]
+    fn delete_reference_clears_its_selections() {
+        let (mut app, _dir) = test_app();
+        app.references = vec![reference("ref-1", "voice.wav"), reference("ref-2", "other.wav")];
+        app.clone_tab.selected_ref = Some("voice.wav".to_owned());
+        app.multi_tab.segments[0].selected_ref = Some("voice.wav".to_owned());
+        app.multi_tab.segments[1].selected_ref = Some("other.wav".to_owned());
+
+        let _ = app.update(Message::ReferenceDeleted(Ok("ref-1".to_owned())));
+
+        assert_eq!(app.references, vec![reference("ref-2", "other.wav")]);
+        assert_eq!(app.clone_tab.selected_ref, None);
+        assert_eq!(app.multi_tab.segments[0].selected_ref, None);
+        assert_eq!(app.multi_tab.segments[1].selected_ref.as_deref(), Some("other.wav"));
+    }
+
+    #[test]
+    fn retry_offered_only_for_transient_errors() {
+        let (mut app, _dir) = test_app();
+        let _ = app.update(Message::GeneratedListLoaded(Err(ApiError::Status(404))));
+        assert_eq!(app.errors.len(), 1);
+        assert!(app.errors[0].retry.is_none());
+
+        let _ = app.update(Message::GeneratedListLoaded(Err(ApiError::Timeout)));
+        assert_eq!(app.errors.len(), 2);
+        let id = app.errors[1].id;
+        assert!(app.errors[1].retry.is_some());
+        let _ = app.update(Message::ErrorRetry(id));
+        assert_eq!(app.errors.len(), 1);
+    }
+
+    #[test]
+    fn errors_are_kept_until_dismissed_or_expired() {
+        let (mut app, _dir) = test_app();
+        app.push_error("Failed to load capabilities".to_owned());
+        app.push_error("Failed to load references".to_owned());
+        app.push_sticky_error("Server crashed".to_owned());
+        // The same error again refreshes the existing row.
+        app.push_error("Failed to load references".to_owned());
+        assert_eq!(app.errors.len(), 3);
+
+        let first = app.errors[0].id;
+        let _ = app.update(Message::ErrorDismiss(first));
+        assert_eq!(app.errors.len(), 2);
+        // A dismissed id is ignored.
+        let _ = app.update(Message::ErrorRetry(first));
+        assert_eq!(app.errors.len(), 2);
+
+        app.expire_errors(Instant::now() + Duration::from_secs(ERROR_EXPIRE_SECS + 1));
+        assert_eq!(app.errors.len(), 1);
+        assert_eq!(app.errors[0].text, "Server crashed");
+    }
+
+    #[test]
+    fn transcription_progress_updates_upload_tab() {
+        let (mut app, _dir) = test_app();
+        app.upload_tab.transcribing = true;
+        let _ = app.update(Message::TranscriptionProgress(35));
+        assert_eq!(app.upload_tab.transcription_progress, 35);
+    }
+
+    #[test]
+    fn loading_pulse_cycles() {
+        assert!((loading_pulse(0) - 20.0).abs() < f32::EPSILON);
+        assert!((loading_pulse(4) - 100.0).abs() < f32::EPSILON);
+        assert!((loading_pulse(5) - 20.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn slow_start_hint_after_threshold() {
+        let (mut app, _dir) = test_app();
+        app.app_config.server.slow_start_secs = 2;
+        let _ = app.update(Message::Tick);
+        assert!(!app.slow_start);
+        let _ = app.update(Message::Tick);
+        assert!(app.slow_start);
+        assert!(app.show_server_logs);
+
+        let _ = app.update(Message::ServerRestart);
+        assert!(!app.slow_start);
+    }
+
+    #[test]
+    fn clamp_preview_offset_without_duration() {
+        assert_eq!(clamp_preview_offset(2.0, None), Duration::from_secs(2));
+        assert_eq!(clamp_preview_offset(-1.0, Some(3.0)), Duration::ZERO);
+    }
+}
@@ -4,7 +4,6 @@ use anyhow::{Context, Result};
 use sha2::{Digest, Sha256};
 
 /// Compute the SHA-256 hash of a file and return it as a lowercase hex string.
-#[allow(dead_code)]
 pub fn file_sha256(path: &Path) -> Result<String> {
     let data = std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
     Ok(bytes_sha256(&data))
@@ -5,3 +5,4 @@ pub mod processing;
 #[allow(dead_code)]
 pub mod concat;
 pub mod hash;
+pub mod wav;
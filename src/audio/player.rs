@@ -1,7 +1,9 @@
 use std::io::Cursor;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
-use rodio::{Decoder, MixerDeviceSink, Player};
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::{Decoder, MixerDeviceSink, Player, Source};
 
 /// Playback state exposed to the UI.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +13,51 @@ pub enum PlaybackState {
     Paused,
 }
 
+/// Playback state plus position, as shown by the playback controls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaybackStatus {
+    pub state: PlaybackState,
+    /// Position within the current clip.
+    pub position: Duration,
+    /// Length of the current clip, when the decoder knows it.
+    pub total: Option<Duration>,
+    /// Clips repeat until stopped.
+    pub looping: bool,
+}
+
+impl PlaybackStatus {
+    /// Nothing loaded.
+    pub const STOPPED: Self = Self {
+        state: PlaybackState::Stopped,
+        position: Duration::ZERO,
+        total: None,
+        looping: false,
+    };
+}
+
+/// Position `fraction` (clamped to `0.0..=1.0`) of the way through `total`.
+pub fn seek_position(fraction: f32, total: Duration) -> Duration {
+    total.mul_f32(fraction.clamp(0.0, 1.0))
+}
+
+/// Position within a clip repeating from `start` to `total`, given the
+/// time `elapsed` since playback began at `start`.
+fn looped_position(start: Duration, elapsed: Duration, total: Option<Duration>) -> Duration {
+    match total.map(|t| t.saturating_sub(start)) {
+        Some(span) if !span.is_zero() => {
+            let nanos = elapsed.as_nanos() % span.as_nanos();
+            start + Duration::from_nanos(u64::try_from(nanos).unwrap_or(0))
+        }
+        _ => start + elapsed,
+    }
+}
+
+/// Display name of an output device.
+#[allow(deprecated)] // `name` is what the OS shows users, which is what we persist.
+fn device_name(device: &rodio::cpal::Device) -> Option<String> {
+    device.name().ok()
+}
+
 /// Wraps rodio's `Player` + `MixerDeviceSink` for controlled audio playback.
 ///
 /// The `MixerDeviceSink` (stream handle) must stay alive for the duration of
@@ -19,6 +66,14 @@ pub struct AudioPlayer {
     _stream: MixerDeviceSink,
     player: Player,
     state: PlaybackState,
+    /// Offset the current clip started at (see `play_bytes_from`).
+    start: Duration,
+    total: Option<Duration>,
+    /// Current clip, kept so looping can be switched mid-play.
+    clip: Option<Vec<u8>>,
+    loop_enabled: bool,
+    /// The queued source repeats on its own.
+    repeating: bool,
 }
 
 impl std::fmt::Debug for AudioPlayer {
@@ -32,15 +87,45 @@ impl std::fmt::Debug for AudioPlayer {
 impl AudioPlayer {
     /// Create a new player using the default audio output device.
     pub fn new() -> Result<Self> {
-        let mut stream =
+        let stream =
             rodio::DeviceSinkBuilder::open_default_sink().context("failed to open audio device")?;
+        Ok(Self::from_stream(stream))
+    }
+
+    /// Create a new player on the output device called `name`.
+    pub fn with_device(name: &str) -> Result<Self> {
+        let device = rodio::cpal::default_host()
+            .output_devices()
+            .context("failed to list output devices")?
+            .find(|d| device_name(d).as_deref() == Some(name))
+            .with_context(|| format!("output device \"{name}\" not found"))?;
+        let stream = rodio::DeviceSinkBuilder::from_device(device)
+            .and_then(|builder| builder.open_sink())
+            .context("failed to open audio device")?;
+        Ok(Self::from_stream(stream))
+    }
+
+    /// Names of the output devices currently available, in host order.
+    pub fn list_output_devices() -> Vec<String> {
+        rodio::cpal::default_host()
+            .output_devices()
+            .map(|devices| devices.filter_map(|d| device_name(&d)).collect())
+            .unwrap_or_default()
+    }
+
+    fn from_stream(mut stream: MixerDeviceSink) -> Self {
         stream.log_on_drop(false);
         let player = Player::connect_new(stream.mixer());
-        Ok(Self {
+        Self {
             _stream: stream,
             player,
             state: PlaybackState::Stopped,
-        })
+            start: Duration::ZERO,
+            total: None,
+            clip: None,
+            loop_enabled: false,
+            repeating: false,
+        }
     }
 
     /// Current playback state.
@@ -54,15 +139,64 @@ impl AudioPlayer {
 
     /// Play WAV audio from raw bytes.  Stops any current playback first.
     pub fn play_bytes(&mut self, wav_data: Vec<u8>) -> Result<()> {
+        self.play_bytes_from(wav_data, Duration::ZERO)
+    }
+
+    /// Play WAV audio from raw bytes, skipping the first `start` of the clip.
+    /// With looping on, the clip repeats from `start`.
+    pub fn play_bytes_from(&mut self, wav_data: Vec<u8>, start: Duration) -> Result<()> {
         self.player.stop();
-        let cursor = Cursor::new(wav_data);
+        let cursor = Cursor::new(wav_data.clone());
         let source =
             Decoder::try_from(cursor).context("failed to decode audio data")?;
-        self.player.append(source);
+        self.total = source.total_duration();
+        self.start = self.total.map_or(start, |total| start.min(total));
+        if self.loop_enabled {
+            self.player.append(source.skip_duration(start).repeat_infinite());
+        } else {
+            self.player.append(source.skip_duration(start));
+        }
+        self.repeating = self.loop_enabled;
+        self.clip = Some(wav_data);
         self.state = PlaybackState::Playing;
         Ok(())
     }
 
+    /// Turn looping on or off. Switching it off mid-play replays the rest of
+    /// the clip once, since a repeating source can't be told to stop.
+    pub fn set_loop(&mut self, enabled: bool) -> Result<()> {
+        self.loop_enabled = enabled;
+        if enabled || !self.repeating || self.state == PlaybackState::Stopped {
+            return Ok(());
+        }
+        let Some(clip) = self.clip.clone() else {
+            return Ok(());
+        };
+        let paused = self.state == PlaybackState::Paused;
+        let position = self.position();
+        self.play_bytes_from(clip, position)?;
+        if paused {
+            self.pause();
+        }
+        Ok(())
+    }
+
+    /// A clip finished after looping was switched on mid-play and is
+    /// waiting for `restart_if_looping`.
+    pub fn loop_pending(&self) -> bool {
+        self.loop_enabled && self.state == PlaybackState::Playing && self.player.empty()
+    }
+
+    /// Replay the clip if `loop_pending`. Call periodically while playing.
+    pub fn restart_if_looping(&mut self) -> Result<()> {
+        if self.loop_pending()
+            && let Some(clip) = self.clip.clone()
+        {
+            self.play_bytes_from(clip, self.start)?;
+        }
+        Ok(())
+    }
+
     /// Pause the current playback.
     pub fn pause(&mut self) {
         if self.state == PlaybackState::Playing {
@@ -85,4 +219,75 @@ impl AudioPlayer {
         self.state = PlaybackState::Stopped;
     }
 
+    /// Jump to `pos` within the current clip. Fails, leaving playback
+    /// untouched, when the source does not support seeking.
+    pub fn seek(&mut self, pos: Duration) -> Result<()> {
+        self.player
+            .try_seek(pos)
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        // The decoder seeks in absolute clip time, so the preview offset
+        // no longer applies to the reported position.
+        self.start = Duration::ZERO;
+        Ok(())
+    }
+
+    /// Position within the current clip, counting any skipped start.
+    pub fn position(&self) -> Duration {
+        if self.state() == PlaybackState::Stopped {
+            return Duration::ZERO;
+        }
+        if self.repeating {
+            return looped_position(self.start, self.player.get_pos(), self.total);
+        }
+        self.start + self.player.get_pos()
+    }
+
+    /// Length of the current clip, if the decoder reported one.
+    pub fn total_duration(&self) -> Option<Duration> {
+        self.total
+    }
+
+    /// State, position and length in one snapshot for the UI.
+    pub fn status(&self) -> PlaybackStatus {
+        PlaybackStatus {
+            state: self.state(),
+            position: self.position(),
+            total: self.total,
+            looping: self.loop_enabled,
+        }
+    }
+
+    /// Set the playback gain (1.0 = unchanged).
+    pub fn set_volume(&mut self, volume: f32) {
+        self.player.set_volume(volume);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seek_position_scales_total() {
+        let total = Duration::from_secs(10);
+        assert_eq!(seek_position(0.25, total), Duration::from_millis(2500));
+        assert_eq!(seek_position(1.0, total), total);
+    }
+
+    #[test]
+    fn looped_position_wraps_after_start() {
+        let start = Duration::from_secs(2);
+        let total = Some(Duration::from_secs(10));
+        assert_eq!(looped_position(start, Duration::from_secs(3), total), Duration::from_secs(5));
+        assert_eq!(looped_position(start, Duration::from_secs(9), total), Duration::from_secs(3));
+        // Unknown length: no wrapping.
+        assert_eq!(looped_position(start, Duration::from_secs(9), None), Duration::from_secs(11));
+    }
+
+    #[test]
+    fn seek_position_clamps_fraction() {
+        let total = Duration::from_secs(10);
+        assert_eq!(seek_position(-0.5, total), Duration::ZERO);
+        assert_eq!(seek_position(1.5, total), total);
+    }
 }
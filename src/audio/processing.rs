@@ -73,8 +73,44 @@ pub fn remove_trailing_silence(
     samples.truncate(final_len);
 }
 
+/// Default RMS level below which a recording's tail counts as silence.
+pub const RECORDING_SILENCE_DB: f32 = -40.0;
+
+/// Peak level recordings are normalized to.
+pub const RECORDING_PEAK_DB: f32 = -3.0;
+
+/// Clean up a microphone take: trim trailing silence below `trim_db` (when
+/// given) and/or peak-normalize.
+pub fn clean_recording(
+    samples: &mut Vec<f32>,
+    sample_rate: u32,
+    trim_db: Option<f32>,
+    normalize: bool,
+) {
+    if let Some(threshold_db) = trim_db {
+        remove_trailing_silence(samples, sample_rate, threshold_db);
+    }
+    if normalize {
+        normalize_audio(samples, RECORDING_PEAK_DB);
+    }
+}
+
+/// Playback gain that brings a clip with the given RMS to `target_rms`.
+///
+/// Silent clips get unity gain, and the result is clamped to ±12 dB so a
+/// near-silent clip is not blown up into noise.
+pub fn loudness_gain(rms: f32, target_rms: f32) -> f32 {
+    const MIN_GAIN: f32 = 0.25;
+    const MAX_GAIN: f32 = 4.0;
+
+    if rms <= f32::EPSILON {
+        return 1.0;
+    }
+    (target_rms / rms).clamp(MIN_GAIN, MAX_GAIN)
+}
+
 /// Compute the RMS level of a slice of f32 samples.
-fn rms_level(samples: &[f32]) -> f32 {
+pub fn rms_level(samples: &[f32]) -> f32 {
     if samples.is_empty() {
         return 0.0;
     }
@@ -83,10 +119,38 @@ fn rms_level(samples: &[f32]) -> f32 {
     mean_sq.sqrt()
 }
 
+/// Min/max envelope of interleaved `samples` for drawing a waveform: one
+/// `(min, max)` pair per column, across all channels. Clips shorter than
+/// `columns` frames get one column per frame.
+pub fn envelope(samples: &[f32], channels: u16, columns: usize) -> Vec<(f32, f32)> {
+    let channels = usize::from(channels.max(1));
+    let frames = samples.len() / channels;
+    let columns = columns.min(frames);
+    (0..columns)
+        .map(|col| {
+            let start = col * frames / columns * channels;
+            let end = (col + 1) * frames / columns * channels;
+            samples[start..end]
+                .iter()
+                .fold((0.0_f32, 0.0_f32), |(lo, hi), &s| (lo.min(s), hi.max(s)))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn envelope_groups_frames_into_columns() {
+        let samples = [0.5, -0.25, 1.0, -1.0];
+        assert_eq!(envelope(&samples, 1, 2), vec![(-0.25, 0.5), (-1.0, 1.0)]);
+        // Two stereo frames can't fill more than two columns.
+        assert_eq!(envelope(&samples, 2, 8), vec![(-0.25, 0.5), (-1.0, 1.0)]);
+        assert!(envelope(&[], 1, 8).is_empty());
+        assert!(envelope(&samples, 1, 0).is_empty());
+    }
+
     #[test]
     fn normalize_silent_signal() {
         let mut samples = vec![0.0; 100];
@@ -169,6 +233,69 @@ mod tests {
         assert_eq!(samples.len(), original_len);
     }
 
+    #[test]
+    fn clean_recording_trims_and_normalizes() {
+        let sample_rate = 16_000_u32;
+        let mut samples = vec![0.25; (sample_rate as usize) * 6];
+        samples.extend(vec![0.0; (sample_rate as usize) * 2]);
+
+        clean_recording(&mut samples, sample_rate, Some(RECORDING_SILENCE_DB), true);
+
+        assert!(samples.len() < (sample_rate as usize) * 7);
+        let peak = 10.0_f32.powf(RECORDING_PEAK_DB / 20.0);
+        assert!((samples[0] - peak).abs() < 1e-4);
+    }
+
+    #[test]
+    fn clean_recording_uses_threshold() {
+        let sample_rate = 16_000_u32;
+        // A quiet tail at about -32 dB: silence at -20 dB, speech at -40 dB.
+        let mut samples = vec![0.25; (sample_rate as usize) * 6];
+        samples.extend(vec![0.025; (sample_rate as usize) * 2]);
+        let mut noisy = samples.clone();
+
+        clean_recording(&mut samples, sample_rate, Some(-40.0), false);
+        assert_eq!(samples.len(), (sample_rate as usize) * 8);
+
+        clean_recording(&mut noisy, sample_rate, Some(-20.0), false);
+        assert!(noisy.len() < (sample_rate as usize) * 7);
+    }
+
+    #[test]
+    fn clean_recording_disabled_leaves_samples() {
+        let sample_rate = 16_000_u32;
+        let mut samples = vec![0.25; (sample_rate as usize) * 6];
+        samples.extend(vec![0.0; (sample_rate as usize) * 2]);
+        let original = samples.clone();
+
+        clean_recording(&mut samples, sample_rate, None, false);
+
+        assert_eq!(samples, original);
+    }
+
+    #[test]
+    fn loudness_gain_boosts_quiet_clip() {
+        let gain = loudness_gain(0.05, 0.1);
+        assert!((gain - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn loudness_gain_attenuates_loud_clip() {
+        let gain = loudness_gain(0.4, 0.1);
+        assert!((gain - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn loudness_gain_clamps_extremes() {
+        assert!((loudness_gain(0.001, 0.1) - 4.0).abs() < 1e-5);
+        assert!((loudness_gain(1.0, 0.01) - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn loudness_gain_silence_is_unity() {
+        assert!((loudness_gain(0.0, 0.1) - 1.0).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn rms_of_empty() {
         assert!(rms_level(&[]).abs() < f32::EPSILON);
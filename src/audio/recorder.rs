@@ -7,6 +7,9 @@ use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 /// Maximum recording duration in seconds.
 const MAX_RECORDING_SECS: u32 = 60;
 
+/// How much of the most recent audio the level meter looks at, in ms.
+const LEVEL_WINDOW_MS: u32 = 100;
+
 /// Recording state exposed to the UI.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RecordingState {
@@ -19,6 +22,8 @@ pub enum RecordingState {
 /// `cpal::Stream` is `!Send`, so `Recorder` must live on the thread where
 /// it was created (typically the main/UI thread).
 pub struct Recorder {
+    /// Input device to record from; `None` uses the host default.
+    device_name: Option<String>,
     stream: Option<cpal::Stream>,
     buffer: Arc<Mutex<Vec<f32>>>,
     sample_rate: u32,
@@ -30,6 +35,7 @@ impl std::fmt::Debug for Recorder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Recorder")
             .field("state", &self.state)
+            .field("device_name", &self.device_name)
             .field("sample_rate", &self.sample_rate)
             .field("channels", &self.channels)
             .finish_non_exhaustive()
@@ -39,16 +45,31 @@ impl std::fmt::Debug for Recorder {
 impl Recorder {
     /// Create a new recorder using the default input device.
     pub fn new() -> Result<Self> {
-        let host = cpal::default_host();
-        let device = host
-            .default_input_device()
-            .context("no input device available")?;
+        Self::open(None)
+    }
+
+    /// Create a new recorder using the input device called `name`.
+    pub fn with_device(name: &str) -> Result<Self> {
+        Self::open(Some(name.to_owned()))
+    }
+
+    /// Names of the input devices currently available, in host order.
+    pub fn list_input_devices() -> Vec<String> {
+        cpal::default_host()
+            .input_devices()
+            .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+            .unwrap_or_default()
+    }
+
+    fn open(device_name: Option<String>) -> Result<Self> {
+        let device = find_input_device(device_name.as_deref())?;
 
         let supported = device
             .default_input_config()
             .context("no default input config")?;
 
         Ok(Self {
+            device_name,
             stream: None,
             buffer: Arc::new(Mutex::new(Vec::new())),
             sample_rate: supported.sample_rate().0,
@@ -73,10 +94,7 @@ impl Recorder {
             bail!("already recording");
         }
 
-        let host = cpal::default_host();
-        let device = host
-            .default_input_device()
-            .context("no input device available")?;
+        let device = find_input_device(self.device_name.as_deref())?;
 
         let supported = device
             .default_input_config()
@@ -117,7 +135,7 @@ impl Recorder {
                     }
                 },
                 |err| {
-                    eprintln!("recording stream error: {err}");
+                    tracing::warn!("recording stream error: {err}");
                 },
                 None,
             )
@@ -151,6 +169,17 @@ impl Recorder {
             .unwrap_or(0)
     }
 
+    /// Peak amplitude (`0.0..=1.0`) of the most recent audio, for a level
+    /// meter. Uses `try_lock` so the audio callback is never blocked; returns
+    /// `0.0` when the buffer is busy.
+    pub fn current_level(&self) -> f32 {
+        let window = (self.sample_rate * LEVEL_WINDOW_MS / 1000) as usize;
+        self.buffer
+            .try_lock()
+            .map(|buf| peak_level(&buf[buf.len().saturating_sub(window)..]))
+            .unwrap_or(0.0)
+    }
+
     /// Get recorded duration in seconds.
     #[allow(clippy::cast_precision_loss)]
     pub fn elapsed_secs(&self) -> f32 {
@@ -161,6 +190,28 @@ impl Recorder {
     }
 }
 
+/// Look up an input device by name, or the host default when `name` is `None`.
+fn find_input_device(name: Option<&str>) -> Result<cpal::Device> {
+    let host = cpal::default_host();
+    let Some(name) = name else {
+        return host
+            .default_input_device()
+            .context("no input device available");
+    };
+    host.input_devices()
+        .context("failed to list input devices")?
+        .find(|d| d.name().is_ok_and(|n| n == name))
+        .with_context(|| format!("input device \"{name}\" not found"))
+}
+
+/// Peak absolute amplitude of `samples`, clamped to `0.0..=1.0`.
+fn peak_level(samples: &[f32]) -> f32 {
+    samples
+        .iter()
+        .fold(0.0_f32, |peak, s| peak.max(s.abs()))
+        .min(1.0)
+}
+
 /// Encode mono f32 samples as WAV bytes (16-bit PCM).
 #[allow(clippy::cast_possible_truncation)]
 pub fn samples_to_wav(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>> {
@@ -235,6 +286,22 @@ mod tests {
         assert_eq!(decoded[1], -32767); // clamped to -1.0
     }
 
+    #[test]
+    fn peak_level_of_silence_is_zero() {
+        assert!(peak_level(&[]).abs() < f32::EPSILON);
+        assert!(peak_level(&[0.0; 16]).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn peak_level_uses_absolute_amplitude() {
+        assert!((peak_level(&[0.1, -0.6, 0.3]) - 0.6).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn peak_level_clamps_clipped_input() {
+        assert!((peak_level(&[1.5, -2.0]) - 1.0).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn recording_state_default() {
         // Just test the enum
@@ -0,0 +1,271 @@
+use std::io::Cursor;
+
+use anyhow::{Context, Result};
+use rodio::Source;
+
+use super::processing::{envelope, normalize_audio, rms_level, trim_to_max_duration};
+
+/// Basic properties of a decoded WAV clip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WavInfo {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub duration_secs: f32,
+    /// RMS level across all channels, in linear amplitude (`0.0..=1.0`).
+    pub rms: f32,
+}
+
+/// Decode WAV bytes (any bit depth) into interleaved f32 samples in `-1.0..=1.0`.
+pub fn decode_samples(wav_bytes: &[u8]) -> Result<(hound::WavSpec, Vec<f32>)> {
+    let cursor = Cursor::new(wav_bytes);
+    let mut reader = hound::WavReader::new(cursor).context("failed to read WAV header")?;
+    let spec = reader.spec();
+
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .map(|s| s.context("failed to read float sample"))
+            .collect::<Result<Vec<_>>>()?,
+        hound::SampleFormat::Int => {
+            #[allow(clippy::cast_precision_loss)]
+            let max_val = (1_i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| {
+                    #[allow(clippy::cast_precision_loss)]
+                    let v = s.context("failed to read int sample")? as f32 / max_val;
+                    Ok(v)
+                })
+                .collect::<Result<Vec<_>>>()?
+        }
+    };
+
+    Ok((spec, samples))
+}
+
+/// Encode interleaved f32 samples as 16-bit PCM WAV with `spec`'s layout.
+#[allow(clippy::cast_possible_truncation)]
+pub fn encode_samples(spec: hound::WavSpec, samples: &[f32]) -> Result<Vec<u8>> {
+    let spec = hound::WavSpec {
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+        ..spec
+    };
+
+    let mut cursor = Cursor::new(Vec::new());
+    {
+        let mut writer =
+            hound::WavWriter::new(&mut cursor, spec).context("failed to create WAV writer")?;
+        for &sample in samples {
+            let int_sample = (sample.clamp(-1.0, 1.0) * 32767.0) as i16;
+            writer
+                .write_sample(int_sample)
+                .context("failed to write WAV sample")?;
+        }
+        writer.finalize().context("failed to finalize WAV")?;
+    }
+
+    Ok(cursor.into_inner())
+}
+
+/// Return a copy of `wav_bytes` peak-normalized to `target_db`.
+pub fn normalize_wav(wav_bytes: &[u8], target_db: f32) -> Result<Vec<u8>> {
+    let (spec, mut samples) = decode_samples(wav_bytes)?;
+    normalize_audio(&mut samples, target_db);
+    encode_samples(spec, &samples)
+}
+
+/// Return a copy of `wav_bytes` cut to its first `max_secs`, or `None`
+/// when the clip is already that short.
+pub fn trim_wav(wav_bytes: &[u8], max_secs: f32) -> Result<Option<Vec<u8>>> {
+    let (spec, mut samples) = decode_samples(wav_bytes)?;
+    let len = samples.len();
+    // Samples are interleaved, so count every channel's share of a second.
+    let rate = spec.sample_rate * u32::from(spec.channels);
+    trim_to_max_duration(&mut samples, rate, max_secs);
+    if samples.len() == len {
+        return Ok(None);
+    }
+    encode_samples(spec, &samples).map(Some)
+}
+
+/// Header details of `wav_bytes` together with its min/max envelope in
+/// `columns` columns, for drawing its waveform, from a single decode.
+pub fn analyze(wav_bytes: &[u8], columns: usize) -> Result<(WavInfo, Vec<(f32, f32)>)> {
+    let (spec, samples) = decode_samples(wav_bytes)?;
+    Ok((info_of(spec, &samples), envelope(&samples, spec.channels, columns)))
+}
+
+/// Whether `bytes` start with a RIFF/WAVE header.
+pub fn is_wav(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WAVE".as_slice())
+}
+
+/// Decode any format the playback decoder understands (mp3, flac, ogg, ...)
+/// and re-encode it as 16-bit WAV at the source sample rate.
+pub fn convert_to_wav(bytes: &[u8]) -> Result<Vec<u8>> {
+    let decoder = rodio::Decoder::try_from(Cursor::new(bytes.to_vec()))
+        .context("unsupported audio format")?;
+    let spec = hound::WavSpec {
+        channels: decoder.channels().get(),
+        sample_rate: decoder.sample_rate().get(),
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let samples: Vec<f32> = decoder.collect();
+    encode_samples(spec, &samples)
+}
+
+/// Prepare audio for a multipart upload.
+///
+/// With `convert` set, non-WAV input is re-encoded as WAV and the file
+/// extension changed to `.wav`; WAV input passes through untouched.
+pub fn prepare_upload(
+    bytes: Vec<u8>,
+    filename: String,
+    convert: bool,
+) -> Result<(Vec<u8>, String)> {
+    if !convert || is_wav(&bytes) {
+        return Ok((bytes, filename));
+    }
+    let wav = convert_to_wav(&bytes)?;
+    let name = std::path::Path::new(&filename)
+        .with_extension("wav")
+        .to_string_lossy()
+        .into_owned();
+    Ok((wav, name))
+}
+
+/// Parse WAV bytes and compute duration and loudness.
+pub fn wav_info(wav_bytes: &[u8]) -> Result<WavInfo> {
+    let (spec, samples) = decode_samples(wav_bytes)?;
+    Ok(info_of(spec, &samples))
+}
+
+/// Duration and loudness of `samples` decoded with `spec`.
+fn info_of(spec: hound::WavSpec, samples: &[f32]) -> WavInfo {
+    let frames = samples.len() / usize::from(spec.channels.max(1));
+
+    #[allow(clippy::cast_precision_loss)]
+    let duration_secs = if spec.sample_rate == 0 {
+        0.0
+    } else {
+        frames as f32 / spec.sample_rate as f32
+    };
+
+    WavInfo {
+        sample_rate: spec.sample_rate,
+        channels: spec.channels,
+        duration_secs,
+        rms: rms_level(samples),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::recorder::samples_to_wav;
+
+    #[test]
+    fn wav_info_duration_and_rate() {
+        let wav = samples_to_wav(&[0.5; 16_000], 16_000).expect("encode");
+        let info = wav_info(&wav).expect("info");
+        assert_eq!(info.sample_rate, 16_000);
+        assert_eq!(info.channels, 1);
+        assert!((info.duration_secs - 1.0).abs() < 1e-3);
+        assert!((info.rms - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn wav_info_silence_has_zero_rms() {
+        let wav = samples_to_wav(&[0.0; 100], 8_000).expect("encode");
+        let info = wav_info(&wav).expect("info");
+        assert!(info.rms.abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn normalize_wav_boosts_quiet_clip() {
+        let wav = samples_to_wav(&[0.05, -0.05, 0.025], 8_000).expect("encode");
+        let normalized = normalize_wav(&wav, 0.0).expect("normalize");
+        let (spec, samples) = decode_samples(&normalized).expect("decode");
+        assert_eq!(spec.sample_rate, 8_000);
+        assert!((samples[0] - 1.0).abs() < 1e-3);
+        assert!((samples[2] - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn trim_wav_cuts_long_clips_only() {
+        let wav = samples_to_wav(&[0.5; 16_000], 8_000).expect("encode");
+        let trimmed = trim_wav(&wav, 1.5).expect("trim").expect("trimmed");
+        let info = wav_info(&trimmed).expect("info");
+        assert!((info.duration_secs - 1.5).abs() < 1e-3);
+
+        assert!(trim_wav(&wav, 2.0).expect("trim").is_none());
+        assert!(trim_wav(&wav, 30.0).expect("trim").is_none());
+    }
+
+    #[test]
+    fn analyze_gives_info_and_requested_columns() {
+        let wav = samples_to_wav(&[0.5; 16_000], 16_000).expect("encode");
+        let (info, columns) = analyze(&wav, 100).expect("analyze");
+        assert_eq!(info, wav_info(&wav).expect("wav info"));
+        assert_eq!(columns.len(), 100);
+        assert!(columns.iter().all(|&(lo, hi)| lo == 0.0 && (hi - 0.5).abs() < 1e-3));
+        assert!(analyze(b"not a wav", 100).is_err());
+    }
+
+    /// `frames` silent MPEG-1 Layer III frames (mono, 44.1 kHz, 128 kbps).
+    fn silent_mp3(frames: usize) -> Vec<u8> {
+        // 144 * 128000 / 44100 = 417 bytes per frame without padding. An
+        // all-zero side info / main data block decodes to silence.
+        let mut frame = vec![0_u8; 417];
+        frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0xC0]);
+        frame.repeat(frames)
+    }
+
+    #[test]
+    fn convert_mp3_to_wav() {
+        let mp3 = silent_mp3(40);
+        assert!(!is_wav(&mp3));
+
+        let wav = convert_to_wav(&mp3).expect("convert");
+        assert!(is_wav(&wav));
+        let info = wav_info(&wav).expect("info");
+        assert_eq!(info.sample_rate, 44_100);
+        assert_eq!(info.channels, 1);
+        let expected = 40.0 * 1152.0 / 44_100.0;
+        assert!(
+            (info.duration_secs - expected).abs() < 0.05,
+            "duration {} != {expected}",
+            info.duration_secs
+        );
+    }
+
+    #[test]
+    fn prepare_upload_renames_converted_file() {
+        let (bytes, name) =
+            prepare_upload(silent_mp3(10), "voice.mp3".to_owned(), true).expect("prepare");
+        assert!(is_wav(&bytes));
+        assert_eq!(name, "voice.wav");
+    }
+
+    #[test]
+    fn prepare_upload_passes_through() {
+        let wav = samples_to_wav(&[0.1; 10], 8_000).expect("encode");
+        let (bytes, name) =
+            prepare_upload(wav.clone(), "a.wav".to_owned(), true).expect("prepare");
+        assert_eq!(bytes, wav);
+        assert_eq!(name, "a.wav");
+
+        let mp3 = silent_mp3(2);
+        let (bytes, name) =
+            prepare_upload(mp3.clone(), "a.mp3".to_owned(), false).expect("prepare");
+        assert_eq!(bytes, mp3);
+        assert_eq!(name, "a.mp3");
+    }
+
+    #[test]
+    fn wav_info_rejects_garbage() {
+        assert!(wav_info(b"not a wav file").is_err());
+    }
+}
@@ -1,8 +1,24 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::message::TabId;
+use crate::transcribe::whisper::WhisperModel;
+
+/// Qwen3-TTS model sizes the server script can load.
+pub const MODEL_SIZES: [&str; 2] = ["0.6B", "1.7B"];
+
+/// Allowed range, in dB, for the recording silence threshold.
+pub const SILENCE_THRESHOLD_RANGE: std::ops::RangeInclusive<f32> = -60.0..=-20.0;
+
+/// Allowed range, in seconds, for the longest reference kept untrimmed.
+pub const MAX_REFERENCE_SECS_RANGE: std::ops::RangeInclusive<f32> = 5.0..=60.0;
+
+/// Allowed range, in dB, for the peak level of normalized exports.
+pub const EXPORT_PEAK_RANGE: std::ops::RangeInclusive<f32> = -12.0..=0.0;
+
 /// Application configuration, persisted to `config.toml`.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct AppConfig {
@@ -10,6 +26,8 @@ pub struct AppConfig {
     pub server: ServerSection,
     #[serde(default)]
     pub ui: UiSection,
+    #[serde(default)]
+    pub audio: AudioSection,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -20,18 +38,138 @@ pub struct ServerSection {
     pub device: String,
     #[serde(default = "default_port")]
     pub port: u16,
+    /// Address the backend binds to and the client connects to.
+    #[serde(default = "default_bind_host")]
+    pub bind_host: String,
     #[serde(default)]
     pub python: Option<String>,
     #[serde(default = "default_script_path")]
     pub script_path: String,
+    /// One of [`MODEL_SIZES`]; smaller sizes load faster on small GPUs.
     #[serde(default = "default_model_size")]
     pub model_size: String,
+    /// Seconds of loading after which the loading screen suggests checking
+    /// the server log.
+    #[serde(default = "default_slow_start_secs")]
+    pub slow_start_secs: u64,
+    /// Connect to a server already answering on `port` instead of spawning
+    /// another one.
+    #[serde(default)]
+    pub use_existing_server: bool,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct UiSection {
     #[serde(default)]
     pub dark_mode: bool,
+    /// Level-match generated clips during playback.
+    #[serde(default)]
+    pub consistent_loudness: bool,
+    /// Seconds to skip when previewing a reference, keyed by reference id.
+    #[serde(default)]
+    pub preview_offsets: BTreeMap<String, f32>,
+    /// Export each clip's source reference alongside it.
+    #[serde(default)]
+    pub export_include_reference: bool,
+    /// Peak-normalize generated clips as they are exported.
+    #[serde(default)]
+    pub export_normalize: bool,
+    /// Peak level (dB) for normalized exports.
+    /// Read through [`UiSection::export_peak_db`], which clamps it.
+    #[serde(default = "default_export_peak_db")]
+    pub export_peak_db: f32,
+    /// Folder export dialogs open in; updated to the last one saved to.
+    #[serde(default)]
+    pub export_dir: Option<PathBuf>,
+    /// Pressing Enter in a text field triggers Generate; Shift+Enter starts
+    /// a new line.
+    #[serde(default)]
+    pub enter_to_generate: bool,
+    /// Stop playback when switching tabs. Running tasks are left alone.
+    #[serde(default)]
+    pub stop_on_tab_switch: bool,
+    /// Peak-normalize microphone recordings before they are used.
+    #[serde(default = "default_true")]
+    pub auto_normalize: bool,
+    /// Trim trailing silence from microphone recordings.
+    #[serde(default = "default_true")]
+    pub auto_trim_silence: bool,
+    /// RMS level (dB) below which a recording's tail counts as silence.
+    /// Read through [`UiSection::silence_threshold_db`], which clamps it.
+    #[serde(default = "default_silence_threshold_db")]
+    pub silence_threshold_db: f32,
+    /// Cut selected or recorded references longer than `max_reference_secs`.
+    #[serde(default)]
+    pub trim_long_references: bool,
+    /// Longest reference, in seconds, used as is.
+    /// Read through [`UiSection::reference_trim_secs`], which clamps it.
+    #[serde(default = "default_max_reference_secs")]
+    pub max_reference_secs: f32,
+    /// Character count past which the text counters warn. Generation is
+    /// still allowed.
+    #[serde(default = "default_text_soft_limit")]
+    pub text_soft_limit: usize,
+    /// Language last picked on each generation tab, restored on launch.
+    #[serde(default)]
+    pub last_languages: BTreeMap<TabId, String>,
+    /// Tab that was open last, restored once the server is ready.
+    #[serde(default = "default_active_tab", deserialize_with = "deserialize_tab")]
+    pub active_tab: TabId,
+}
+
+impl Default for UiSection {
+    fn default() -> Self {
+        Self {
+            dark_mode: false,
+            consistent_loudness: false,
+            preview_offsets: BTreeMap::new(),
+            export_include_reference: false,
+            export_normalize: false,
+            export_peak_db: default_export_peak_db(),
+            export_dir: None,
+            enter_to_generate: false,
+            stop_on_tab_switch: false,
+            auto_normalize: true,
+            auto_trim_silence: true,
+            silence_threshold_db: default_silence_threshold_db(),
+            trim_long_references: false,
+            max_reference_secs: default_max_reference_secs(),
+            text_soft_limit: default_text_soft_limit(),
+            last_languages: BTreeMap::new(),
+            active_tab: default_active_tab(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AudioSection {
+    /// Peak-normalize reference previews so A/B comparisons are level-matched.
+    #[serde(default)]
+    pub normalize_preview: bool,
+    /// Re-encode non-WAV uploads (mp3, flac, ...) as WAV before sending.
+    #[serde(default = "default_true")]
+    pub convert_uploads_to_wav: bool,
+    /// Whisper model used to transcribe references.
+    #[serde(default)]
+    pub whisper_model: WhisperModel,
+    /// Microphone to record from; unset uses the system default.
+    #[serde(default)]
+    pub input_device: Option<String>,
+    /// Speakers to play through; unset uses the system default.
+    #[serde(default)]
+    pub output_device: Option<String>,
+}
+
+impl Default for AudioSection {
+    fn default() -> Self {
+        Self {
+            normalize_preview: false,
+            convert_uploads_to_wav: true,
+            whisper_model: WhisperModel::Base,
+            input_device: None,
+            output_device: None,
+        }
+    }
 }
 
 impl Default for ServerSection {
@@ -40,13 +178,79 @@ impl Default for ServerSection {
             models: default_models(),
             device: default_device(),
             port: default_port(),
+            bind_host: default_bind_host(),
             python: None,
             script_path: default_script_path(),
             model_size: default_model_size(),
+            slow_start_secs: default_slow_start_secs(),
+            use_existing_server: false,
+        }
+    }
+}
+
+impl UiSection {
+    /// The silence threshold, clamped to [`SILENCE_THRESHOLD_RANGE`] so a
+    /// hand-edited config can't trim away speech or nothing at all.
+    pub fn silence_threshold_db(&self) -> f32 {
+        clamped(
+            self.silence_threshold_db,
+            SILENCE_THRESHOLD_RANGE,
+            default_silence_threshold_db(),
+        )
+    }
+
+    /// Length to trim long references to, or `None` when they are kept
+    /// whole.
+    pub fn reference_trim_secs(&self) -> Option<f32> {
+        if !self.trim_long_references {
+            return None;
         }
+        Some(clamped(
+            self.max_reference_secs,
+            MAX_REFERENCE_SECS_RANGE,
+            default_max_reference_secs(),
+        ))
+    }
+
+    /// Peak level to normalize exports to, or `None` when exports are
+    /// written as generated.
+    pub fn export_peak_db(&self) -> Option<f32> {
+        if !self.export_normalize {
+            return None;
+        }
+        Some(clamped(
+            self.export_peak_db,
+            EXPORT_PEAK_RANGE,
+            default_export_peak_db(),
+        ))
+    }
+
+    /// Set the silence threshold, clamped to [`SILENCE_THRESHOLD_RANGE`].
+    pub fn set_silence_threshold_db(&mut self, db: f32) {
+        self.silence_threshold_db =
+            clamped(db, SILENCE_THRESHOLD_RANGE, default_silence_threshold_db());
+    }
+
+    /// Set the reference trim length, clamped to [`MAX_REFERENCE_SECS_RANGE`].
+    pub fn set_max_reference_secs(&mut self, secs: f32) {
+        self.max_reference_secs =
+            clamped(secs, MAX_REFERENCE_SECS_RANGE, default_max_reference_secs());
+    }
+
+    /// Set the export peak level, clamped to [`EXPORT_PEAK_RANGE`].
+    pub fn set_export_peak_db(&mut self, db: f32) {
+        self.export_peak_db = clamped(db, EXPORT_PEAK_RANGE, default_export_peak_db());
     }
 }
 
+/// `value` clamped to `range`, or `default` when it is NaN.
+fn clamped(value: f32, range: std::ops::RangeInclusive<f32>, default: f32) -> f32 {
+    if value.is_nan() {
+        return default;
+    }
+    value.clamp(*range.start(), *range.end())
+}
+
 fn default_models() -> Vec<String> {
     vec![
         "base".to_owned(),
@@ -55,6 +259,44 @@ fn default_models() -> Vec<String> {
     ]
 }
 
+fn default_true() -> bool {
+    true
+}
+
+fn default_active_tab() -> TabId {
+    TabId::Clone
+}
+
+/// Read a tab name, falling back to the default for unknown or malformed
+/// values instead of rejecting the whole config.
+fn deserialize_tab<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<TabId, D::Error> {
+    use serde::de::IntoDeserializer;
+
+    let value = toml::Value::deserialize(deserializer)?;
+    let tab = value.as_str().and_then(|name| {
+        let parsed: Result<TabId, serde::de::value::Error> =
+            TabId::deserialize(name.into_deserializer());
+        parsed.ok()
+    });
+    Ok(tab.unwrap_or_else(default_active_tab))
+}
+
+fn default_silence_threshold_db() -> f32 {
+    crate::audio::processing::RECORDING_SILENCE_DB
+}
+
+fn default_max_reference_secs() -> f32 {
+    30.0
+}
+
+fn default_export_peak_db() -> f32 {
+    -1.0
+}
+
+fn default_text_soft_limit() -> usize {
+    1000
+}
+
 fn default_device() -> String {
     "auto".to_owned()
 }
@@ -63,6 +305,10 @@ fn default_port() -> u16 {
     8000
 }
 
+fn default_bind_host() -> String {
+    "127.0.0.1".to_owned()
+}
+
 fn default_script_path() -> String {
     "python/start_server.py".to_owned()
 }
@@ -71,15 +317,26 @@ fn default_model_size() -> String {
     "1.7B".to_owned()
 }
 
-/// Return the path to `config.toml` in the data directory.
-pub fn config_path() -> PathBuf {
+fn default_slow_start_secs() -> u64 {
+    300
+}
+
+/// Return qvox's data directory, `{data_dir}/qvox`, which holds the
+/// config, history, queue, models and transcription cache.
+pub fn data_dir() -> PathBuf {
     let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
-    base.join("qvox").join("config.toml")
+    base.join("qvox")
 }
 
-/// Load config from disk, returning defaults if the file does not exist.
-pub fn load() -> AppConfig {
-    let path = config_path();
+/// Return the path to `config.toml` in `data_dir`.
+pub fn config_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("config.toml")
+}
+
+/// Load config from `data_dir`, returning defaults if the file does not
+/// exist.
+pub fn load(data_dir: &Path) -> AppConfig {
+    let path = config_path(data_dir);
     if !path.exists() {
         return AppConfig::default();
     }
@@ -90,9 +347,9 @@ pub fn load() -> AppConfig {
     }
 }
 
-/// Save config to disk.
-pub fn save(config: &AppConfig) -> Result<()> {
-    let path = config_path();
+/// Save config to `data_dir`.
+pub fn save(data_dir: &Path, config: &AppConfig) -> Result<()> {
+    let path = config_path(data_dir);
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).context("failed to create config directory")?;
     }
@@ -108,6 +365,7 @@ impl AppConfig {
             models: self.server.models.clone(),
             device: self.server.device.clone(),
             port: self.server.port,
+            bind_host: self.server.bind_host.clone(),
             python_path: self.server.python.clone(),
             script_path: self.server.script_path.clone(),
             model_size: self.server.model_size.clone(),
@@ -127,12 +385,124 @@ mod tests {
         assert_eq!(config, decoded);
     }
 
+    #[test]
+    fn data_files_live_in_data_dir() {
+        let dir = data_dir();
+        assert!(dir.ends_with("qvox"));
+        assert_eq!(config_path(&dir).parent(), Some(dir.as_path()));
+        assert_eq!(crate::history::history_path(&dir).parent(), Some(dir.as_path()));
+        assert_eq!(crate::queue::queue_path(&dir).parent(), Some(dir.as_path()));
+    }
+
     #[test]
     fn deserialize_empty_toml() {
         let config: AppConfig = toml::from_str("").expect("deserialize empty");
         assert_eq!(config.server.port, 8000);
         assert_eq!(config.server.device, "auto");
+        assert_eq!(config.server.bind_host, "127.0.0.1");
+        assert!(!config.server.use_existing_server);
         assert!(!config.ui.dark_mode);
+        assert!(config.audio.convert_uploads_to_wav);
+        assert!(config.ui.auto_normalize);
+        assert!(config.ui.auto_trim_silence);
+        assert!((config.ui.silence_threshold_db() + 40.0).abs() < f32::EPSILON);
+        assert_eq!(config.ui.text_soft_limit, 1000);
+        assert_eq!(config.audio.whisper_model, WhisperModel::Base);
+        assert_eq!(config.audio.input_device, None);
+        assert_eq!(config.audio.output_device, None);
+        assert_eq!(config.ui.export_dir, None);
+    }
+
+    #[test]
+    fn clamped_keeps_values_in_range() {
+        assert!((clamped(5.0, 0.0..=1.0, 0.5) - 1.0).abs() < f32::EPSILON);
+        assert!(clamped(-5.0, 0.0..=1.0, 0.5).abs() < f32::EPSILON);
+        assert!((clamped(0.25, 0.0..=1.0, 0.5) - 0.25).abs() < f32::EPSILON);
+        assert!((clamped(f32::NAN, 0.0..=1.0, 0.5) - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn silence_threshold_is_clamped() {
+        let mut ui = UiSection {
+            silence_threshold_db: -90.0,
+            ..UiSection::default()
+        };
+        assert!((ui.silence_threshold_db() + 60.0).abs() < f32::EPSILON);
+        ui.silence_threshold_db = 0.0;
+        assert!((ui.silence_threshold_db() + 20.0).abs() < f32::EPSILON);
+        ui.silence_threshold_db = -35.0;
+        assert!((ui.silence_threshold_db() + 35.0).abs() < f32::EPSILON);
+        ui.silence_threshold_db = f32::NAN;
+        assert!((ui.silence_threshold_db() + 40.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn reference_trim_is_clamped() {
+        let mut ui = UiSection::default();
+        assert_eq!(ui.reference_trim_secs(), None);
+        ui.trim_long_references = true;
+        assert_eq!(ui.reference_trim_secs(), Some(30.0));
+        ui.max_reference_secs = 1.0;
+        assert_eq!(ui.reference_trim_secs(), Some(5.0));
+        ui.max_reference_secs = f32::NAN;
+        assert_eq!(ui.reference_trim_secs(), Some(30.0));
+        ui.trim_long_references = false;
+        assert_eq!(ui.reference_trim_secs(), None);
+    }
+
+    #[test]
+    fn export_peak_only_when_normalizing() {
+        let mut ui = UiSection {
+            export_peak_db: -30.0,
+            ..UiSection::default()
+        };
+        assert_eq!(ui.export_peak_db(), None);
+        ui.export_normalize = true;
+        assert_eq!(ui.export_peak_db(), Some(-12.0));
+        ui.export_peak_db = 3.0;
+        assert_eq!(ui.export_peak_db(), Some(0.0));
+        ui.export_peak_db = f32::NAN;
+        assert_eq!(ui.export_peak_db(), Some(-1.0));
+    }
+
+    #[test]
+    fn active_tab_round_trip() {
+        let mut config = AppConfig::default();
+        config.ui.active_tab = TabId::MultiSpeaker;
+        let toml_str = toml::to_string_pretty(&config).expect("serialize");
+        assert!(toml_str.contains("active_tab = \"multi_speaker\""));
+        let decoded: AppConfig = toml::from_str(&toml_str).expect("deserialize");
+        assert_eq!(decoded.ui.active_tab, TabId::MultiSpeaker);
+    }
+
+    #[test]
+    fn unknown_active_tab_defaults_to_clone() {
+        for value in ["\"karaoke\"", "3"] {
+            let toml_str = format!("[ui]\ndark_mode = true\nactive_tab = {value}\n");
+            let config: AppConfig = toml::from_str(&toml_str).expect("deserialize");
+            assert_eq!(config.ui.active_tab, TabId::Clone);
+            assert!(config.ui.dark_mode);
+        }
+    }
+
+    #[test]
+    fn input_device_round_trip() {
+        let mut config = AppConfig::default();
+        config.audio.input_device = Some("USB Microphone".to_owned());
+        let toml_str = toml::to_string_pretty(&config).expect("serialize");
+        let decoded: AppConfig = toml::from_str(&toml_str).expect("deserialize");
+        assert_eq!(decoded.audio.input_device.as_deref(), Some("USB Microphone"));
+    }
+
+    #[test]
+    fn last_languages_round_trip() {
+        let mut config = AppConfig::default();
+        config.ui.last_languages.insert(TabId::Clone, "Japanese".to_owned());
+        config.ui.last_languages.insert(TabId::MultiSpeaker, "English".to_owned());
+        let toml_str = toml::to_string_pretty(&config).expect("serialize");
+        assert!(toml_str.contains("multi_speaker = \"English\""));
+        let decoded: AppConfig = toml::from_str(&toml_str).expect("deserialize");
+        assert_eq!(decoded.ui.last_languages, config.ui.last_languages);
     }
 
     #[test]
@@ -149,6 +519,7 @@ dark_mode = true
         assert_eq!(config.server.port, 9000);
         assert_eq!(config.server.models, vec!["base", "voice_design"]);
         assert!(config.ui.dark_mode);
+        assert!(config.ui.auto_trim_silence);
         assert_eq!(config.server.device, "auto");
     }
 
@@ -163,7 +534,18 @@ dark_mode = true
 
     #[test]
     fn config_path_has_filename() {
-        let path = config_path();
+        let path = config_path(Path::new("/data/qvox"));
         assert_eq!(path.file_name().and_then(|f| f.to_str()), Some("config.toml"));
     }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join("qvox_test_config_round_trip");
+        let mut config = AppConfig::default();
+        config.server.port = 9001;
+        save(&dir, &config).expect("save");
+        assert_eq!(load(&dir), config);
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(load(&dir), AppConfig::default());
+    }
 }
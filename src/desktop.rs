@@ -0,0 +1,85 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+
+/// Open the OS file manager at `path`, selecting it where the platform supports it.
+///
+/// Directories are opened directly; files open their containing folder.
+pub fn reveal_in_file_manager(path: &Path) -> Result<()> {
+    if !path.exists() {
+        bail!("{} does not exist yet", path.display());
+    }
+    reveal_command(path, path.is_dir())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("failed to open file manager for {}", path.display()))?;
+    Ok(())
+}
+
+/// Build the platform command that reveals `path`.
+fn reveal_command(path: &Path, is_dir: bool) -> Command {
+    if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("open");
+        if !is_dir {
+            cmd.arg("-R");
+        }
+        cmd.arg(path);
+        cmd
+    } else if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("explorer");
+        if is_dir {
+            cmd.arg(path);
+        } else {
+            cmd.arg(format!("/select,{}", path.display()));
+        }
+        cmd
+    } else {
+        // xdg-open cannot select a file, so open its folder instead.
+        let target = if is_dir {
+            path
+        } else {
+            path.parent().unwrap_or(path)
+        };
+        let mut cmd = Command::new("xdg-open");
+        cmd.arg(target);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reveal_command_opens_parent_of_file() {
+        let cmd = reveal_command(Path::new("/tmp/qvox/out.wav"), false);
+        assert_eq!(cmd.get_program(), "xdg-open");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, vec![Path::new("/tmp/qvox").as_os_str()]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reveal_command_opens_directory() {
+        let cmd = reveal_command(Path::new("/tmp/qvox"), true);
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, vec![Path::new("/tmp/qvox").as_os_str()]);
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn reveal_command_selects_file() {
+        let cmd = reveal_command(Path::new("/tmp/qvox/out.wav"), false);
+        assert_eq!(cmd.get_program(), "open");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args[0], "-R");
+    }
+
+    #[test]
+    fn reveal_missing_path_errors() {
+        assert!(reveal_in_file_manager(Path::new("/nonexistent/qvox/path")).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use cpal::traits::HostTrait;
+
+use crate::api::client::ApiClient;
+use crate::config::AppConfig;
+use crate::transcribe::whisper::WhisperModel;
+
+/// Outcome of a single diagnostic check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticResult {
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+    /// What the user can do about a failure.
+    pub remediation: Option<&'static str>,
+}
+
+impl DiagnosticResult {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: true,
+            detail: detail.into(),
+            remediation: None,
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, remediation: &'static str) -> Self {
+        Self {
+            name,
+            passed: false,
+            detail: detail.into(),
+            remediation: Some(remediation),
+        }
+    }
+}
+
+/// Run every check against the app's `data_dir`, including a health
+/// request against `base_url`.
+pub async fn run_diagnostics(
+    config: AppConfig,
+    base_url: String,
+    data_dir: PathBuf,
+) -> Vec<DiagnosticResult> {
+    let mut results = run_local_checks(&config, &data_dir);
+    results.push(check_backend(&base_url).await);
+    results
+}
+
+/// Run the checks that don't need the backend.
+pub fn run_local_checks(config: &AppConfig, data_dir: &Path) -> Vec<DiagnosticResult> {
+    vec![
+        check_uv(),
+        check_python(),
+        check_script_path(&config.server.script_path),
+        check_dir_writable(data_dir),
+        check_output_device(),
+        check_input_device(),
+        check_whisper_model(data_dir, config.audio.whisper_model),
+    ]
+}
+
+fn check_uv() -> DiagnosticResult {
+    let ok = Command::new("uv")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success());
+    if ok {
+        DiagnosticResult::pass("uv", "found on PATH")
+    } else {
+        DiagnosticResult::fail(
+            "uv",
+            "not found on PATH",
+            "Install uv (https://docs.astral.sh/uv/) or enter the nix dev shell.",
+        )
+    }
+}
+
+fn check_python() -> DiagnosticResult {
+    match crate::server::manager::find_python() {
+        Ok(python) => DiagnosticResult::pass("Python", format!("found {python}")),
+        Err(e) => DiagnosticResult::fail("Python", e.to_string(), "Install Python 3."),
+    }
+}
+
+fn check_script_path(path: &str) -> DiagnosticResult {
+    match crate::server::manager::check_script_path(path) {
+        Ok(()) => DiagnosticResult::pass("Server script", path.to_owned()),
+        Err(e) => {
+            DiagnosticResult::fail("Server script", e, "Fix the server script path in Settings.")
+        }
+    }
+}
+
+fn check_dir_writable(dir: &Path) -> DiagnosticResult {
+    const NAME: &str = "Data directory";
+    const FIX: &str = "Check permissions on the data directory.";
+
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        return DiagnosticResult::fail(NAME, format!("{}: {e}", dir.display()), FIX);
+    }
+    let probe = dir.join(".qvox_write_test");
+    match std::fs::write(&probe, b"ok") {
+        Ok(()) => {
+            std::fs::remove_file(&probe).ok();
+            DiagnosticResult::pass(NAME, dir.display().to_string())
+        }
+        Err(e) => DiagnosticResult::fail(NAME, format!("{}: {e}", dir.display()), FIX),
+    }
+}
+
+fn check_output_device() -> DiagnosticResult {
+    if cpal::default_host().default_output_device().is_some() {
+        DiagnosticResult::pass("Audio output", "default device present")
+    } else {
+        DiagnosticResult::fail(
+            "Audio output",
+            "no output device",
+            "Connect speakers/headphones or check your sound server.",
+        )
+    }
+}
+
+fn check_input_device() -> DiagnosticResult {
+    if cpal::default_host().default_input_device().is_some() {
+        DiagnosticResult::pass("Audio input", "default device present")
+    } else {
+        DiagnosticResult::fail(
+            "Audio input",
+            "no input device",
+            "Connect a microphone to use recording (uploads still work).",
+        )
+    }
+}
+
+fn check_whisper_model(data_dir: &Path, model: WhisperModel) -> DiagnosticResult {
+    if crate::transcribe::whisper::model_exists(data_dir, model) {
+        DiagnosticResult::pass("Whisper model", format!("{model} downloaded"))
+    } else {
+        DiagnosticResult::fail(
+            "Whisper model",
+            format!("{model} not downloaded"),
+            "It downloads automatically on first transcription.",
+        )
+    }
+}
+
+async fn check_backend(base_url: &str) -> DiagnosticResult {
+    match ApiClient::new(base_url).health().await {
+        Ok(resp) if resp.voice_cloner_loaded => {
+            DiagnosticResult::pass("Backend", format!("{base_url} ({})", resp.status))
+        }
+        Ok(_) => DiagnosticResult::fail(
+            "Backend",
+            format!("{base_url} is up but models are still loading"),
+            "Wait for model loading to finish.",
+        ),
+        Err(e) => DiagnosticResult::fail(
+            "Backend",
+            format!("{base_url}: {e}"),
+            "Restart the server and check its logs.",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_path_missing_fails() {
+        let result = check_script_path("/nonexistent/start_server.py");
+        assert!(!result.passed);
+        assert!(result.remediation.is_some());
+    }
+
+    #[test]
+    fn script_path_existing_passes() {
+        let dir = std::env::temp_dir().join("qvox_test_diag_script");
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("start_server.py");
+        std::fs::write(&path, "").expect("write");
+
+        let result = check_script_path(&path.to_string_lossy());
+        assert!(result.passed);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn writable_dir_passes() {
+        let dir = std::env::temp_dir().join("qvox_test_diag_writable");
+        let result = check_dir_writable(&dir);
+        assert!(result.passed, "{result:?}");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn dir_under_a_file_fails() {
+        let dir = std::env::temp_dir().join("qvox_test_diag_blocked");
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let file = dir.join("file");
+        std::fs::write(&file, "").expect("write");
+
+        let result = check_dir_writable(&file.join("sub"));
+        assert!(!result.passed);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn device_checks_are_structured() {
+        let output = check_output_device();
+        assert_eq!(output.name, "Audio output");
+        assert_eq!(output.passed, output.remediation.is_none());
+
+        let input = check_input_device();
+        assert_eq!(input.name, "Audio input");
+        assert_eq!(input.passed, input.remediation.is_none());
+    }
+
+    #[test]
+    fn local_checks_cover_everything_but_backend() {
+        let dir = std::env::temp_dir().join("qvox_test_diag_local");
+        let results = run_local_checks(&AppConfig::default(), &dir);
+        let names: Vec<_> = results.iter().map(|r| r.name).collect();
+        assert!(names.contains(&"Server script"));
+        assert!(names.contains(&"Whisper model"));
+        assert!(!names.contains(&"Backend"));
+
+        let data_dir = results.iter().find(|r| r.name == "Data directory");
+        assert_eq!(data_dir.map(|r| r.detail.clone()), Some(dir.display().to_string()));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,170 @@
+use std::path::{Path, PathBuf};
+
+use crate::api::client::ApiClient;
+use crate::api::types::{GeneratedAudio, ReferenceAudio};
+
+/// One generated clip to export, paired with its source reference when
+/// requested and still present on the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportEntry {
+    pub audio_id: String,
+    pub file_name: String,
+    pub reference_id: Option<String>,
+}
+
+/// Build export entries for `items`, resolving each `ref_audio_id` against
+/// the known references. Entries whose reference was deleted export alone.
+pub fn plan_export(
+    items: &[GeneratedAudio],
+    references: &[ReferenceAudio],
+    include_reference: bool,
+) -> Vec<ExportEntry> {
+    items
+        .iter()
+        .map(|item| ExportEntry {
+            audio_id: item.id.clone(),
+            file_name: sanitize_file_name(&item.filename),
+            reference_id: item
+                .ref_audio_id
+                .as_ref()
+                .filter(|_| include_reference)
+                .filter(|id| references.iter().any(|r| &r.id == *id))
+                .cloned(),
+        })
+        .collect()
+}
+
+/// `name` reduced to a plain file name that is safe on every platform:
+/// directories are dropped and reserved characters become `_`.
+pub fn sanitize_file_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '|' | '?' | '*') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_matches(|c: char| c == '.' || c.is_whitespace());
+    if cleaned.is_empty() {
+        "export.wav".to_owned()
+    } else {
+        cleaned.to_owned()
+    }
+}
+
+/// Where the reference clip is written next to an exported file:
+/// `take.wav` → `take_reference.wav`.
+pub fn reference_path(dest: &Path) -> PathBuf {
+    let stem = dest
+        .file_stem()
+        .map_or_else(|| "export".into(), |s| s.to_string_lossy());
+    dest.with_file_name(format!("{stem}_reference.wav"))
+}
+
+/// A file dialog titled `title` that opens in `start_dir`, creating it
+/// first. A folder that can't be created is left out, so the dialog falls
+/// back to the system default.
+pub async fn export_dialog(title: &str, start_dir: Option<PathBuf>) -> rfd::AsyncFileDialog {
+    let dialog = rfd::AsyncFileDialog::new().set_title(title);
+    match start_dir {
+        Some(dir) if tokio::fs::create_dir_all(&dir).await.is_ok() => dialog.set_directory(dir),
+        _ => dialog,
+    }
+}
+
+/// Download `entry` to `dest`, plus its reference clip alongside it.
+/// With `peak_db` set the generated clip is peak-normalized to it first;
+/// the reference is written as uploaded.
+///
+/// A reference that fails to download (e.g. deleted meanwhile) is skipped;
+/// only a failure on the generated clip itself is an error.
+pub async fn export_entry(
+    client: &ApiClient,
+    entry: &ExportEntry,
+    dest: &Path,
+    peak_db: Option<f32>,
+) -> Result<Vec<PathBuf>, String> {
+    let mut audio = client
+        .task_audio(&entry.audio_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(db) = peak_db {
+        audio = crate::audio::wav::normalize_wav(&audio, db)
+            .map_err(|e| format!("failed to normalize {}: {e}", entry.file_name))?;
+    }
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    tokio::fs::write(dest, audio)
+        .await
+        .map_err(|e| format!("failed to write {}: {e}", dest.display()))?;
+    let mut written = vec![dest.to_path_buf()];
+
+    if let Some(ref_id) = &entry.reference_id
+        && let Ok(ref_audio) = client.reference_audio(ref_id).await
+    {
+        let ref_dest = reference_path(dest);
+        if tokio::fs::write(&ref_dest, ref_audio).await.is_ok() {
+            written.push(ref_dest);
+        }
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{generated, reference};
+
+    fn cloned_from(id: &str, ref_id: Option<&str>) -> GeneratedAudio {
+        GeneratedAudio {
+            ref_audio_id: ref_id.map(str::to_owned),
+            ..generated(id, "Hello")
+        }
+    }
+
+    #[test]
+    fn plan_export_pairs_existing_references() {
+        let items = [
+            cloned_from("a", Some("ref-1")),
+            cloned_from("b", Some("deleted")),
+            cloned_from("c", None),
+        ];
+        let plan = plan_export(&items, &[reference("ref-1", "sample.wav")], true);
+
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan[0].reference_id.as_deref(), Some("ref-1"));
+        assert_eq!(plan[1].reference_id, None);
+        assert_eq!(plan[2].reference_id, None);
+        assert_eq!(plan[0].file_name, "a.wav");
+    }
+
+    #[test]
+    fn plan_export_without_references() {
+        let items = [cloned_from("a", Some("ref-1"))];
+        let plan = plan_export(&items, &[reference("ref-1", "sample.wav")], false);
+        assert_eq!(plan[0].reference_id, None);
+    }
+
+    #[test]
+    fn sanitize_file_name_keeps_only_a_safe_name() {
+        assert_eq!(sanitize_file_name("take.wav"), "take.wav");
+        assert_eq!(sanitize_file_name("../../etc/take.wav"), "take.wav");
+        assert_eq!(sanitize_file_name("C:\\out\\take.wav"), "take.wav");
+        assert_eq!(sanitize_file_name("what? \"yes\".wav"), "what_ _yes_.wav");
+        assert_eq!(sanitize_file_name(".."), "export.wav");
+    }
+
+    #[test]
+    fn reference_path_sits_next_to_export() {
+        let path = reference_path(Path::new("/tmp/out/take.wav"));
+        assert_eq!(path, Path::new("/tmp/out/take_reference.wav"));
+    }
+}
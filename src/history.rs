@@ -0,0 +1,232 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::api::types::ReferenceAudio;
+use crate::message::{TabId, TaskRequest};
+
+/// Oldest entries beyond this many are dropped when appending.
+const MAX_ENTRIES: usize = 500;
+
+/// One completed generation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEntry {
+    /// Unix time (seconds) the generation finished.
+    pub timestamp: u64,
+    /// Tab the generation was started from.
+    pub tab: TabId,
+    pub text: String,
+    pub language: String,
+    /// Reference name(s), uploaded file name, or speaker the voice came from.
+    #[serde(default)]
+    pub ref_name: Option<String>,
+    /// Seconds from submit to finished audio.
+    pub generation_secs: u64,
+    /// The request to re-run; `None` for uploads, whose audio isn't kept.
+    #[serde(default)]
+    pub request: Option<TaskRequest>,
+}
+
+impl HistoryEntry {
+    /// Build an entry for a finished queueable request, naming references
+    /// from `references` where they are still known.
+    pub fn from_request(
+        request: &TaskRequest,
+        references: &[ReferenceAudio],
+        generation_secs: u64,
+    ) -> Self {
+        let ref_label = |id: &str| {
+            references
+                .iter()
+                .find(|r| r.id == id)
+                .map_or_else(|| id.to_owned(), |r| r.display_name().to_owned())
+        };
+        let (tab, text, language, ref_name) = match request {
+            TaskRequest::Clone(r) => (
+                TabId::Clone,
+                r.text.clone(),
+                r.language.clone(),
+                Some(ref_label(&r.ref_audio_id)),
+            ),
+            TaskRequest::VoiceDesign(r) => {
+                (TabId::VoiceDesign, r.text.clone(), r.language.clone(), None)
+            }
+            TaskRequest::CustomVoice(r) => (
+                TabId::CustomVoice,
+                r.text.clone(),
+                r.language.clone(),
+                Some(r.speaker.clone()),
+            ),
+            TaskRequest::MultiSpeaker(r) => {
+                let text: Vec<&str> = r.segments.iter().map(|s| s.text.as_str()).collect();
+                let mut names: Vec<String> =
+                    r.segments.iter().map(|s| ref_label(&s.ref_audio_id)).collect();
+                names.dedup();
+                let language = r
+                    .segments
+                    .first()
+                    .map_or_else(|| "auto".to_owned(), |s| s.language.clone());
+                (TabId::MultiSpeaker, text.join(" "), language, Some(names.join(", ")))
+            }
+        };
+        Self {
+            timestamp: now_secs(),
+            tab,
+            text,
+            language,
+            ref_name,
+            generation_secs,
+            request: Some(request.clone()),
+        }
+    }
+
+    /// Build an entry for an Upload tab generation.
+    pub fn from_upload(
+        text: String,
+        language: String,
+        file_name: Option<String>,
+        generation_secs: u64,
+    ) -> Self {
+        Self {
+            timestamp: now_secs(),
+            tab: TabId::Upload,
+            text,
+            language,
+            ref_name: file_name,
+            generation_secs,
+            request: None,
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Return the path to `history.json` in `data_dir`.
+pub fn history_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("history.json")
+}
+
+/// Load the generation history, oldest first. Missing or unreadable
+/// history is empty.
+pub fn load_history(data_dir: &Path) -> Vec<HistoryEntry> {
+    read_history(&history_path(data_dir))
+}
+
+/// Persist the generation history.
+pub fn save_history(data_dir: &Path, history: &[HistoryEntry]) -> Result<()> {
+    write_history(&history_path(data_dir), history)
+}
+
+/// Append `entry`, dropping the oldest entries beyond `MAX_ENTRIES`.
+pub fn push_entry(history: &mut Vec<HistoryEntry>, entry: HistoryEntry) {
+    history.push(entry);
+    if history.len() > MAX_ENTRIES {
+        let excess = history.len() - MAX_ENTRIES;
+        history.drain(..excess);
+    }
+}
+
+fn write_history(path: &Path, history: &[HistoryEntry]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("failed to create history directory")?;
+    }
+    let contents =
+        serde_json::to_string_pretty(history).context("failed to serialize history")?;
+    std::fs::write(path, contents).context("failed to write history file")?;
+    Ok(())
+}
+
+fn read_history(path: &Path) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::{CloneRequest, MultiSpeakerRequest, MultiSpeakerSegment};
+    use crate::test_fixtures::reference;
+
+    fn clone_req(ref_id: &str) -> TaskRequest {
+        TaskRequest::Clone(CloneRequest {
+            text: "Hello".to_owned(),
+            ref_audio_id: ref_id.to_owned(),
+            ref_text: None,
+            language: "English".to_owned(),
+            batch_line: None,
+        })
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let dir = std::env::temp_dir().join("qvox_test_history");
+        let path = dir.join("history.json");
+        let history = vec![
+            HistoryEntry::from_request(&clone_req("ref-1"), &[], 12),
+            HistoryEntry::from_upload("Hi".to_owned(), "auto".to_owned(), None, 3),
+        ];
+
+        write_history(&path, &history).expect("write");
+        assert_eq!(read_history(&path), history);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn read_missing_history_is_empty() {
+        assert!(read_history(Path::new("/nonexistent/qvox/history.json")).is_empty());
+    }
+
+    #[test]
+    fn clone_entry_names_reference() {
+        let entry =
+            HistoryEntry::from_request(&clone_req("ref-1"), &[reference("ref-1", "Alice")], 7);
+        assert_eq!(entry.tab, TabId::Clone);
+        assert_eq!(entry.text, "Hello");
+        assert_eq!(entry.language, "English");
+        assert_eq!(entry.ref_name.as_deref(), Some("Alice"));
+        assert_eq!(entry.generation_secs, 7);
+        assert_eq!(entry.request, Some(clone_req("ref-1")));
+    }
+
+    #[test]
+    fn multi_speaker_entry_joins_segments() {
+        let segment = |text: &str, ref_id: &str| MultiSpeakerSegment {
+            text: text.to_owned(),
+            ref_audio_id: ref_id.to_owned(),
+            ref_text: None,
+            language: "Japanese".to_owned(),
+        };
+        let request = TaskRequest::MultiSpeaker(MultiSpeakerRequest {
+            segments: vec![segment("A", "ref-1"), segment("B", "ref-2")],
+        });
+        let references = [reference("ref-1", "Alice"), reference("ref-2", "Bob")];
+
+        let entry = HistoryEntry::from_request(&request, &references, 20);
+        assert_eq!(entry.tab, TabId::MultiSpeaker);
+        assert_eq!(entry.text, "A B");
+        assert_eq!(entry.language, "Japanese");
+        assert_eq!(entry.ref_name.as_deref(), Some("Alice, Bob"));
+    }
+
+    #[test]
+    fn push_entry_caps_history() {
+        let mut history = Vec::new();
+        for i in 0..MAX_ENTRIES + 3 {
+            push_entry(
+                &mut history,
+                HistoryEntry::from_upload(i.to_string(), "auto".to_owned(), None, 0),
+            );
+        }
+        assert_eq!(history.len(), MAX_ENTRIES);
+        assert_eq!(history[0].text, "3");
+    }
+}
@@ -5,18 +5,77 @@ mod api;
 mod app;
 mod audio;
 mod config;
+mod desktop;
+mod diagnostics;
+mod export;
+mod history;
 mod message;
+mod preset;
+mod queue;
 mod server;
+#[cfg(test)]
+mod test_fixtures;
 mod transcribe;
 mod views;
 
 use app::Qvox;
 
 fn main() -> anyhow::Result<()> {
-    iced::application(Qvox::new, Qvox::update, Qvox::view)
+    init_logging();
+
+    if std::env::args().nth(1).as_deref() == Some("doctor") {
+        return doctor();
+    }
+
+    // --safe-mode: skip spawning the server and open Settings directly.
+    let safe_mode = std::env::args().skip(1).any(|a| a == "--safe-mode");
+
+    iced::application(move || Qvox::new(safe_mode), Qvox::update, Qvox::view)
         .title(Qvox::title)
         .subscription(Qvox::subscription)
         .theme(Qvox::theme)
+        .exit_on_close_request(false)
         .run()?;
     Ok(())
 }
+
+/// Log to stderr when `QVOX_LOG` is set: `QVOX_LOG=1` traces API requests
+/// and responses, and any other value is used as an `EnvFilter` directive
+/// (e.g. `QVOX_LOG=qvox=trace,reqwest=debug`).
+fn init_logging() {
+    let Ok(directive) = std::env::var("QVOX_LOG") else {
+        return;
+    };
+    let directive = if directive == "1" {
+        "qvox=debug".to_owned()
+    } else {
+        directive
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::new(directive))
+        .with_writer(std::io::stderr)
+        .init();
+}
+
+/// `qvox doctor`: print diagnostic results and exit non-zero on failure.
+fn doctor() -> anyhow::Result<()> {
+    let data_dir = config::data_dir();
+    let config = config::load(&data_dir);
+    let base_url = server::manager::base_url_for(&config.server.bind_host, config.server.port);
+    let runtime = tokio::runtime::Runtime::new()?;
+    let results = runtime.block_on(diagnostics::run_diagnostics(config, base_url, data_dir));
+
+    for r in &results {
+        let mark = if r.passed { "PASS" } else { "FAIL" };
+        println!("[{mark}] {}: {}", r.name, r.detail);
+        if let Some(fix) = r.remediation {
+            println!("       {fix}");
+        }
+    }
+
+    if results.iter().all(|r| r.passed) {
+        Ok(())
+    } else {
+        anyhow::bail!("some checks failed")
+    }
+}
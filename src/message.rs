@@ -1,6 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::client::ApiError;
 use crate::api::types::{
-    CapabilitiesResponse, GeneratedAudio, LanguagesResponse, ReferenceAudio, TaskStatus,
-    TaskStatusResponse,
+    CapabilitiesResponse, CloneRequest, CustomVoiceRequest, GeneratedAudio, HealthResponse,
+    LanguagesResponse, MultiSpeakerRequest, ReferenceAudio, TaskStatus, TaskStatusResponse,
+    VoiceDesignRequest,
 };
 
 #[derive(Debug, Clone)]
@@ -9,50 +13,91 @@ pub enum Message {
     // ─── Server lifecycle ───────────────────────────────────────
     /// Server process has been spawned; begin health polling.
     ServerSpawned,
-    /// Result of a health check poll.
-    HealthCheck(bool),
+    /// Answer to the check for a server already running on the configured
+    /// port: the host that answered, if any.
+    ExistingServerProbed(Option<String>),
+    /// Result of a health check poll: the host that answered and its
+    /// health, or `None` if no host answered.
+    HealthCheck(Option<(String, HealthResponse)>),
     /// Server is ready (voice cloner loaded).
     ServerReady,
     /// Server failed to start or crashed.
     ServerError(String),
     /// Elapsed-time tick while loading (every 1 second).
     Tick,
+    /// Periodic check, once the server is ready, that it is still alive.
+    ServerWatchTick,
+    /// Whether an attached server still answered the watch tick's health
+    /// probe.
+    AttachedServerProbed(bool),
+    /// Show or hide the server log on the loading screen.
+    ServerLogsToggled,
+    /// Kill and re-spawn the server with the current settings.
+    ServerRestart,
 
     // ─── Data loading ───────────────────────────────────────────
     /// Capabilities fetched from server.
     CapabilitiesLoaded(Result<CapabilitiesResponse, String>),
+    /// Health check fetched once the server is ready.
+    HealthLoaded(Result<HealthResponse, String>),
     /// Reference audio list fetched.
     ReferencesLoaded(Result<Vec<ReferenceAudio>, String>),
     /// Languages list fetched.
     LanguagesLoaded(Result<LanguagesResponse, String>),
 
+    // ─── Reference management ───────────────────────────────────
+    /// Rename field edited for a reference (id, draft name).
+    ReferenceNameChanged(String, String),
+    /// Rename a reference (id, new name).
+    ReferenceRename(String, String),
+    /// Rename result (id, name stored by the server).
+    ReferenceRenamed(Result<(String, String), String>),
+    /// Ask to delete a reference by id.
+    ReferenceDelete(String),
+    /// Deletion result (id of the deleted reference).
+    ReferenceDeleted(Result<String, String>),
+    /// Open a reference by id in the Upload tab to transcribe it.
+    ReferenceTranscribe(String),
+    /// Audio of the reference to transcribe fetched (display name, bytes).
+    ReferenceTranscribeLoaded(Result<(String, Vec<u8>), String>),
+
     // ─── Tab navigation ─────────────────────────────────────────
     /// User switched tabs.
     TabSelected(TabId),
+    /// Jump back to the previously active tab.
+    TabBack,
 
     // ─── Clone tab inputs ───────────────────────────────────────
-    /// Text input changed.
-    CloneTextChanged(String),
+    /// Edit in the text editor.
+    CloneTextAction(iced::widget::text_editor::Action),
     /// Reference audio selected from dropdown.
     CloneRefSelected(String),
     /// Language selected.
     CloneLanguageSelected(String),
     /// Generate button pressed.
     CloneGenerate,
+    /// Reset the clone tab, keeping its language.
+    CloneClear,
+    /// Batch mode (one generation per line) toggled.
+    CloneBatchToggled(bool),
+    /// Drop the batch's lines that haven't been sent yet.
+    CloneBatchStop,
 
     // ─── Design tab inputs ─────────────────────────────────────
-    /// Text input changed on design tab.
-    DesignTextChanged(String),
+    /// Edit in the design tab's text editor.
+    DesignTextAction(iced::widget::text_editor::Action),
     /// Voice description (instruct) changed.
     DesignInstructChanged(String),
     /// Language selected on design tab.
     DesignLanguageSelected(String),
     /// Generate button pressed on design tab.
     DesignGenerate,
+    /// Reset the design tab, keeping its language.
+    DesignClear,
 
     // ─── Custom Voice tab inputs ────────────────────────────────
-    /// Text input changed on custom voice tab.
-    CustomTextChanged(String),
+    /// Edit in the custom voice tab's text editor.
+    CustomTextAction(iced::widget::text_editor::Action),
     /// Speaker selected from dropdown.
     CustomSpeakerSelected(String),
     /// Language selected on custom voice tab.
@@ -61,12 +106,24 @@ pub enum Message {
     CustomInstructChanged(String),
     /// Generate button pressed on custom voice tab.
     CustomGenerate,
+    /// Reset the custom voice tab, keeping its language.
+    CustomClear,
 
     // ─── Multi-Speaker tab inputs ──────────────────────────────
     /// Add a new segment.
     MultiAddSegment,
     /// Remove segment at index.
     MultiRemoveSegment(usize),
+    /// Swap the segment at index with the one before it.
+    MultiMoveSegmentUp(usize),
+    /// Swap the segment at index with the one after it.
+    MultiMoveSegmentDown(usize),
+    /// Insert a copy of the segment at index (same speaker, empty text) after it.
+    MultiDuplicateSegment(usize),
+    /// Open a dialogue script (`Speaker: text` per line) to replace the segments.
+    MultiImportScript,
+    /// Script parsed into segments; an empty error means the dialog was cancelled.
+    MultiScriptLoaded(Result<Vec<crate::views::multispeaker_tab::SegmentState>, String>),
     /// Reference audio selected for segment at index.
     MultiRefSelected(usize, String),
     /// Text changed for segment at index.
@@ -77,28 +134,50 @@ pub enum Message {
     MultiGenerate,
 
     // ─── Task lifecycle ─────────────────────────────────────────
-    /// Generation task created, received `task_id`.
-    TaskCreated(Result<String, String>),
+    /// Generation task created from a tab, received `task_id`, the server's
+    /// estimated seconds and the submit latency. Carries the queueable
+    /// request that was sent, if any.
+    TaskCreated(
+        TabId,
+        Option<TaskRequest>,
+        Result<(String, Option<f64>, std::time::Duration), String>,
+    ),
     /// Task status poll result.
-    TaskProgress(Result<TaskStatusResponse, String>),
+    TaskProgress(String, Result<TaskStatusResponse, String>),
+    /// Cancel button pressed on the task with the given id.
+    TaskCancel(String),
+    /// Cancel request finished for the given task id.
+    TaskCancelled(Result<String, String>),
     /// Task polling tick (every 1 second during generation).
     TaskPollTick,
-    /// Audio data fetched for completed task.
-    TaskAudioLoaded(Result<Vec<u8>, String>),
+    /// Audio data fetched for the completed task with the given id.
+    TaskAudioLoaded(String, Result<Vec<u8>, String>),
+    /// Resume the generation queue restored from the last session.
+    QueueResume,
+    /// Discard the generation queue restored from the last session.
+    QueueDiscard,
 
     // ─── Playback ───────────────────────────────────────────────
-    /// Play generated audio (from active task).
+    /// Play generated audio (from the active tab's task).
     PlayGenerated,
     /// Play reference audio preview.
     PlayReference(String),
-    /// Reference audio bytes fetched for preview.
-    ReferenceAudioFetched(Result<Vec<u8>, String>),
+    /// Play back the microphone take selected on the Upload tab.
+    PlayRecording,
+    /// Reference audio bytes fetched for preview (id, bytes).
+    ReferenceAudioFetched(Result<(String, Vec<u8>), String>),
+    /// Set how far into a reference (by id) its preview starts.
+    ReferencePreviewOffset(String, std::time::Duration),
     /// Pause playback.
     PlaybackPause,
     /// Resume playback.
     PlaybackResume,
     /// Stop playback.
     PlaybackStop,
+    /// Seek to a fraction (`0.0..=1.0`) of the current clip.
+    PlaybackSeek(f32),
+    /// Turn looping playback on or off.
+    PlaybackToggleLoop,
     /// Playback state poll tick (detects when audio finishes).
     PlaybackTick,
 
@@ -107,12 +186,18 @@ pub enum Message {
     UploadPickFile,
     /// File selected from dialog (path, bytes, filename).
     UploadFileSelected(std::path::PathBuf, Vec<u8>, String),
-    /// Text input changed on upload tab.
-    UploadTextChanged(String),
+    /// Edit in the upload tab's text editor.
+    UploadTextAction(iced::widget::text_editor::Action),
     /// Language selected on upload tab.
     UploadLanguageSelected(String),
     /// Generate button pressed on upload tab.
     UploadGenerate,
+    /// Reset the upload tab and drop its audio, keeping its language.
+    UploadClear,
+    /// Upload the selected file and its transcription as a reference.
+    UploadSaveReference,
+    /// Reference upload finished.
+    ReferenceUploaded(Result<ReferenceAudio, String>),
 
     // ─── Recording ────────────────────────────────────────────────
     /// Start microphone recording.
@@ -121,28 +206,106 @@ pub enum Message {
     RecordStop,
     /// Recording tick (update elapsed time display).
     RecordTick,
+    /// Input device chosen for recording.
+    RecordDeviceSelected(String),
 
     // ─── Transcription ────────────────────────────────────────────
+    /// Download the selected Whisper model ahead of first use.
+    DownloadWhisperModel,
     /// Whisper model download progress (downloaded, total).
     ModelDownloadProgress(u64, u64),
     /// Model download finished.
     ModelDownloaded(Result<std::path::PathBuf, String>),
-    /// Transcription result for uploaded audio.
-    TranscriptionDone(Result<String, String>),
+    /// Transcription progress, in percent.
+    TranscriptionProgress(u8),
+    /// Transcription of the uploaded audio with this hash finished; `None` if it was cancelled.
+    TranscriptionDone(String, Option<Result<String, String>>),
+    /// Transcribe the selected upload again after a failure.
+    RetryTranscription,
+    /// Stop the running transcription, leaving the transcript empty.
+    CancelTranscription,
+    /// The upload's transcription was edited.
+    UploadRefTextChanged(String),
+    /// An edited transcription was written to the cache.
+    TranscriptionCacheSaved(Result<(), String>),
+
+    // ─── Keyboard ───────────────────────────────────────────────
+    /// Ctrl+Enter (Cmd+Enter on macOS): generate on the active tab.
+    GenerateShortcut,
+    /// Escape: stop playback, cancel the active tab's running task and
+    /// dismiss the error banner.
+    EscapePressed,
+    /// The window is being closed: finish pending work, then close it.
+    WindowCloseRequested(iced::window::Id),
+
+    // ─── History ────────────────────────────────────────────────
+    /// Repopulate the originating tab from a history entry (by index).
+    HistoryRerun(usize),
 
     // ─── Generated list ─────────────────────────────────────────
     /// Generated audio list fetched.
-    GeneratedListLoaded(Result<Vec<GeneratedAudio>, String>),
+    GeneratedListLoaded(Result<Vec<GeneratedAudio>, ApiError>),
     /// Refresh the generated audio list.
     RefreshGeneratedList,
     /// Play a generated audio item by ID.
     GeneratedPlay(String),
-    /// Audio bytes fetched for a generated item.
-    GeneratedAudioFetched(Result<Vec<u8>, String>),
-    /// Delete a generated audio item by ID.
+    /// Add or remove a generated item from the selection by ID.
+    GeneratedToggleSelect(String),
+    /// Select every item the filter shows, or clear them if all already are.
+    GeneratedSelectAllVisible,
+    /// Ask to delete every selected generated item.
+    GeneratedDeleteSelected,
+    /// Reorder the generated list.
+    GeneratedSortChanged(crate::views::generated_list::SortMode),
+    /// The generated list's filter query changed.
+    GeneratedFilterChanged(String),
+    /// Show or hide the full text of a generated item by ID.
+    GeneratedToggleExpand(String),
+    /// Audio bytes fetched for a generated item (id, bytes).
+    GeneratedAudioFetched(Result<(String, Vec<u8>), String>),
+    /// Generate a fresh take of a generated item's text and reference, by ID.
+    GeneratedRegenerate(String),
+    /// Ask to delete a generated audio item by ID.
     GeneratedDelete(String),
+    /// Go ahead with the deletion awaiting confirmation.
+    DeleteConfirm,
+    /// Drop the deletion awaiting confirmation.
+    DeleteCancel,
+    /// The undo window for a deletion ran out; delete the item on the server
+    /// unless that deletion was undone or superseded. Carries the deletion's
+    /// sequence number.
+    GeneratedDeleteCommit(u64),
+    /// Bring back the most recently deleted item before it leaves the server.
+    GeneratedUndoDelete,
     /// Deletion result.
     GeneratedDeleted(Result<String, String>),
+    /// Save a generated audio item to disk by ID.
+    GeneratedExport(String),
+    /// Save every generated audio item into a folder.
+    GeneratedExportAll,
+    /// "Include reference audio" export option toggled.
+    ExportIncludeReferenceToggled(bool),
+    /// "Normalize" export option toggled.
+    ExportNormalizeToggled(bool),
+    /// Peak level (dB) for normalized exports changed.
+    ExportPeakChanged(f32),
+    /// Export finished with the written paths (empty if the dialog was cancelled).
+    ExportDone(Result<Vec<std::path::PathBuf>, String>),
+    /// Export-all finished with the written paths and an error for each clip
+    /// that failed (both empty if the dialog was cancelled).
+    ExportAllDone(Vec<std::path::PathBuf>, Vec<String>),
+
+    // ─── Presets ──────────────────────────────────────────────────
+    /// Pick a `.qvoxpreset` file and apply it.
+    PresetImport,
+    /// Preset file parsed.
+    PresetLoaded(Result<crate::preset::Preset, String>),
+    /// Save the given tab's fields as a preset.
+    PresetExport(TabId),
+    /// Preset written to disk.
+    PresetSaved(Result<(), String>),
+    /// Embedded reference from a clone preset uploaded.
+    PresetReferenceUploaded(Result<ReferenceAudio, String>),
 
     // ─── Settings ─────────────────────────────────────────────────
     /// Model checkbox toggled.
@@ -151,20 +314,82 @@ pub enum Message {
     SettingsDeviceChanged(String),
     /// Port field changed.
     SettingsPortChanged(String),
+    /// "Use a server already running on this port" toggled.
+    SettingsUseExistingServerToggled(bool),
     /// Script path field changed.
     SettingsScriptPathChanged(String),
+    /// Python interpreter field changed (empty means use uv).
+    SettingsPythonPathChanged(String),
     /// Dark mode toggled.
     SettingsDarkModeToggled(bool),
+    /// Consistent loudness playback toggled.
+    SettingsConsistentLoudnessToggled(bool),
+    /// Normalize-on-preview for reference audio toggled.
+    SettingsNormalizePreviewToggled(bool),
+    /// Convert-uploads-to-WAV toggled.
+    SettingsConvertUploadsToggled(bool),
+    /// Enter-to-generate toggled.
+    SettingsEnterToGenerateToggled(bool),
+    /// Stop-playback-on-tab-switch toggled.
+    SettingsStopOnTabSwitchToggled(bool),
+    /// Auto-normalize-recordings toggled.
+    SettingsAutoNormalizeToggled(bool),
+    /// Auto-trim-recording-silence toggled.
+    SettingsAutoTrimSilenceToggled(bool),
+    /// Recording silence threshold changed, in dB.
+    SettingsSilenceThresholdChanged(f32),
+    /// Trim-long-references toggled.
+    SettingsTrimLongReferencesToggled(bool),
+    /// Longest untrimmed reference, in seconds, changed.
+    SettingsMaxReferenceSecsChanged(f32),
+    /// A slider backed by the config was released; save the value it was
+    /// dragged to.
+    ConfigSliderReleased,
+    /// Whisper model size selected.
+    SettingsWhisperModelSelected(crate::transcribe::whisper::WhisperModel),
+    /// Audio output device selected.
+    SettingsOutputDeviceSelected(String),
+    /// Model size selected.
+    SettingsModelSizeChanged(String),
     /// Save settings and restart server.
     SettingsSave,
+    /// Replace the unsaved settings with the defaults.
+    SettingsReset,
+    /// Delete all cached Whisper transcriptions.
+    ClearTranscriptionCache,
+    /// Ask to delete the downloaded Whisper models.
+    DeleteModels,
+    /// Delete the downloaded Whisper models after confirmation.
+    DeleteModelsConfirm,
+    /// Keep the downloaded Whisper models.
+    DeleteModelsCancel,
+    /// Leave the loading screen and open the Settings tab.
+    OpenSettings,
+    /// Open the system file manager at the given path.
+    RevealPath(std::path::PathBuf),
+    /// Copy text to the system clipboard.
+    CopyToClipboard(String),
+    /// Run the diagnostic self-test.
+    DiagnosticsRun,
+    /// Diagnostic results are in.
+    DiagnosticsDone(Vec<crate::diagnostics::DiagnosticResult>),
+    /// The background device probe finished; `None` if it couldn't run.
+    DeviceDetected(Option<&'static str>),
+    /// The background GPU count probe finished with the device choices.
+    DevicesDetected(Vec<String>),
 
     // ─── Error ────────────────────────────────────────────────────
-    /// Dismiss the error banner.
-    ErrorDismiss,
+    /// Dismiss one error banner by id.
+    ErrorDismiss(u64),
+    /// Re-send the request that raised an error, by id.
+    ErrorRetry(u64),
+    /// Drop errors that have been shown long enough.
+    ErrorExpireTick,
 }
 
 /// Tab identifiers.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TabId {
     Clone,
     Upload,
@@ -174,28 +399,204 @@ pub enum TabId {
     Settings,
 }
 
+/// A queueable generation request.
+///
+/// Upload-based clones carry raw audio bytes and are not represented here.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TaskRequest {
+    Clone(CloneRequest),
+    VoiceDesign(VoiceDesignRequest),
+    CustomVoice(CustomVoiceRequest),
+    MultiSpeaker(MultiSpeakerRequest),
+}
+
+impl TaskRequest {
+    /// Reference audio ids this request depends on.
+    pub fn ref_audio_ids(&self) -> Vec<&str> {
+        match self {
+            Self::Clone(r) => vec![r.ref_audio_id.as_str()],
+            Self::MultiSpeaker(r) => r.segments.iter().map(|s| s.ref_audio_id.as_str()).collect(),
+            Self::VoiceDesign(_) | Self::CustomVoice(_) => Vec::new(),
+        }
+    }
+
+    /// Tab this kind of request is made from.
+    pub fn tab(&self) -> TabId {
+        match self {
+            Self::Clone(_) => TabId::Clone,
+            Self::VoiceDesign(_) => TabId::VoiceDesign,
+            Self::CustomVoice(_) => TabId::CustomVoice,
+            Self::MultiSpeaker(_) => TabId::MultiSpeaker,
+        }
+    }
+}
+
+/// One-line description of the settings that produced a request, naming
+/// references from `references` where they are still known.
+pub fn summarize_request(req: &TaskRequest, references: &[ReferenceAudio]) -> String {
+    use crate::views::common::truncate_text;
+
+    match req {
+        TaskRequest::Clone(r) => {
+            let ref_name = references
+                .iter()
+                .find(|a| a.id == r.ref_audio_id)
+                .map_or_else(
+                    || r.ref_audio_id.chars().take(8).collect(),
+                    |a| a.display_name().to_owned(),
+                );
+            let mut summary = format!("Voice clone | ref: {ref_name} | lang: {}", r.language);
+            if r.ref_text.is_some() {
+                summary.push_str(" | with transcript");
+            }
+            summary
+        }
+        TaskRequest::VoiceDesign(r) => format!(
+            "Voice design | \"{}\" | lang: {}",
+            truncate_text(&r.instruct, 40),
+            r.language
+        ),
+        TaskRequest::CustomVoice(r) => {
+            let mut summary =
+                format!("Custom voice | speaker: {} | lang: {}", r.speaker, r.language);
+            if let Some(instruct) = r.instruct.as_deref().filter(|i| !i.is_empty()) {
+                summary.push_str(&format!(" | style: \"{}\"", truncate_text(instruct, 40)));
+            }
+            summary
+        }
+        TaskRequest::MultiSpeaker(r) => {
+            let mut refs: Vec<&str> = r.segments.iter().map(|s| s.ref_audio_id.as_str()).collect();
+            refs.sort_unstable();
+            refs.dedup();
+            let mut langs: Vec<&str> = r.segments.iter().map(|s| s.language.as_str()).collect();
+            langs.sort_unstable();
+            langs.dedup();
+            format!(
+                "Multi-speaker | {} segment(s) | {} reference(s) | lang: {}",
+                r.segments.len(),
+                refs.len(),
+                langs.join(", ")
+            )
+        }
+    }
+}
+
+/// A deletion waiting for the user to confirm it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingDelete {
+    /// One generated clip, by id.
+    Generated(String),
+    /// Every checked generated clip.
+    SelectedGenerated,
+    /// A reference, by id.
+    Reference(String),
+}
+
+/// A blocking operation that disables conflicting actions while it runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusyReason {
+    Transcribing,
+    DownloadingModel,
+    Exporting,
+}
+
+impl BusyReason {
+    /// Short label for the "Working: X" indicator.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Transcribing => "transcribing audio",
+            Self::DownloadingModel => "downloading Whisper model",
+            Self::Exporting => "exporting audio",
+        }
+    }
+}
+
+/// A user action that may conflict with a [`BusyReason`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserAction {
+    Generate,
+    Record,
+    PickFile,
+    Export,
+}
+
+/// Whether `action` may run while `busy` is in progress.
+pub fn is_action_allowed(busy: Option<BusyReason>, action: UserAction) -> bool {
+    match busy {
+        None => true,
+        Some(BusyReason::Transcribing | BusyReason::DownloadingModel) => !matches!(
+            action,
+            UserAction::Generate | UserAction::Record | UserAction::PickFile
+        ),
+        Some(BusyReason::Exporting) => action != UserAction::Export,
+    }
+}
+
+impl Message {
+    /// The user action this message triggers, if it is one that can be blocked.
+    pub fn user_action(&self) -> Option<UserAction> {
+        match self {
+            Self::CloneGenerate
+            | Self::UploadGenerate
+            | Self::DesignGenerate
+            | Self::CustomGenerate
+            | Self::MultiGenerate
+            | Self::GeneratedRegenerate(_) => Some(UserAction::Generate),
+            Self::RecordStart => Some(UserAction::Record),
+            Self::UploadPickFile | Self::ReferenceTranscribe(_) => Some(UserAction::PickFile),
+            Self::GeneratedExport(_) | Self::GeneratedExportAll => Some(UserAction::Export),
+            _ => None,
+        }
+    }
+}
+
+/// The Upload tab inputs a generation was submitted with, kept for its
+/// history entry since the tab may change while the task runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UploadSnapshot {
+    pub text: String,
+    pub language: String,
+    pub file_name: String,
+}
+
 /// State of an active generation task.
 #[derive(Debug, Clone)]
 pub struct ActiveTask {
     pub task_id: String,
+    /// Tab the task was started from; that tab shows its progress.
+    pub tab: TabId,
     pub status: TaskStatus,
     pub progress: u32,
     pub elapsed_secs: u64,
     pub status_text: String,
     pub error: Option<String>,
-    pub audio_data: Option<Vec<u8>>,
+    /// 1-based segment being generated (multi-speaker only).
+    pub current_segment: Option<u32>,
+    pub total_segments: Option<u32>,
+    /// The request that started this task, when it was queueable.
+    pub request: Option<TaskRequest>,
+    /// What an Upload tab task was submitted with.
+    pub upload: Option<UploadSnapshot>,
+    /// Server's estimate of the generation time, in seconds.
+    pub estimated_secs: Option<f64>,
 }
 
 impl ActiveTask {
-    pub fn new(task_id: String) -> Self {
+    pub fn new(task_id: String, tab: TabId) -> Self {
         Self {
             task_id,
+            tab,
             status: TaskStatus::Processing,
             progress: 0,
             elapsed_secs: 0,
             status_text: "Initializing voice cloner...".to_owned(),
             error: None,
-            audio_data: None,
+            current_segment: None,
+            total_segments: None,
+            request: None,
+            upload: None,
+            estimated_secs: None,
         }
     }
 
@@ -203,6 +604,8 @@ impl ActiveTask {
         self.status = resp.status;
         self.progress = resp.progress;
         self.status_text = progress_text(resp);
+        self.current_segment = resp.current_segment;
+        self.total_segments = resp.total_segments;
 
         if let Some(err) = &resp.error {
             self.error = Some(err.clone());
@@ -239,6 +642,25 @@ fn normal_progress_text(progress: u32) -> String {
     }
 }
 
+/// Progress of one multi-speaker segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentStatus {
+    Done,
+    Active,
+    Pending,
+}
+
+/// Status of each segment given the 1-based `current` segment out of `total`.
+pub fn segment_statuses(current: u32, total: u32) -> Vec<SegmentStatus> {
+    (1..=total)
+        .map(|n| match n.cmp(&current) {
+            std::cmp::Ordering::Less => SegmentStatus::Done,
+            std::cmp::Ordering::Equal => SegmentStatus::Active,
+            std::cmp::Ordering::Greater => SegmentStatus::Pending,
+        })
+        .collect()
+}
+
 fn multi_speaker_progress_text(resp: &TaskStatusResponse) -> String {
     let current = resp.current_segment.unwrap_or(0);
     let total = resp.total_segments.unwrap_or(0);
@@ -255,14 +677,157 @@ fn multi_speaker_progress_text(resp: &TaskStatusResponse) -> String {
 mod tests {
     use super::*;
 
+    fn segment(ref_id: &str, language: &str) -> crate::api::types::MultiSpeakerSegment {
+        crate::api::types::MultiSpeakerSegment {
+            text: "Hi".to_owned(),
+            ref_audio_id: ref_id.to_owned(),
+            ref_text: None,
+            language: language.to_owned(),
+        }
+    }
+
+    #[test]
+    fn summarize_clone_request() {
+        let req = TaskRequest::Clone(CloneRequest {
+            text: "Hello".to_owned(),
+            ref_audio_id: "0123456789abcdef".to_owned(),
+            ref_text: Some("transcript".to_owned()),
+            language: "en".to_owned(),
+            batch_line: None,
+        });
+        assert_eq!(
+            summarize_request(&req, &[]),
+            "Voice clone | ref: 01234567 | lang: en | with transcript"
+        );
+        let references = [crate::test_fixtures::reference("0123456789abcdef", "alice.wav")];
+        assert_eq!(
+            summarize_request(&req, &references),
+            "Voice clone | ref: alice.wav | lang: en | with transcript"
+        );
+    }
+
+    #[test]
+    fn summarize_design_request() {
+        let req = TaskRequest::VoiceDesign(VoiceDesignRequest {
+            text: "Hello".to_owned(),
+            instruct: "A warm voice".to_owned(),
+            language: "auto".to_owned(),
+        });
+        assert_eq!(summarize_request(&req, &[]), "Voice design | \"A warm voice\" | lang: auto");
+    }
+
+    #[test]
+    fn summarize_custom_request() {
+        let mut custom = CustomVoiceRequest {
+            text: "Hello".to_owned(),
+            speaker: "Vivian".to_owned(),
+            language: "ja".to_owned(),
+            instruct: None,
+        };
+        assert_eq!(
+            summarize_request(&TaskRequest::CustomVoice(custom.clone()), &[]),
+            "Custom voice | speaker: Vivian | lang: ja"
+        );
+        custom.instruct = Some("Slowly".to_owned());
+        assert_eq!(
+            summarize_request(&TaskRequest::CustomVoice(custom), &[]),
+            "Custom voice | speaker: Vivian | lang: ja | style: \"Slowly\""
+        );
+    }
+
+    #[test]
+    fn summarize_multi_speaker_request() {
+        let req = TaskRequest::MultiSpeaker(MultiSpeakerRequest {
+            segments: vec![segment("a", "en"), segment("b", "ja"), segment("a", "en")],
+        });
+        assert_eq!(
+            summarize_request(&req, &[]),
+            "Multi-speaker | 3 segment(s) | 2 reference(s) | lang: en, ja"
+        );
+    }
+
+    #[test]
+    fn segment_statuses_first_segment() {
+        assert_eq!(
+            segment_statuses(1, 3),
+            vec![
+                SegmentStatus::Active,
+                SegmentStatus::Pending,
+                SegmentStatus::Pending
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_statuses_middle_segment() {
+        assert_eq!(
+            segment_statuses(2, 3),
+            vec![
+                SegmentStatus::Done,
+                SegmentStatus::Active,
+                SegmentStatus::Pending
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_statuses_last_segment() {
+        assert_eq!(
+            segment_statuses(3, 3),
+            vec![SegmentStatus::Done, SegmentStatus::Done, SegmentStatus::Active]
+        );
+    }
+
+    #[test]
+    fn segment_statuses_zero_total() {
+        assert!(segment_statuses(0, 0).is_empty());
+        assert!(segment_statuses(2, 0).is_empty());
+    }
+
+    #[test]
+    fn idle_allows_every_action() {
+        for action in [
+            UserAction::Generate,
+            UserAction::Record,
+            UserAction::PickFile,
+            UserAction::Export,
+        ] {
+            assert!(is_action_allowed(None, action));
+        }
+    }
+
+    #[test]
+    fn transcription_blocks_conflicting_actions() {
+        for busy in [BusyReason::Transcribing, BusyReason::DownloadingModel] {
+            assert!(!is_action_allowed(Some(busy), UserAction::Generate));
+            assert!(!is_action_allowed(Some(busy), UserAction::Record));
+            assert!(!is_action_allowed(Some(busy), UserAction::PickFile));
+            assert!(is_action_allowed(Some(busy), UserAction::Export));
+        }
+    }
+
+    #[test]
+    fn export_blocks_only_another_export() {
+        let busy = Some(BusyReason::Exporting);
+        assert!(!is_action_allowed(busy, UserAction::Export));
+        assert!(is_action_allowed(busy, UserAction::Generate));
+        assert!(is_action_allowed(busy, UserAction::Record));
+    }
+
+    #[test]
+    fn generate_messages_map_to_generate_action() {
+        assert_eq!(Message::CloneGenerate.user_action(), Some(UserAction::Generate));
+        assert_eq!(Message::RecordStart.user_action(), Some(UserAction::Record));
+        assert_eq!(Message::PlaybackStop.user_action(), None);
+    }
+
     #[test]
     fn active_task_new() {
-        let task = ActiveTask::new("task-1".to_owned());
+        let task = ActiveTask::new("task-1".to_owned(), TabId::Clone);
         assert_eq!(task.task_id, "task-1");
         assert_eq!(task.status, TaskStatus::Processing);
         assert_eq!(task.progress, 0);
         assert!(task.error.is_none());
-        assert!(task.audio_data.is_none());
     }
 
     #[test]
@@ -327,7 +892,7 @@ mod tests {
 
     #[test]
     fn active_task_update_progress() {
-        let mut task = ActiveTask::new("t1".to_owned());
+        let mut task = ActiveTask::new("t1".to_owned(), TabId::MultiSpeaker);
         let resp = TaskStatusResponse {
             status: TaskStatus::Processing,
             progress: 60,
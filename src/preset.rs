@@ -0,0 +1,155 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+/// File extension for shareable voice presets.
+pub const PRESET_EXTENSION: &str = "qvoxpreset";
+
+const PRESET_VERSION: u32 = 1;
+
+/// A shareable voice setup, stored as JSON in a `.qvoxpreset` file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub version: u32,
+    pub name: String,
+    pub language: String,
+    pub voice: PresetVoice,
+}
+
+/// Tab-specific settings carried by a preset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PresetVoice {
+    VoiceDesign {
+        instruct: String,
+    },
+    CustomVoice {
+        speaker: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        instruct: Option<String>,
+    },
+    Clone {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ref_text: Option<String>,
+        /// Reference clip embedded as base64 WAV bytes, uploaded on import.
+        #[serde(default, skip_serializing_if = "Option::is_none", with = "base64_bytes")]
+        reference_wav: Option<Vec<u8>>,
+    },
+}
+
+/// Serde adapter writing optional bytes as a base64 string rather than a
+/// JSON array of numbers.
+mod base64_bytes {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    // Serde's `with` hands fields over by reference.
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(
+        bytes: &Option<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match bytes {
+            Some(bytes) => serializer.serialize_some(&STANDARD.encode(bytes)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|encoded| STANDARD.decode(encoded).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+impl Preset {
+    pub fn new(name: String, language: String, voice: PresetVoice) -> Self {
+        Self {
+            version: PRESET_VERSION,
+            name,
+            language,
+            voice,
+        }
+    }
+}
+
+/// Parse a `.qvoxpreset` file.
+pub fn load_preset(bytes: &[u8]) -> Result<Preset> {
+    let preset: Preset = serde_json::from_slice(bytes).context("invalid preset file")?;
+    if preset.version > PRESET_VERSION {
+        bail!(
+            "preset version {} is newer than supported version {PRESET_VERSION}",
+            preset.version
+        );
+    }
+    Ok(preset)
+}
+
+/// Serialize a preset for saving to disk.
+pub fn save_preset(preset: &Preset) -> Vec<u8> {
+    // Serializing plain strings and bytes cannot fail.
+    serde_json::to_vec_pretty(preset).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+
+    #[test]
+    fn design_preset_round_trip() {
+        let preset = Preset::new(
+            "Narrator".to_owned(),
+            "en".to_owned(),
+            PresetVoice::VoiceDesign {
+                instruct: "A calm, deep narrator voice".to_owned(),
+            },
+        );
+        let bytes = save_preset(&preset);
+        assert_eq!(load_preset(&bytes).expect("load"), preset);
+    }
+
+    #[test]
+    fn clone_preset_round_trip_with_embedded_clip() {
+        let wav = crate::audio::recorder::samples_to_wav(&[0.0, 0.25, -0.25], 16_000)
+            .expect("encode");
+        let preset = Preset::new(
+            "My voice".to_owned(),
+            "ja".to_owned(),
+            PresetVoice::Clone {
+                ref_text: Some("こんにちは".to_owned()),
+                reference_wav: Some(wav.clone()),
+            },
+        );
+        let bytes = save_preset(&preset);
+        let json = String::from_utf8(bytes.clone()).expect("utf-8");
+        assert!(json.contains(&base64::engine::general_purpose::STANDARD.encode(&wav)));
+        let loaded = load_preset(&bytes).expect("load");
+        assert_eq!(loaded, preset);
+        assert!(matches!(
+            loaded.voice,
+            PresetVoice::Clone { reference_wav: Some(clip), .. } if clip == wav
+        ));
+    }
+
+    #[test]
+    fn load_preset_rejects_newer_version() {
+        let mut preset = Preset::new(
+            "Future".to_owned(),
+            "auto".to_owned(),
+            PresetVoice::CustomVoice {
+                speaker: "Vivian".to_owned(),
+                instruct: None,
+            },
+        );
+        preset.version = PRESET_VERSION + 1;
+        assert!(load_preset(&save_preset(&preset)).is_err());
+    }
+
+    #[test]
+    fn load_preset_rejects_garbage() {
+        assert!(load_preset(b"not json").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::api::types::ReferenceAudio;
+use crate::message::TaskRequest;
+
+/// Return the path to `queue.json` in `data_dir`.
+pub fn queue_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("queue.json")
+}
+
+/// Persist the pending generation queue.
+pub fn save_queue(data_dir: &Path, queue: &[TaskRequest]) -> Result<()> {
+    write_queue(&queue_path(data_dir), queue)
+}
+
+/// Load the persisted queue, returning an empty queue if missing or unreadable.
+pub fn load_queue(data_dir: &Path) -> Vec<TaskRequest> {
+    read_queue(&queue_path(data_dir))
+}
+
+/// Remove the persisted queue file.
+pub fn clear_queue(data_dir: &Path) -> Result<()> {
+    let path = queue_path(data_dir);
+    if path.exists() {
+        std::fs::remove_file(&path).context("failed to remove queue file")?;
+    }
+    Ok(())
+}
+
+/// Split off queued requests that reference audio no longer on the server.
+///
+/// Returns the requests to keep and the number skipped.
+pub fn drop_stale(
+    queue: Vec<TaskRequest>,
+    references: &[ReferenceAudio],
+) -> (Vec<TaskRequest>, usize) {
+    let total = queue.len();
+    let kept: Vec<TaskRequest> = queue
+        .into_iter()
+        .filter(|req| {
+            req.ref_audio_ids()
+                .iter()
+                .all(|id| references.iter().any(|r| r.id == *id))
+        })
+        .collect();
+    let skipped = total - kept.len();
+    (kept, skipped)
+}
+
+fn write_queue(path: &Path, queue: &[TaskRequest]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("failed to create queue directory")?;
+    }
+    let contents = serde_json::to_string_pretty(queue).context("failed to serialize queue")?;
+    std::fs::write(path, contents).context("failed to write queue file")?;
+    Ok(())
+}
+
+fn read_queue(path: &Path) -> Vec<TaskRequest> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::{
+        CloneRequest, MultiSpeakerRequest, MultiSpeakerSegment, VoiceDesignRequest,
+    };
+    use crate::test_fixtures::reference;
+
+    fn clone_req(ref_id: &str) -> TaskRequest {
+        TaskRequest::Clone(CloneRequest {
+            text: "Hello".to_owned(),
+            ref_audio_id: ref_id.to_owned(),
+            ref_text: None,
+            language: "auto".to_owned(),
+            batch_line: None,
+        })
+    }
+
+    #[test]
+    fn queue_round_trip() {
+        let dir = std::env::temp_dir().join("qvox_test_queue");
+        let path = dir.join("queue.json");
+        let queue = vec![
+            clone_req("ref-1"),
+            TaskRequest::VoiceDesign(VoiceDesignRequest {
+                text: "Hi".to_owned(),
+                instruct: "A calm voice".to_owned(),
+                language: "English".to_owned(),
+            }),
+        ];
+
+        write_queue(&path, &queue).expect("write");
+        assert_eq!(read_queue(&path), queue);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn read_missing_queue_is_empty() {
+        assert!(read_queue(Path::new("/nonexistent/qvox/queue.json")).is_empty());
+    }
+
+    #[test]
+    fn drop_stale_skips_deleted_references() {
+        let multi = TaskRequest::MultiSpeaker(MultiSpeakerRequest {
+            segments: vec![
+                MultiSpeakerSegment {
+                    text: "A".to_owned(),
+                    ref_audio_id: "ref-1".to_owned(),
+                    ref_text: None,
+                    language: "auto".to_owned(),
+                },
+                MultiSpeakerSegment {
+                    text: "B".to_owned(),
+                    ref_audio_id: "gone".to_owned(),
+                    ref_text: None,
+                    language: "auto".to_owned(),
+                },
+            ],
+        });
+        let queue = vec![clone_req("ref-1"), clone_req("gone"), multi];
+
+        let (kept, skipped) = drop_stale(queue, &[reference("ref-1", "sample.wav")]);
+        assert_eq!(kept, vec![clone_req("ref-1")]);
+        assert_eq!(skipped, 2);
+    }
+
+    #[test]
+    fn drop_stale_keeps_requests_without_references() {
+        let design = TaskRequest::VoiceDesign(VoiceDesignRequest {
+            text: "Hi".to_owned(),
+            instruct: "Bright".to_owned(),
+            language: "auto".to_owned(),
+        });
+        let (kept, skipped) = drop_stale(vec![design.clone()], &[]);
+        assert_eq!(kept, vec![design]);
+        assert_eq!(skipped, 0);
+    }
+}
@@ -1,11 +1,19 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::net::TcpListener;
+use std::path::Path;
 use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[cfg(unix)]
 use std::os::unix::process::CommandExt;
 
 use anyhow::{Context, Result, bail};
 
-use crate::api::client::ApiClient;
+use crate::api::client::{ApiClient, RetryPolicy};
+use crate::api::types::HealthResponse;
 
 /// Configuration for spawning the Python TTS server.
 #[derive(Debug, Clone)]
@@ -13,6 +21,7 @@ pub struct ServerConfig {
     pub models: Vec<String>,
     pub device: String,
     pub port: u16,
+    pub bind_host: String,
     pub python_path: Option<String>,
     pub script_path: String,
     pub model_size: String,
@@ -24,6 +33,7 @@ impl Default for ServerConfig {
             models: vec!["base".to_owned()],
             device: "auto".to_owned(),
             port: 8000,
+            bind_host: "127.0.0.1".to_owned(),
             python_path: None,
             script_path: "python/start_server.py".to_owned(),
             model_size: "1.7B".to_owned(),
@@ -31,17 +41,29 @@ impl Default for ServerConfig {
     }
 }
 
+/// How many lines of server output `ServerManager` keeps.
+const MAX_LOG_LINES: usize = 500;
+
+/// Shared buffer of the most recent server output lines.
+type LogBuffer = Arc<Mutex<VecDeque<String>>>;
+
 /// Manages the lifecycle of the Python TTS backend process.
 pub struct ServerManager {
     child: Option<Child>,
+    host: String,
     port: u16,
+    logs: LogBuffer,
+    /// Connected to a server qvox didn't start, which it leaves running.
+    attached: bool,
 }
 
 impl std::fmt::Debug for ServerManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ServerManager")
+            .field("host", &self.host)
             .field("port", &self.port)
             .field("has_child", &self.child.is_some())
+            .field("attached", &self.attached)
             .finish()
     }
 }
@@ -50,14 +72,23 @@ impl ServerManager {
     /// Spawn the Python server with the given configuration.
     ///
     /// Tries ports from `config.port` to `config.port + 99` until one succeeds.
+    /// Runs the script with `config.python_path` when set, otherwise via uv.
     pub fn spawn(config: &ServerConfig) -> Result<Self> {
-        let port = config.port;
+        if let Err(e) = check_script_path(&config.script_path) {
+            bail!(e);
+        }
+        let Some(port) = find_free_port(&config.bind_host, config.port, PORT_ATTEMPTS) else {
+            bail!(
+                "no free port in {}..={} on {}",
+                config.port,
+                config.port.saturating_add(PORT_ATTEMPTS - 1),
+                config.bind_host
+            );
+        };
 
-        let mut cmd = Command::new("uv");
-        cmd.arg("run")
-            .arg("--project")
-            .arg("python")
-            .arg(&config.script_path)
+        let mut cmd = launch_command(config)?;
+        cmd.arg("--host")
+            .arg(&config.bind_host)
             .arg("--port")
             .arg(port.to_string())
             .arg("--models")
@@ -66,26 +97,87 @@ impl ServerManager {
             .arg(&config.device)
             .arg("--model-size")
             .arg(&config.model_size)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit());
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
 
         // Create a new process group so we can kill uv + uvicorn together.
         #[cfg(unix)]
         cmd.process_group(0);
 
-        let child = cmd
-            .spawn()
-            .with_context(|| "failed to spawn Python server via uv".to_owned())?;
+        let mut child = cmd.spawn().with_context(|| {
+            format!(
+                "failed to spawn Python server via {}",
+                cmd.get_program().to_string_lossy()
+            )
+        })?;
+
+        let logs = LogBuffer::default();
+        if let Some(stdout) = child.stdout.take() {
+            spawn_log_reader(stdout, Arc::clone(&logs));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_log_reader(stderr, Arc::clone(&logs));
+        }
 
         Ok(Self {
             child: Some(child),
+            host: config.bind_host.clone(),
             port,
+            logs,
+            attached: false,
         })
     }
 
+    /// Use a server that is already running on `host:port`, e.g. one started
+    /// by hand for debugging. It has no process or logs here and is left
+    /// running when this manager is killed or dropped.
+    pub fn attach(host: &str, port: u16) -> Self {
+        Self {
+            child: None,
+            host: host.to_owned(),
+            port,
+            logs: LogBuffer::default(),
+            attached: true,
+        }
+    }
+
+    /// Whether this manager adopted a server instead of spawning one.
+    pub fn is_attached(&self) -> bool {
+        self.attached
+    }
+
+    /// The most recent lines the server wrote to stdout/stderr, oldest first.
+    pub fn recent_logs(&self) -> Vec<String> {
+        self.logs
+            .lock()
+            .map(|logs| logs.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
     /// Returns the base URL the server is listening on.
     pub fn base_url(&self) -> String {
-        format!("http://localhost:{}", self.port)
+        base_url_for(&self.host, self.port)
+    }
+
+    /// Returns the port the server was started on.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Host candidates for health polling: the configured host first, then
+    /// the other loopback family.
+    pub fn health_hosts(&self) -> Vec<String> {
+        loopback_candidates(&self.host)
+    }
+
+    /// Host the API is currently reached on.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Switch to a host that answered health polling.
+    pub fn set_host(&mut self, host: String) {
+        self.host = host;
     }
 
     /// Returns an API client configured for this server.
@@ -93,11 +185,15 @@ impl ServerManager {
         ApiClient::new(&self.base_url())
     }
 
-    /// Check if the server process is still running.
+    /// Check if the server process is still running, reaping it once it has
+    /// exited so it doesn't linger as a zombie. An attached server has no
+    /// process here and always reports `true`; probe its health instead.
     pub fn is_running(&mut self) -> bool {
-        self.child
-            .as_mut()
-            .is_some_and(|c| c.try_wait().ok().flatten().is_none())
+        self.attached
+            || self
+                .child
+                .as_mut()
+                .is_some_and(|c| c.try_wait().ok().flatten().is_none())
     }
 
     /// Attempt a single health check. Returns `true` if the server is ready
@@ -110,32 +206,199 @@ impl ServerManager {
         }
     }
 
-    /// Kill the server process and all its children (process group).
+    /// Stop the server process and all its children (process group),
+    /// waiting up to `SHUTDOWN_TIMEOUT` for it to exit.
     pub fn kill(&mut self) {
-        if let Some(mut child) = self.child.take() {
+        self.kill_timeout(SHUTDOWN_TIMEOUT);
+    }
+
+    /// Ask the server to exit (SIGTERM to its process group on Unix, so it
+    /// can free GPU memory), wait up to `timeout`, then force-kill it.
+    pub fn kill_timeout(&mut self, timeout: Duration) {
+        let Some(mut child) = self.child.take() else {
+            return;
+        };
+        #[cfg(unix)]
+        signal_group(&child, libc::SIGTERM);
+        #[cfg(not(unix))]
+        let _ = child.kill();
+
+        if !wait_timeout(&mut child, timeout) {
             #[cfg(unix)]
-            {
-                // Send SIGTERM to the entire process group (uv + uvicorn).
-                // SAFETY: kill with a negative PID targets the process group.
-                unsafe {
-                    libc::kill(-(child.id() as libc::pid_t), libc::SIGTERM);
-                }
-            }
-            #[cfg(not(unix))]
-            {
-                let _ = child.kill();
-            }
-            let _ = child.wait();
+            signal_group(&child, libc::SIGKILL);
+            let _ = child.kill();
         }
+        let _ = child.wait();
     }
 }
 
 impl Drop for ServerManager {
     fn drop(&mut self) {
-        self.kill();
+        self.kill_timeout(SHUTDOWN_TIMEOUT);
+    }
+}
+
+/// How long `kill` and `Drop` give the server to exit before force-killing.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Send `signal` to the process group led by `child` (uv + uvicorn).
+#[cfg(unix)]
+fn signal_group(child: &Child, signal: libc::c_int) {
+    // SAFETY: kill with a negative PID targets the process group.
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), signal);
     }
 }
 
+/// Poll `child` until it exits or `timeout` passes. Returns whether it
+/// exited.
+fn wait_timeout(child: &mut Child, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => return true,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(50)),
+            _ => return false,
+        }
+    }
+}
+
+/// Command that runs the server script, before its arguments: the configured
+/// interpreter when `python_path` is set, else `uv run` when uv is installed,
+/// else whichever Python is on PATH.
+fn launch_command(config: &ServerConfig) -> Result<Command> {
+    let python = config
+        .python_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+    let cmd = match python {
+        Some(python) => python_command(python, &config.script_path),
+        None if uv_available() => {
+            let mut cmd = Command::new("uv");
+            cmd.arg("run")
+                .arg("--project")
+                .arg("python")
+                .arg(&config.script_path);
+            cmd
+        }
+        None => python_command(&find_python()?, &config.script_path),
+    };
+    Ok(cmd)
+}
+
+fn python_command(python: &str, script_path: &str) -> Command {
+    let mut cmd = Command::new(python);
+    cmd.arg(script_path);
+    cmd
+}
+
+/// Whether `uv` runs on this machine.
+fn uv_available() -> bool {
+    Command::new("uv")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+/// Read `stream` line by line on a background thread into `logs`. The thread
+/// ends when the server closes the pipe.
+fn spawn_log_reader(stream: impl Read + Send + 'static, logs: LogBuffer) {
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            if let Ok(mut logs) = logs.lock() {
+                push_log_line(&mut logs, line);
+            }
+        }
+    });
+}
+
+/// Append `line`, dropping the oldest lines beyond `MAX_LOG_LINES`.
+fn push_log_line(logs: &mut VecDeque<String>, line: String) {
+    if logs.len() >= MAX_LOG_LINES {
+        logs.pop_front();
+    }
+    logs.push_back(line);
+}
+
+/// Verify the server script exists before handing it to `uv`, which would
+/// otherwise fail with a less obvious error.
+pub fn check_script_path(path: &str) -> Result<(), String> {
+    let script = Path::new(path);
+    if script.is_file() {
+        Ok(())
+    } else if script.exists() {
+        Err(format!("Server script path {path} is not a file"))
+    } else {
+        Err(format!("Server script not found at {path}"))
+    }
+}
+
+/// How many consecutive ports `spawn` tries, starting at the configured one.
+const PORT_ATTEMPTS: u16 = 100;
+
+/// First port in `start..start + attempts` that `host` can bind, if any.
+///
+/// The test listener is dropped before returning, so another process could
+/// still grab the port before the server binds it; this only skips ports
+/// that are already taken, such as one held by a stale server.
+pub fn find_free_port(host: &str, start: u16, attempts: u16) -> Option<u16> {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    (0..attempts)
+        .map_while(|offset| start.checked_add(offset))
+        .find(|&port| TcpListener::bind((host, port)).is_ok())
+}
+
+/// Build the HTTP base URL for `host:port`, bracketing IPv6 literals.
+pub fn base_url_for(host: &str, port: u16) -> String {
+    if host.contains(':') && !host.starts_with('[') {
+        format!("http://[{host}]:{port}")
+    } else {
+        format!("http://{host}:{port}")
+    }
+}
+
+/// Hosts to try when connecting to `host`.
+///
+/// `localhost` may resolve to `::1` while the backend listens on
+/// `127.0.0.1` (or vice versa), so loopback hosts also try the other family.
+pub fn loopback_candidates(host: &str) -> Vec<String> {
+    let fallback: &[&str] = match host {
+        "127.0.0.1" | "0.0.0.0" => &["::1"],
+        "::1" | "::" => &["127.0.0.1"],
+        "localhost" => &["127.0.0.1", "::1"],
+        _ => &[],
+    };
+    let primary = match host {
+        "0.0.0.0" => "127.0.0.1",
+        "::" => "::1",
+        h => h,
+    };
+    std::iter::once(primary)
+        .chain(fallback.iter().copied())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// How long a single health probe may take before the next host is tried.
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Health-check each host in order and return the first that answers,
+/// with its health response. Probes are not retried: the caller polls
+/// again on the next tick anyway.
+pub async fn probe_health(hosts: &[String], port: u16) -> Option<(String, HealthResponse)> {
+    for host in hosts {
+        let client = ApiClient::with_timeout(&base_url_for(host, port), HEALTH_PROBE_TIMEOUT)
+            .with_retry_policy(RetryPolicy::NONE);
+        if let Ok(resp) = client.health().await {
+            return Some((host.clone(), resp));
+        }
+    }
+    None
+}
+
 /// Detect a Python executable on PATH, preferring `python3` over `python`.
 pub fn find_python() -> Result<String> {
     for candidate in &["python3", "python"] {
@@ -152,14 +415,87 @@ pub fn find_python() -> Result<String> {
     bail!("Python not found. Install Python 3 and ensure python3 or python is on PATH.")
 }
 
+/// Probe the machine for the most capable inference device.
+///
+/// Advisory only: returns `cuda` when `nvidia-smi` runs successfully, `mps`
+/// on Apple Silicon, and `cpu` otherwise.
+pub fn detect_best_device() -> &'static str {
+    let has_nvidia = Command::new("nvidia-smi")
+        .arg("-L")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success());
+    if has_nvidia {
+        return "cuda";
+    }
+    if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        return "mps";
+    }
+    "cpu"
+}
+
+/// Device strings to offer in settings: the common choices, plus one
+/// `cuda:N` entry per GPU reported by `nvidia-smi` when there are several.
+pub fn detect_devices() -> Vec<String> {
+    let gpu_count = Command::new("nvidia-smi")
+        .arg("-L")
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map_or(0, |out| {
+            String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .filter(|line| line.starts_with("GPU "))
+                .count()
+        });
+    device_options(gpu_count)
+}
+
+/// Device choices for a machine with `gpu_count` NVIDIA GPUs; `0` gives the
+/// common choices shown before `detect_devices` has run.
+pub fn device_options(gpu_count: usize) -> Vec<String> {
+    let mut options: Vec<String> =
+        ["auto", "cpu", "cuda", "mps"].iter().map(|&d| d.to_owned()).collect();
+    if gpu_count > 1 {
+        options.extend((0..gpu_count).map(|i| format!("cuda:{i}")));
+    }
+    options
+}
+
+/// Compare the configured device against the detected one and return a
+/// warning when the configuration looks wrong for this machine.
+///
+/// `"auto"` never warns, and device indices (`cuda:1`) are compared by family.
+pub fn device_suggestion(detected: &str, configured: &str) -> Option<String> {
+    let configured = configured.trim();
+    let family = configured.split(':').next().unwrap_or(configured);
+    match family {
+        "" | "auto" => None,
+        f if f == detected => None,
+        "cuda" | "mps" => Some(format!(
+            "\"{configured}\" does not appear to be available on this machine \
+             (detected: {detected}). Consider \"auto\"."
+        )),
+        "cpu" => Some(format!(
+            "A {detected} device was detected; \"{detected}\" will be much faster than \"cpu\"."
+        )),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[test]
     fn server_config_default() {
         let config = ServerConfig::default();
         assert_eq!(config.port, 8000);
+        assert_eq!(config.bind_host, "127.0.0.1");
         assert_eq!(config.models, vec!["base"]);
         assert_eq!(config.device, "auto");
         assert!(config.python_path.is_none());
@@ -175,22 +511,276 @@ mod tests {
         assert!(python == "python3" || python == "python");
     }
 
+    #[test]
+    fn launch_command_uses_configured_python() {
+        let config = ServerConfig {
+            python_path: Some("/opt/venv/bin/python".to_owned()),
+            ..ServerConfig::default()
+        };
+        let cmd = launch_command(&config).expect("command");
+        assert_eq!(cmd.get_program(), "/opt/venv/bin/python");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, vec!["python/start_server.py"]);
+    }
+
+    #[test]
+    fn launch_command_ignores_blank_python_path() {
+        let config = ServerConfig {
+            python_path: Some("  ".to_owned()),
+            ..ServerConfig::default()
+        };
+        let cmd = launch_command(&config).expect("command");
+        assert_ne!(cmd.get_program(), "  ");
+        assert!(cmd.get_args().any(|a| a == "python/start_server.py"));
+    }
+
+    #[test]
+    fn detect_best_device_is_known() {
+        assert!(["cuda", "mps", "cpu"].contains(&detect_best_device()));
+    }
+
+    #[test]
+    fn device_options_lists_common_devices() {
+        assert_eq!(device_options(0), vec!["auto", "cpu", "cuda", "mps"]);
+        assert_eq!(device_options(1).len(), 4);
+    }
+
+    #[test]
+    fn device_options_adds_indices_for_several_gpus() {
+        let options = device_options(2);
+        assert!(options.ends_with(&["cuda:0".to_owned(), "cuda:1".to_owned()]));
+    }
+
+    #[test]
+    fn device_suggestion_matching_device() {
+        assert!(device_suggestion("cuda", "cuda").is_none());
+        assert!(device_suggestion("cuda", "cuda:0").is_none());
+        assert!(device_suggestion("cpu", "cpu").is_none());
+    }
+
+    #[test]
+    fn device_suggestion_auto_never_warns() {
+        assert!(device_suggestion("cpu", "auto").is_none());
+        assert!(device_suggestion("cuda", "auto").is_none());
+    }
+
+    #[test]
+    fn device_suggestion_missing_gpu() {
+        let hint = device_suggestion("cpu", "cuda").expect("should warn");
+        assert!(hint.contains("cuda"));
+        assert!(device_suggestion("cuda", "mps").is_some());
+    }
+
+    #[test]
+    fn device_suggestion_cpu_with_gpu_available() {
+        let hint = device_suggestion("cuda", "cpu").expect("should suggest gpu");
+        assert!(hint.contains("cuda"));
+    }
+
     #[test]
     fn server_manager_base_url() {
         // Create a manager without actually spawning, just to test base_url
         let mgr = ServerManager {
             child: None,
+            host: "127.0.0.1".to_owned(),
+            port: 9123,
+            logs: LogBuffer::default(),
+            attached: false,
+        };
+        assert_eq!(mgr.base_url(), "http://127.0.0.1:9123");
+    }
+
+    #[test]
+    fn push_log_line_keeps_latest_lines() {
+        let mut logs = VecDeque::new();
+        for i in 0..MAX_LOG_LINES + 5 {
+            push_log_line(&mut logs, format!("line {i}"));
+        }
+        assert_eq!(logs.len(), MAX_LOG_LINES);
+        assert_eq!(logs.front().map(String::as_str), Some("line 5"));
+        assert_eq!(logs.back().map(String::as_str), Some(&*format!("line {}", MAX_LOG_LINES + 4)));
+    }
+
+    #[test]
+    fn log_reader_collects_lines() {
+        let logs = LogBuffer::default();
+        let mgr = ServerManager {
+            child: None,
+            host: "127.0.0.1".to_owned(),
             port: 9123,
+            logs: Arc::clone(&logs),
+            attached: false,
         };
-        assert_eq!(mgr.base_url(), "http://localhost:9123");
+        spawn_log_reader(&b"Loading model\nTraceback (most recent call last):\n"[..], logs);
+        for _ in 0..100 {
+            if mgr.recent_logs().len() == 2 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(
+            mgr.recent_logs(),
+            vec!["Loading model", "Traceback (most recent call last):"]
+        );
+    }
+
+    #[test]
+    fn check_script_path_existing() {
+        let dir = std::env::temp_dir().join("qvox_test_script_path");
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("start_server.py");
+        std::fs::write(&path, "").expect("write");
+
+        assert!(check_script_path(&path.to_string_lossy()).is_ok());
+        let err = check_script_path(&dir.to_string_lossy()).expect_err("directory");
+        assert!(err.contains("not a file"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn check_script_path_missing() {
+        let err = check_script_path("/nonexistent/start_server.py").expect_err("missing");
+        assert_eq!(err, "Server script not found at /nonexistent/start_server.py");
+    }
+
+    #[test]
+    fn find_free_port_skips_taken_port() {
+        let taken = TcpListener::bind(("127.0.0.1", 0)).expect("bind");
+        let port = taken.local_addr().expect("addr").port();
+
+        let found = find_free_port("127.0.0.1", port, 10).expect("free port");
+        assert_ne!(found, port);
+        assert!(found > port && found < port.saturating_add(10));
+    }
+
+    #[test]
+    fn find_free_port_gives_up_after_attempts() {
+        let taken = TcpListener::bind(("127.0.0.1", 0)).expect("bind");
+        let port = taken.local_addr().expect("addr").port();
+        assert_eq!(find_free_port("127.0.0.1", port, 1), None);
+        assert_eq!(find_free_port("127.0.0.1", port, 0), None);
+    }
+
+    #[test]
+    fn base_url_for_ipv4_and_hostname() {
+        assert_eq!(base_url_for("127.0.0.1", 8000), "http://127.0.0.1:8000");
+        assert_eq!(base_url_for("localhost", 8001), "http://localhost:8001");
+    }
+
+    #[test]
+    fn base_url_for_ipv6_is_bracketed() {
+        assert_eq!(base_url_for("::1", 8000), "http://[::1]:8000");
+        assert_eq!(base_url_for("[::1]", 8000), "http://[::1]:8000");
+    }
+
+    #[test]
+    fn loopback_candidates_try_other_family() {
+        assert_eq!(loopback_candidates("127.0.0.1"), vec!["127.0.0.1", "::1"]);
+        assert_eq!(loopback_candidates("::1"), vec!["::1", "127.0.0.1"]);
+        assert_eq!(loopback_candidates("0.0.0.0"), vec!["127.0.0.1", "::1"]);
+        assert_eq!(loopback_candidates("192.168.1.5"), vec!["192.168.1.5"]);
+    }
+
+    #[tokio::test]
+    async fn probe_health_falls_back_to_ipv4() {
+        // MockServer listens on 127.0.0.1 only, so [::1] refuses.
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "healthy",
+                "voice_cloner_loaded": true,
+                "loaded_models": ["base"]
+            })))
+            .mount(&server)
+            .await;
+        let port = server.address().port();
+
+        let hosts = vec!["::1".to_owned(), "127.0.0.1".to_owned()];
+        let found = probe_health(&hosts, port).await;
+        let found = found.map(|(host, health)| (host, health.voice_cloner_loaded));
+        assert_eq!(found, Some(("127.0.0.1".to_owned(), true)));
+    }
+
+    #[tokio::test]
+    async fn probe_health_none_when_unreachable() {
+        let server = MockServer::start().await;
+        let port = server.address().port();
+        drop(server);
+
+        assert!(probe_health(&["127.0.0.1".to_owned()], port).await.is_none());
     }
 
     #[test]
     fn server_manager_not_running_without_child() {
         let mut mgr = ServerManager {
             child: None,
+            host: "127.0.0.1".to_owned(),
             port: 8000,
+            logs: LogBuffer::default(),
+            attached: false,
         };
         assert!(!mgr.is_running());
     }
+
+    #[test]
+    fn attached_server_is_left_running() {
+        let mut mgr = ServerManager::attach("::1", 8123);
+        assert!(mgr.is_attached());
+        assert_eq!(mgr.base_url(), "http://[::1]:8123");
+        assert_eq!(mgr.health_hosts(), vec!["::1", "127.0.0.1"]);
+        mgr.kill();
+        assert!(mgr.is_running());
+    }
+
+    #[cfg(unix)]
+    fn manager_for(cmd: &mut Command) -> ServerManager {
+        cmd.process_group(0);
+        ServerManager {
+            child: Some(cmd.spawn().expect("spawn")),
+            host: "127.0.0.1".to_owned(),
+            port: 8000,
+            logs: LogBuffer::default(),
+            attached: false,
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exited_server_is_reaped() {
+        let mut mgr = manager_for(&mut Command::new("true"));
+        for _ in 0..100 {
+            if !mgr.is_running() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(!mgr.is_running());
+        let child = mgr.child.as_mut().expect("child");
+        assert!(child.try_wait().expect("status").is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kill_timeout_stops_server_on_terminate() {
+        let mut mgr = manager_for(Command::new("sleep").arg("30"));
+        assert!(mgr.is_running());
+        let started = Instant::now();
+        mgr.kill_timeout(Duration::from_secs(5));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!mgr.is_running());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kill_timeout_force_kills_server_ignoring_terminate() {
+        let mut mgr = manager_for(Command::new("sh").args(["-c", "trap '' TERM; sleep 30"]));
+        // Let the shell install its trap first.
+        thread::sleep(Duration::from_millis(100));
+        let started = Instant::now();
+        mgr.kill_timeout(Duration::from_millis(200));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!mgr.is_running());
+    }
 }
@@ -0,0 +1,29 @@
+//! Server objects shared by the unit tests.
+
+use crate::api::types::{GeneratedAudio, ReferenceAudio};
+
+/// A reference uploaded as `original_name`, never renamed and without a
+/// transcript.
+pub fn reference(id: &str, original_name: &str) -> ReferenceAudio {
+    ReferenceAudio {
+        id: id.to_owned(),
+        filename: format!("{id}.wav"),
+        original_name: original_name.to_owned(),
+        name: None,
+        ref_text: None,
+        created_at: "1234567890.123".to_owned(),
+    }
+}
+
+/// A generated clip of `text` with no source reference.
+pub fn generated(id: &str, text: &str) -> GeneratedAudio {
+    GeneratedAudio {
+        id: id.to_owned(),
+        filename: format!("{id}.wav"),
+        ref_audio_id: None,
+        ref_audio_name: None,
+        generated_text: text.to_owned(),
+        created_at: "1700000000.0".to_owned(),
+        generation_time_seconds: None,
+    }
+}
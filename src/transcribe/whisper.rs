@@ -1,49 +1,127 @@
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::{Context, Result, bail};
 use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
-/// Default model file name.
-const MODEL_FILENAME: &str = "ggml-base.bin";
+/// `HuggingFace` directory holding the ggml Whisper models.
+const MODEL_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// Whisper model size. Larger models are slower but transcribe non-English
+/// speech far more accurately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WhisperModel {
+    Tiny,
+    #[default]
+    Base,
+    Small,
+    Medium,
+}
+
+impl WhisperModel {
+    pub const ALL: [Self; 4] = [Self::Tiny, Self::Base, Self::Small, Self::Medium];
+
+    /// Short name, as used in config and cache file names.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Tiny => "tiny",
+            Self::Base => "base",
+            Self::Small => "small",
+            Self::Medium => "medium",
+        }
+    }
 
-/// `HuggingFace` URL for the default Whisper model.
-const MODEL_URL: &str =
-    "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin";
+    /// Model file name, e.g. `ggml-base.bin`.
+    pub fn filename(self) -> String {
+        format!("ggml-{}.bin", self.name())
+    }
+
+    /// `HuggingFace` download URL.
+    pub fn url(self) -> String {
+        format!("{MODEL_BASE_URL}/{}", self.filename())
+    }
+}
+
+impl std::fmt::Display for WhisperModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// First four bytes of every ggml model file (`0x67676d6c`, little-endian).
+const GGML_MAGIC: [u8; 4] = *b"lmgg";
 
 /// Target sample rate for Whisper input.
 const TARGET_SAMPLE_RATE: u32 = 16_000;
 
 /// Return the directory where Whisper models are stored.
 ///
-/// Path: `{data_dir}/qvox/models/`
-pub fn models_dir() -> Result<PathBuf> {
-    let data = dirs::data_dir().context("could not determine data directory")?;
-    Ok(data.join("qvox").join("models"))
+/// Path: `{data_dir}/models/`
+pub fn models_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("models")
+}
+
+/// Return the full path to the given Whisper model.
+pub fn default_model_path(data_dir: &Path, model: WhisperModel) -> PathBuf {
+    models_dir(data_dir).join(model.filename())
 }
 
-/// Return the full path to the default Whisper model.
-pub fn default_model_path() -> Result<PathBuf> {
-    Ok(models_dir()?.join(MODEL_FILENAME))
+/// Check whether the given model is already downloaded.
+pub fn model_exists(data_dir: &Path, model: WhisperModel) -> bool {
+    default_model_path(data_dir, model).exists()
 }
 
-/// Check whether the default model is already downloaded.
-pub fn model_exists() -> bool {
-    default_model_path().is_ok_and(|p| p.exists())
+/// Downloaded models and their sizes in bytes.
+pub fn downloaded_models(data_dir: &Path) -> Vec<(WhisperModel, u64)> {
+    WhisperModel::ALL
+        .into_iter()
+        .filter_map(|model| {
+            let path = default_model_path(data_dir, model);
+            Some((model, std::fs::metadata(path).ok()?.len()))
+        })
+        .collect()
 }
 
-/// Download the Whisper model from `HuggingFace`.
+/// Delete every downloaded model, including interrupted `.tmp` downloads.
+/// They are fetched again on next use.
+pub fn delete_models(data_dir: &Path) -> Result<()> {
+    for model in WhisperModel::ALL {
+        let path = default_model_path(data_dir, model);
+        for path in [path.with_extension("bin.tmp"), path] {
+            match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(e).with_context(|| format!("failed to delete {}", path.display()));
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Download a Whisper model from `HuggingFace`.
+///
+/// An interrupted download left as `.bin.tmp` is resumed with a `Range`
+/// request; if the server ignores the range it starts over.
 ///
 /// Calls `on_progress(bytes_downloaded, total_bytes)` periodically.
 /// `total_bytes` may be 0 if the server does not provide `Content-Length`.
-pub async fn download_model<F>(on_progress: F) -> Result<PathBuf>
+pub async fn download_model<F>(
+    data_dir: &Path,
+    model: WhisperModel,
+    on_progress: F,
+) -> Result<PathBuf>
 where
     F: Fn(u64, u64),
 {
     use tokio::io::AsyncWriteExt;
 
-    let model_path = default_model_path()?;
+    let model_path = default_model_path(data_dir, model);
 
     if model_path.exists() {
         return Ok(model_path);
@@ -54,19 +132,38 @@ where
         .await
         .context("failed to create models directory")?;
 
-    let response = reqwest::get(MODEL_URL)
-        .await
-        .context("failed to start model download")?
+    let tmp_path = model_path.with_extension("bin.tmp");
+    let existing = tokio::fs::metadata(&tmp_path).await.map_or(0, |m| m.len());
+
+    let client = reqwest::Client::new();
+    let mut response = request_model(&client, model, existing).await?;
+    if existing > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // The partial file is already complete or longer than the model.
+        response = request_model(&client, model, 0).await?;
+    }
+    let response = response
         .error_for_status()
         .context("model download returned error status")?;
 
-    let total = response.content_length().unwrap_or(0);
-    let mut downloaded: u64 = 0;
-
-    let tmp_path = model_path.with_extension("bin.tmp");
-    let mut file = tokio::fs::File::create(&tmp_path)
-        .await
-        .context("failed to create temp model file")?;
+    let content_range = response
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)
+        .and_then(|v| v.to_str().ok());
+    let offset = resume_offset(response.status(), content_range, existing);
+    let total = response.content_length().map_or(0, |len| len + offset);
+    let mut downloaded = offset;
+
+    let mut file = if offset > 0 {
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&tmp_path)
+            .await
+            .context("failed to reopen temp model file")?
+    } else {
+        tokio::fs::File::create(&tmp_path)
+            .await
+            .context("failed to create temp model file")?
+    };
 
     let mut stream = response.bytes_stream();
 
@@ -82,6 +179,13 @@ where
     file.flush().await.context("failed to flush model file")?;
     drop(file);
 
+    // Checksums aren't pinned for the upstream models, so rely on the
+    // length and header to catch truncated or error-page downloads.
+    if let Err(e) = verify_model_file(&tmp_path, total, None) {
+        tokio::fs::remove_file(&tmp_path).await.ok();
+        return Err(e.context(format!("downloaded {model} model is corrupt")));
+    }
+
     tokio::fs::rename(&tmp_path, &model_path)
         .await
         .context("failed to rename temp model file")?;
@@ -89,6 +193,78 @@ where
     Ok(model_path)
 }
 
+/// Request `model`, asking only for the bytes after `from` when nonzero.
+async fn request_model(
+    client: &reqwest::Client,
+    model: WhisperModel,
+    from: u64,
+) -> Result<reqwest::Response> {
+    let mut request = client.get(model.url());
+    if from > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={from}-"));
+    }
+    request.send().await.context("failed to start model download")
+}
+
+/// Byte offset a download response continues from: `existing` when the
+/// server answered `206` with a range starting there, otherwise 0 so the
+/// partial file is rewritten.
+fn resume_offset(status: reqwest::StatusCode, content_range: Option<&str>, existing: u64) -> u64 {
+    let start = content_range
+        .and_then(|r| r.strip_prefix("bytes "))
+        .and_then(|r| r.split_once('-'))
+        .and_then(|(start, _)| start.trim().parse::<u64>().ok());
+    if status == reqwest::StatusCode::PARTIAL_CONTENT && start == Some(existing) {
+        existing
+    } else {
+        0
+    }
+}
+
+/// Check a downloaded model file: its size must equal `expected_len` (when
+/// nonzero), it must start with the ggml header, and its SHA-256 must match
+/// `expected_sha256` when one is given.
+pub fn verify_model_file(
+    path: &Path,
+    expected_len: u64,
+    expected_sha256: Option<&str>,
+) -> Result<()> {
+    use std::io::Read;
+
+    let len = std::fs::metadata(path)
+        .with_context(|| format!("failed to read {}", path.display()))?
+        .len();
+    if expected_len > 0 && len != expected_len {
+        bail!("expected {expected_len} bytes, got {len}");
+    }
+
+    let mut magic = [0u8; 4];
+    std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    if magic != GGML_MAGIC {
+        bail!("not a ggml model file");
+    }
+
+    if let Some(expected) = expected_sha256 {
+        let actual = crate::audio::hash::file_sha256(path)?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            bail!("SHA-256 mismatch: expected {expected}, got {actual}");
+        }
+    }
+    Ok(())
+}
+
+/// Load audio in any format the playback decoder understands (WAV, mp3,
+/// flac, ogg, ...) and return mono f32 samples resampled to 16 kHz.
+pub fn load_audio_16khz_mono(bytes: &[u8]) -> Result<Vec<f32>> {
+    if crate::audio::wav::is_wav(bytes) {
+        return load_wav_16khz_mono(bytes);
+    }
+    let wav = crate::audio::wav::convert_to_wav(bytes).context("failed to decode audio")?;
+    load_wav_16khz_mono(&wav)
+}
+
 /// Load a WAV file (any sample rate, any bit depth) and return mono f32
 /// samples resampled to 16 kHz.
 pub fn load_wav_16khz_mono(wav_bytes: &[u8]) -> Result<Vec<f32>> {
@@ -166,23 +342,70 @@ fn linear_resample(input: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
     output
 }
 
-/// Transcribe audio bytes (WAV format) using the default Whisper model.
+/// Map one of the app's language names (e.g. `"English"`) to Whisper's
+/// ISO 639-1 code. `"auto"` and unknown names map to `None` (auto-detect).
+pub fn whisper_language_code(language: &str) -> Option<&'static str> {
+    let code = match language.to_ascii_lowercase().as_str() {
+        "chinese" => "zh",
+        "english" => "en",
+        "japanese" => "ja",
+        "korean" => "ko",
+        "german" => "de",
+        "french" => "fr",
+        "russian" => "ru",
+        "portuguese" => "pt",
+        "spanish" => "es",
+        "italian" => "it",
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Transcribe audio bytes (WAV or any other decodable format) using the
+/// given Whisper model.
 ///
-/// This is a blocking operation and should be called via
+/// `language` is a Whisper language code (see [`whisper_language_code`]);
+/// `None` auto-detects. `on_progress` receives the percentage done as
+/// Whisper works through the clip. Setting `cancel` aborts the run with an
+/// error. This is a blocking operation and should be called via
 /// `tokio::task::spawn_blocking`.
-pub fn transcribe(wav_bytes: &[u8]) -> Result<String> {
-    let model_path = default_model_path()?;
+pub fn transcribe<F>(
+    data_dir: &Path,
+    wav_bytes: &[u8],
+    model: WhisperModel,
+    language: Option<&str>,
+    cancel: Arc<AtomicBool>,
+    on_progress: F,
+) -> Result<String>
+where
+    F: FnMut(u8) + 'static,
+{
+    let model_path = default_model_path(data_dir, model);
     if !model_path.exists() {
         bail!("Whisper model not found at {}", model_path.display());
     }
 
-    let audio = load_wav_16khz_mono(wav_bytes)?;
-    transcribe_with_model(&model_path, &audio)
+    let audio = load_audio_16khz_mono(wav_bytes)?;
+    transcribe_with_model(&model_path, &audio, language, cancel, on_progress)
+}
+
+/// Clamp Whisper's raw progress value to a `0..=100` percentage.
+fn progress_percent(raw: i32) -> u8 {
+    u8::try_from(raw.clamp(0, 100)).unwrap_or(100)
 }
 
 /// Transcribe pre-processed 16 kHz mono f32 audio using the model at
-/// the given path.
-fn transcribe_with_model(model_path: &Path, audio: &[f32]) -> Result<String> {
+/// the given path, stopping early once `cancel` is set.
+fn transcribe_with_model<F>(
+    model_path: &Path,
+    audio: &[f32],
+    language: Option<&str>,
+    cancel: Arc<AtomicBool>,
+    mut on_progress: F,
+) -> Result<String>
+where
+    F: FnMut(u8) + 'static,
+{
     let ctx = WhisperContext::new_with_params(
         &model_path.to_string_lossy(),
         WhisperContextParameters::default(),
@@ -195,15 +418,20 @@ fn transcribe_with_model(model_path: &Path, audio: &[f32]) -> Result<String> {
 
     let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 0 });
     params.set_n_threads(num_threads());
-    params.set_language(None); // auto-detect
+    params.set_language(language); // `None` auto-detects
     params.set_print_special(false);
     params.set_print_progress(false);
     params.set_print_realtime(false);
     params.set_print_timestamps(false);
+    params.set_progress_callback_safe(move |raw: i32| on_progress(progress_percent(raw)));
+    let abort = Arc::clone(&cancel);
+    params.set_abort_callback_safe(move || abort.load(Ordering::Relaxed));
 
-    state
-        .full(params, audio)
-        .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {e}"))?;
+    let result = state.full(params, audio);
+    if cancel.load(Ordering::Relaxed) {
+        bail!("transcription cancelled");
+    }
+    result.map_err(|e| anyhow::anyhow!("Whisper transcription failed: {e}"))?;
 
     let mut text = String::new();
     for segment in state.as_iter() {
@@ -215,23 +443,47 @@ fn transcribe_with_model(model_path: &Path, audio: &[f32]) -> Result<String> {
     Ok(text.trim().to_owned())
 }
 
-/// Return the cache path for a given audio file hash.
+/// Return the directory where transcriptions are cached.
 ///
-/// The cache file is stored at `{data_dir}/qvox/cache/{hash}.txt`.
-pub fn cache_path(audio_hash: &str) -> Result<PathBuf> {
-    let data = dirs::data_dir().context("could not determine data directory")?;
-    Ok(data.join("qvox").join("cache").join(format!("{audio_hash}.txt")))
+/// Path: `{data_dir}/cache/`
+pub fn cache_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("cache")
 }
 
-/// Look up cached transcription for the given audio hash.
-pub fn cached_transcription(audio_hash: &str) -> Option<String> {
-    let path = cache_path(audio_hash).ok()?;
-    std::fs::read_to_string(path).ok()
+/// Return the cache path for a given audio file hash, model and Whisper
+/// language code (`None` for auto-detect).
+///
+/// The cache file is stored at `{data_dir}/cache/{hash}-{model}-{lang}.txt`,
+/// so switching models or languages never returns another run's transcript.
+pub fn cache_path(
+    data_dir: &Path,
+    audio_hash: &str,
+    model: WhisperModel,
+    language: Option<&str>,
+) -> PathBuf {
+    let language = language.unwrap_or("auto");
+    cache_dir(data_dir).join(format!("{audio_hash}-{model}-{language}.txt"))
+}
+
+/// Look up cached transcription for the given audio hash, model and language.
+pub fn cached_transcription(
+    data_dir: &Path,
+    audio_hash: &str,
+    model: WhisperModel,
+    language: Option<&str>,
+) -> Option<String> {
+    std::fs::read_to_string(cache_path(data_dir, audio_hash, model, language)).ok()
 }
 
 /// Save transcription text to cache.
-pub fn save_transcription_cache(audio_hash: &str, text: &str) -> Result<()> {
-    let path = cache_path(audio_hash)?;
+pub fn save_transcription_cache(
+    data_dir: &Path,
+    audio_hash: &str,
+    model: WhisperModel,
+    language: Option<&str>,
+    text: &str,
+) -> Result<()> {
+    let path = cache_path(data_dir, audio_hash, model, language);
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).context("failed to create cache directory")?;
     }
@@ -239,6 +491,45 @@ pub fn save_transcription_cache(audio_hash: &str, text: &str) -> Result<()> {
     Ok(())
 }
 
+/// Total size in bytes of the cached transcriptions.
+pub fn cache_size(data_dir: &Path) -> u64 {
+    dir_size(&cache_dir(data_dir))
+}
+
+/// Delete every cached transcription.
+pub fn clear_cache(data_dir: &Path) -> Result<()> {
+    remove_files(&cache_dir(data_dir))
+}
+
+/// Total size of the files directly inside `dir`; 0 if it doesn't exist.
+fn dir_size(dir: &Path) -> u64 {
+    std::fs::read_dir(dir).map_or(0, |entries| {
+        entries
+            .filter_map(|e| e.ok()?.metadata().ok())
+            .filter(std::fs::Metadata::is_file)
+            .map(|m| m.len())
+            .sum()
+    })
+}
+
+/// Remove the files directly inside `dir`. A missing directory is already
+/// clear.
+fn remove_files(dir: &Path) -> Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).context("failed to read cache directory"),
+    };
+    for entry in entries {
+        let path = entry.context("failed to read cache directory")?.path();
+        if path.is_file() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("failed to delete {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
 /// Pick a reasonable thread count for Whisper.
 #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
 fn num_threads() -> i32 {
@@ -255,40 +546,131 @@ mod tests {
 
     #[test]
     fn models_dir_is_under_data() {
-        let dir = models_dir().expect("models_dir");
-        assert!(dir.ends_with("qvox/models"));
+        assert_eq!(models_dir(Path::new("/data/qvox")), Path::new("/data/qvox/models"));
     }
 
     #[test]
     fn default_model_path_has_filename() {
-        let path = default_model_path().expect("model path");
+        let data = Path::new("/data/qvox");
+        let path = default_model_path(data, WhisperModel::Base);
         assert_eq!(path.file_name().and_then(|n| n.to_str()), Some("ggml-base.bin"));
+        let path = default_model_path(data, WhisperModel::Medium);
+        assert_eq!(path.file_name().and_then(|n| n.to_str()), Some("ggml-medium.bin"));
     }
 
     #[test]
-    fn cache_path_uses_hash() {
-        let path = cache_path("abc123").expect("cache path");
-        assert!(path.to_string_lossy().contains("abc123.txt"));
+    fn delete_models_removes_downloads_and_partials() {
+        let dir = std::env::temp_dir().join("qvox_test_delete_models");
+        let path = default_model_path(&dir, WhisperModel::Tiny);
+        std::fs::create_dir_all(models_dir(&dir)).expect("create dir");
+        std::fs::write(&path, "model").expect("write");
+        std::fs::write(path.with_extension("bin.tmp"), "part").expect("write");
+        assert_eq!(downloaded_models(&dir), vec![(WhisperModel::Tiny, 5)]);
+
+        delete_models(&dir).expect("delete");
+        assert!(downloaded_models(&dir).is_empty());
+        assert!(!path.with_extension("bin.tmp").exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn model_url_points_at_file() {
+        assert_eq!(
+            WhisperModel::Small.url(),
+            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin"
+        );
+    }
+
+    #[test]
+    fn model_serde_round_trip() {
+        for model in WhisperModel::ALL {
+            let json = serde_json::to_string(&model).expect("serialize");
+            assert_eq!(json, format!("\"{}\"", model.name()));
+            let back: WhisperModel = serde_json::from_str(&json).expect("deserialize");
+            assert_eq!(back, model);
+        }
+    }
+
+    #[test]
+    fn cache_path_uses_hash_model_and_language() {
+        let path = cache_path(Path::new("/data/qvox"), "abc123", WhisperModel::Tiny, None);
+        assert_eq!(path, Path::new("/data/qvox/cache/abc123-tiny-auto.txt"));
+        let path = cache_path(Path::new("/data/qvox"), "abc123", WhisperModel::Tiny, Some("ja"));
+        assert_eq!(path, Path::new("/data/qvox/cache/abc123-tiny-ja.txt"));
     }
 
     #[test]
     fn cached_transcription_returns_none_for_missing() {
-        assert!(cached_transcription("nonexistent_hash_12345").is_none());
+        let dir = std::env::temp_dir().join("qvox_test_cache_missing");
+        assert!(cached_transcription(&dir, "nonexistent_hash", WhisperModel::Base, None).is_none());
     }
 
     #[test]
     fn save_and_read_cache() {
-        let hash = "test_cache_round_trip_qvox";
+        let dir = std::env::temp_dir().join("qvox_test_cache_round_trip");
+        let hash = "abc123";
         let text = "Hello world transcription";
 
-        save_transcription_cache(hash, text).expect("save");
-        let cached = cached_transcription(hash);
+        save_transcription_cache(&dir, hash, WhisperModel::Base, Some("en"), text).expect("save");
+        let cached = cached_transcription(&dir, hash, WhisperModel::Base, Some("en"));
         assert_eq!(cached.as_deref(), Some(text));
+        // Another model's or language's cache is separate.
+        assert!(cached_transcription(&dir, hash, WhisperModel::Small, Some("en")).is_none());
+        assert!(cached_transcription(&dir, hash, WhisperModel::Base, None).is_none());
+        assert!(cache_size(&dir) > 0);
 
-        // Cleanup
-        if let Ok(path) = cache_path(hash) {
-            std::fs::remove_file(path).ok();
-        }
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn dir_size_and_remove_files() {
+        let dir = std::env::temp_dir().join("qvox_test_cache_clear");
+        std::fs::create_dir_all(dir.join("nested")).expect("create dir");
+        std::fs::write(dir.join("a.txt"), "abc").expect("write");
+        std::fs::write(dir.join("b.txt"), "hello").expect("write");
+        assert_eq!(dir_size(&dir), 8);
+
+        remove_files(&dir).expect("remove");
+        assert_eq!(dir_size(&dir), 0);
+        assert!(dir.join("nested").exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(dir_size(&dir), 0);
+        remove_files(&dir).expect("missing dir is clear");
+    }
+
+    #[test]
+    fn resume_offset_requires_matching_partial_content() {
+        use reqwest::StatusCode;
+
+        let range = Some("bytes 100-999/1000");
+        assert_eq!(resume_offset(StatusCode::PARTIAL_CONTENT, range, 100), 100);
+        // The server ignored the range: start over.
+        assert_eq!(resume_offset(StatusCode::OK, None, 100), 0);
+        // A range from elsewhere can't be appended.
+        assert_eq!(resume_offset(StatusCode::PARTIAL_CONTENT, range, 50), 0);
+        assert_eq!(resume_offset(StatusCode::PARTIAL_CONTENT, None, 100), 0);
+        assert_eq!(resume_offset(StatusCode::OK, None, 0), 0);
+    }
+
+    #[test]
+    fn verify_model_file_checks_size_header_and_hash() {
+        let dir = std::env::temp_dir().join("qvox_test_verify_model");
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let path = dir.join("ggml-test.bin");
+        std::fs::write(&path, b"lmgg1234").expect("write");
+        let sha = crate::audio::hash::bytes_sha256(b"lmgg1234");
+
+        assert!(verify_model_file(&path, 8, Some(&sha)).is_ok());
+        assert!(verify_model_file(&path, 0, None).is_ok());
+        assert!(verify_model_file(&path, 9, None).is_err());
+        assert!(verify_model_file(&path, 8, Some("00")).is_err());
+
+        std::fs::write(&path, b"<!DOCTYPE html>").expect("write");
+        assert!(verify_model_file(&path, 0, None).is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
     }
 
     #[test]
@@ -368,6 +750,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn load_audio_decodes_mp3() {
+        // 40 silent MPEG-1 Layer III frames, mono, 44.1 kHz, 128 kbps.
+        let mut frame = vec![0_u8; 417];
+        frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0xC0]);
+        let samples = load_audio_16khz_mono(&frame.repeat(40)).expect("decode mp3");
+        let expected = 40 * 1152 * 16_000 / 44_100;
+        assert!(samples.len().abs_diff(expected) < 200, "got {} samples", samples.len());
+        assert!(samples.iter().all(|s| s.abs() < 1e-3));
+    }
+
+    #[test]
+    fn load_audio_rejects_garbage() {
+        assert!(load_audio_16khz_mono(b"not audio at all").is_err());
+    }
+
+    #[test]
+    fn language_codes_for_known_names() {
+        assert_eq!(whisper_language_code("English"), Some("en"));
+        assert_eq!(whisper_language_code("Japanese"), Some("ja"));
+        assert_eq!(whisper_language_code("chinese"), Some("zh"));
+    }
+
+    #[test]
+    fn language_code_auto_detects() {
+        assert_eq!(whisper_language_code("auto"), None);
+        assert_eq!(whisper_language_code("Klingon"), None);
+    }
+
+    #[test]
+    fn progress_percent_clamps() {
+        assert_eq!(progress_percent(-5), 0);
+        assert_eq!(progress_percent(42), 42);
+        assert_eq!(progress_percent(250), 100);
+    }
+
     #[test]
     fn num_threads_reasonable() {
         let n = num_threads();
@@ -1,53 +1,200 @@
-use iced::widget::{button, column, pick_list, progress_bar, row, text, text_input};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use iced::widget::{button, checkbox, column, pick_list, row, slider, text};
 use iced::{Element, Length};
 
 use crate::api::types::{ReferenceAudio, TaskStatus};
-use crate::audio::player::PlaybackState;
-use crate::message::{ActiveTask, Message};
+use crate::audio::player::{PlaybackState, PlaybackStatus};
+use crate::message::{ActiveTask, Message, TabId, summarize_request};
+use crate::views::common::{
+    EditorText, ReferenceOption, char_counter, elapsed_label, format_playback_time,
+    generate_button, missing_ref_text_hint, preset_row, progress_row, reference_options,
+    selected_option, submit_message, text_area,
+};
+
+/// How far one line of a batch has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchLineStatus {
+    /// Queued, or sent and waiting for the server to create its task.
+    Waiting,
+    Running,
+    Done,
+    Failed,
+    /// Stopped before it was sent, or cancelled while running.
+    Stopped,
+}
+
+impl BatchLineStatus {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Waiting => "waiting",
+            Self::Running => "generating",
+            Self::Done => "done",
+            Self::Failed => "failed",
+            Self::Stopped => "stopped",
+        }
+    }
+}
+
+/// One line of a batch, submitted as its own clone task.
+#[derive(Debug, Clone)]
+pub struct BatchLine {
+    /// Carried by the line's request as `CloneRequest::batch_line`, so
+    /// lines with the same text are told apart.
+    pub id: u64,
+    pub text: String,
+    pub task_id: Option<String>,
+    pub status: BatchLineStatus,
+}
 
 /// State specific to the Voice Clone tab.
 #[derive(Debug, Clone, Default)]
 pub struct CloneTabState {
-    pub text: String,
+    pub editor: EditorText,
     pub selected_ref: Option<String>,
     pub selected_language: String,
+    /// Generate each non-empty line of the text as its own clone.
+    pub batch_mode: bool,
+    /// Lines of the last batch, in order.
+    pub batch: Vec<BatchLine>,
+    /// Id for the next batch line.
+    next_batch_line: u64,
 }
 
 impl CloneTabState {
     pub fn new() -> Self {
         Self {
-            text: String::new(),
+            editor: EditorText::default(),
             selected_ref: None,
             selected_language: "auto".to_owned(),
+            batch_mode: false,
+            batch: Vec::new(),
+            next_batch_line: 0,
         }
     }
+
+    /// The non-empty lines of the text, trimmed, as a batch would send them.
+    pub fn batch_lines(&self) -> Vec<String> {
+        self.editor
+            .text()
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    /// Start tracking a new batch of `lines`, all waiting.
+    pub fn start_batch(&mut self, lines: Vec<String>) {
+        self.batch = lines
+            .into_iter()
+            .map(|text| {
+                self.next_batch_line += 1;
+                BatchLine {
+                    id: self.next_batch_line,
+                    text,
+                    task_id: None,
+                    status: BatchLineStatus::Waiting,
+                }
+            })
+            .collect();
+    }
+
+    /// Whether the last batch still has lines waiting or running.
+    pub fn batch_running(&self) -> bool {
+        self.batch
+            .iter()
+            .any(|l| matches!(l.status, BatchLineStatus::Waiting | BatchLineStatus::Running))
+    }
+
+    /// Record the server's answer to the request for batch line `line_id`:
+    /// the task it created, or `None` when submitting failed. Ids that
+    /// belong to no waiting line are ignored.
+    pub fn batch_task_created(&mut self, line_id: u64, task_id: Option<String>) {
+        let line = self.batch.iter_mut().find(|l| {
+            l.id == line_id && l.status == BatchLineStatus::Waiting && l.task_id.is_none()
+        });
+        if let Some(line) = line {
+            line.status = if task_id.is_some() {
+                BatchLineStatus::Running
+            } else {
+                BatchLineStatus::Failed
+            };
+            line.task_id = task_id;
+        }
+    }
+
+    /// Mark the running line for `task_id` as finished with `status`.
+    pub fn batch_task_finished(&mut self, task_id: &str, status: BatchLineStatus) {
+        if let Some(line) = self.batch.iter_mut().find(|l| {
+            l.status == BatchLineStatus::Running && l.task_id.as_deref() == Some(task_id)
+        }) {
+            line.status = status;
+        }
+    }
+
+    /// Replace the text in the editor.
+    pub fn set_text(&mut self, text: &str) {
+        self.editor = EditorText::with_text(text);
+    }
+
+    /// Reset to `new()`, keeping the selected language and the batch
+    /// mode and progress.
+    pub fn clear(&mut self) {
+        let selected_language = std::mem::take(&mut self.selected_language);
+        let batch = std::mem::take(&mut self.batch);
+        *self = Self {
+            selected_language,
+            batch_mode: self.batch_mode,
+            batch,
+            next_batch_line: self.next_batch_line,
+            ..Self::new()
+        };
+    }
+}
+
+/// One-line tally of a batch, e.g. "2 done, 1 failed, 3 remaining".
+fn batch_summary(batch: &[BatchLine]) -> String {
+    let count = |status: BatchLineStatus| batch.iter().filter(|l| l.status == status).count();
+    let remaining = count(BatchLineStatus::Waiting) + count(BatchLineStatus::Running);
+    let mut parts = vec![
+        format!("{} done", count(BatchLineStatus::Done)),
+        format!("{} failed", count(BatchLineStatus::Failed)),
+    ];
+    let stopped = count(BatchLineStatus::Stopped);
+    if stopped > 0 {
+        parts.push(format!("{stopped} stopped"));
+    }
+    if remaining > 0 {
+        parts.push(format!("{remaining} remaining"));
+    }
+    parts.join(", ")
 }
 
 // LCOV_EXCL_START
 
 /// Build the Voice Clone tab view.
+#[allow(clippy::too_many_arguments)]
 pub fn view<'a>(
     state: &'a CloneTabState,
     references: &'a [ReferenceAudio],
+    preview_offsets: &'a BTreeMap<String, f32>,
     languages: &'a [String],
     active_task: Option<&'a ActiveTask>,
-    playback: PlaybackState,
+    has_result: bool,
+    playback: PlaybackStatus,
     model_available: bool,
+    enter_to_generate: bool,
+    text_soft_limit: usize,
 ) -> Element<'a, Message> {
-    let ref_names: Vec<String> = references
-        .iter()
-        .map(|r| {
-            r.name
-                .clone()
-                .unwrap_or_else(|| r.original_name.clone())
-        })
-        .collect();
-
-    let ref_picker = pick_list(
-        ref_names,
-        state.selected_ref.clone(),
-        Message::CloneRefSelected,
-    )
+    let ref_options = reference_options(references);
+    let selected = selected_option(&ref_options, state.selected_ref.as_ref());
+    let missing_ref_text = selected.clone().filter(|o| !o.has_ref_text);
+
+    let ref_picker = pick_list(ref_options, selected, |option: ReferenceOption| {
+        Message::CloneRefSelected(option.name)
+    })
     .placeholder("Select reference audio...");
 
     // Preview button for the selected reference audio
@@ -56,14 +203,27 @@ pub fn view<'a>(
         // Find the reference audio ID for the selected name
         let ref_audio = references
             .iter()
-            .find(|r| r.name.as_deref().unwrap_or(&r.original_name) == ref_name.as_str());
+            .find(|r| r.display_name() == ref_name.as_str());
         if let Some(audio) = ref_audio {
             let mut preview_btn = button(text("Preview"));
-            if playback == PlaybackState::Stopped {
+            if playback.state == PlaybackState::Stopped {
                 preview_btn =
                     preview_btn.on_press(Message::PlayReference(audio.id.clone()));
             }
             ref_row = ref_row.push(preview_btn);
+
+            let offset = preview_offsets.get(&audio.id).copied().unwrap_or(0.0);
+            let id = audio.id.clone();
+            ref_row = ref_row
+                .push(text(format!("Skip {offset:.1}s")).size(12))
+                .push(
+                    slider(0.0..=10.0, offset, move |secs| {
+                        Message::ReferencePreviewOffset(id.clone(), Duration::from_secs_f32(secs))
+                    })
+                    .on_release(Message::ConfigSliderReleased)
+                    .step(0.5)
+                    .width(Length::Fixed(120.0)),
+                );
         }
     }
 
@@ -74,72 +234,112 @@ pub fn view<'a>(
     )
     .placeholder("Language");
 
-    let text_field = text_input("Enter text to generate...", &state.text)
-        .on_input(Message::CloneTextChanged)
-        .width(Length::Fill);
-
     let is_generating = active_task
         .as_ref()
         .is_some_and(|t| t.status == TaskStatus::Processing);
-    let can_generate =
-        !state.text.is_empty() && state.selected_ref.is_some() && !is_generating && model_available;
+    let batch_len = state.batch_lines().len();
+    let has_text = if state.batch_mode {
+        batch_len > 0 && !state.batch_running()
+    } else {
+        !state.editor.is_empty()
+    };
+    let can_generate = has_text && state.selected_ref.is_some() && model_available;
 
-    let mut generate_btn = button(text("Generate"));
+    let label = if state.batch_mode {
+        format!("Generate {batch_len} line(s)")
+    } else if is_generating {
+        "Queue".to_owned()
+    } else {
+        "Generate".to_owned()
+    };
+    let mut generate_btn = button(text(label));
     if can_generate {
         generate_btn = generate_btn.on_press(Message::CloneGenerate);
     }
+    // Enter adds a line in batch mode, where lines are the point.
+    let submit = if state.batch_mode {
+        None
+    } else {
+        submit_message(enter_to_generate, can_generate, Message::CloneGenerate)
+    };
+    let text_field = text_area(&state.editor, Message::CloneTextAction, submit);
 
     let mut content = column![
         text("Voice Clone").size(24),
+        preset_row(TabId::Clone, state.selected_ref.is_some()),
         text("Reference Audio").size(14),
         ref_row,
+    ]
+    .spacing(8)
+    .padding(20)
+    .width(Length::Fill);
+
+    if let Some(option) = &missing_ref_text {
+        content = content.push(missing_ref_text_hint(option));
+    }
+
+    content = content.push(column![
         text("Language").size(14),
         lang_picker,
         text("Text").size(14),
+        checkbox(state.batch_mode)
+            .label("Batch: generate each line separately")
+            .on_toggle(Message::CloneBatchToggled)
+            .text_size(12),
         text_field,
-        row![generate_btn].spacing(8),
+        char_counter(&state.editor.text(), text_soft_limit),
+        row![
+            generate_button(generate_btn, TabId::Clone, model_available),
+            button(text("Clear")).on_press(Message::CloneClear),
+        ]
+        .spacing(8),
     ]
-    .spacing(8)
-    .padding(20)
-    .width(Length::Fill);
+    .spacing(8));
+
+    if !state.batch.is_empty() {
+        let mut header = row![text(format!("Batch: {}", batch_summary(&state.batch))).size(14)]
+            .spacing(8);
+        if state.batch_running() {
+            header = header.push(button(text("Stop batch")).on_press(Message::CloneBatchStop));
+        }
+        content = content.push(header);
+        for (i, line) in state.batch.iter().enumerate() {
+            content = content
+                .push(text(format!("{}. [{}] {}", i + 1, line.status.label(), line.text)).size(12));
+        }
+    }
 
     // Progress section
     if let Some(task) = active_task {
-        #[allow(clippy::cast_precision_loss)]
-        let progress_value = task.progress as f32;
         content = content
-            .push(progress_bar(0.0..=100.0, progress_value))
+            .push(progress_row(task))
             .push(text(&task.status_text).size(14))
-            .push(
-                text(format!(
-                    "Elapsed: {:02}:{:02}",
-                    task.elapsed_secs / 60,
-                    task.elapsed_secs % 60
-                ))
-                .size(12),
-            );
+            .push(text(elapsed_label(task)).size(12));
 
         if let Some(err) = &task.error {
             content = content.push(text(err).size(14));
         }
 
-        // Playback controls for completed task with audio data
-        if task.status == TaskStatus::Completed && task.audio_data.is_some() {
-            content = content.push(playback_controls(playback));
+        if let Some(request) = &task.request {
+            content = content.push(text(summarize_request(request, references)).size(11));
         }
-    } else if playback != PlaybackState::Stopped {
-        // Playback controls for reference preview (no active task)
+
+    }
+
+    // The tab's last result, or a reference preview that is playing.
+    if has_result || playback.state != PlaybackState::Stopped {
         content = content.push(playback_controls(playback));
     }
 
     content.into()
 }
 
-/// Render play/pause/stop buttons based on current playback state.
-pub fn playback_controls(playback: PlaybackState) -> Element<'static, Message> {
+/// Render play/pause/stop buttons based on current playback state, with
+/// position and length while a clip is loaded.
+pub fn playback_controls(playback: PlaybackStatus) -> Element<'static, Message> {
     let mut controls = row![].spacing(8);
 
-    match playback {
+    match playback.state {
         PlaybackState::Stopped => {
             controls = controls.push(button(text("Play")).on_press(Message::PlayGenerated));
         }
@@ -153,7 +353,67 @@ pub fn playback_controls(playback: PlaybackState) -> Element<'static, Message> {
         }
     }
 
+    controls = controls.push(
+        button(text(if playback.looping { "Loop: on" } else { "Loop: off" }))
+            .on_press(Message::PlaybackToggleLoop),
+    );
+
+    if playback.state != PlaybackState::Stopped {
+        if let Some(total) = playback.total.filter(|t| !t.is_zero()) {
+            let fraction = (playback.position.as_secs_f32() / total.as_secs_f32()).min(1.0);
+            controls = controls.push(
+                slider(0.0..=1.0, fraction, Message::PlaybackSeek)
+                    .step(0.001)
+                    .width(Length::Fill),
+            );
+        }
+        controls = controls.push(
+            text(format_playback_time(playback.position, playback.total)).size(12),
+        );
+    }
+
     controls.into()
 }
 
 // LCOV_EXCL_STOP
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch_state(text: &str) -> CloneTabState {
+        let mut state = CloneTabState::new();
+        state.set_text(text);
+        let lines = state.batch_lines();
+        state.start_batch(lines);
+        state
+    }
+
+    #[test]
+    fn batch_lines_skip_blank_lines() {
+        let state = batch_state("  one \n\ntwo\n   \nthree");
+        let texts: Vec<&str> = state.batch.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(texts, ["one", "two", "three"]);
+        assert!(state.batch_running());
+    }
+
+    #[test]
+    fn batch_lines_follow_their_tasks() {
+        let mut state = batch_state("same\nsame\nother");
+        let ids: Vec<u64> = state.batch.iter().map(|l| l.id).collect();
+        state.batch_task_created(ids[1], None);
+        state.batch_task_created(ids[0], Some("task-1".to_owned()));
+        state.batch_task_created(ids[0], Some("task-9".to_owned()));
+        assert_eq!(state.batch[0].status, BatchLineStatus::Running);
+        assert_eq!(state.batch[1].status, BatchLineStatus::Failed);
+        assert_eq!(state.batch[2].status, BatchLineStatus::Waiting);
+
+        state.batch_task_finished("task-1", BatchLineStatus::Done);
+        assert_eq!(state.batch[0].status, BatchLineStatus::Done);
+        assert_eq!(batch_summary(&state.batch), "1 done, 1 failed, 1 remaining");
+
+        state.batch[2].status = BatchLineStatus::Stopped;
+        assert!(!state.batch_running());
+        assert_eq!(batch_summary(&state.batch), "1 done, 1 failed, 1 stopped");
+    }
+}
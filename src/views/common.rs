@@ -0,0 +1,513 @@
+use iced::keyboard::{Key, key::Named};
+use iced::widget::text_editor::{self, Action, Binding, KeyPress};
+use iced::widget::{
+    Button, button, column, container, progress_bar, row, text, text_editor as editor, tooltip,
+};
+use iced::{Element, Length};
+
+use crate::api::types::{ReferenceAudio, TaskStatus};
+use crate::message::{ActiveTask, Message, TabId};
+
+/// Truncate `s` to at most `max_chars` characters, appending `...` when cut.
+///
+/// Counts chars rather than bytes so multibyte text (e.g. Japanese) never
+/// gets sliced mid-character.
+pub fn truncate_text(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        s.to_owned()
+    } else {
+        let truncated: String = s.chars().take(max_chars).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// A reference as listed in a picker, flagged when it has no transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceOption {
+    pub id: String,
+    /// Display name, as used by the tabs' `selected_ref`.
+    pub name: String,
+    pub has_ref_text: bool,
+}
+
+impl std::fmt::Display for ReferenceOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.has_ref_text {
+            f.write_str(&self.name)
+        } else {
+            write!(f, "{} (no transcript)", self.name)
+        }
+    }
+}
+
+/// Picker options for `references`, in order.
+pub fn reference_options(references: &[ReferenceAudio]) -> Vec<ReferenceOption> {
+    references
+        .iter()
+        .map(|r| ReferenceOption {
+            id: r.id.clone(),
+            name: r.display_name().to_owned(),
+            has_ref_text: r.ref_text.as_deref().is_some_and(|t| !t.trim().is_empty()),
+        })
+        .collect()
+}
+
+/// The option in `options` named `selected`, if any.
+pub fn selected_option(
+    options: &[ReferenceOption],
+    selected: Option<&String>,
+) -> Option<ReferenceOption> {
+    selected.and_then(|name| options.iter().find(|o| &o.name == name).cloned())
+}
+
+/// Hint shown when the selected reference has no transcript, with a button
+/// that opens it in the Upload tab for transcription.
+pub fn missing_ref_text_hint<'a>(option: &ReferenceOption) -> Element<'a, Message> {
+    row![
+        text(format!(
+            "\"{}\" has no transcript, so cloning quality may suffer. Transcribe it in the \
+             Upload tab and save it with its transcript.",
+            option.name
+        ))
+        .size(12),
+        button(text("Transcribe").size(12))
+            .on_press(Message::ReferenceTranscribe(option.id.clone())),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Multiline content of a tab's main text field, and the only copy of that
+/// text.
+#[derive(Default)]
+pub struct EditorText(text_editor::Content);
+
+impl EditorText {
+    pub fn with_text(text: &str) -> Self {
+        Self(text_editor::Content::with_text(text))
+    }
+
+    pub fn content(&self) -> &text_editor::Content {
+        &self.0
+    }
+
+    /// The text, without the editor's trailing line break.
+    pub fn text(&self) -> String {
+        editor_string(self.0.text())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text().is_empty()
+    }
+
+    pub fn perform(&mut self, action: Action) {
+        self.0.perform(action);
+    }
+}
+
+impl Clone for EditorText {
+    fn clone(&self) -> Self {
+        Self::with_text(&self.text())
+    }
+}
+
+impl std::fmt::Debug for EditorText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EditorText").field(&self.0.text()).finish()
+    }
+}
+
+/// `text` from `text_editor::Content::text`, without the line break the
+/// editor reports after its last line.
+fn editor_string(mut text: String) -> String {
+    if text.ends_with('\n') {
+        text.pop();
+    }
+    text
+}
+
+/// Multiline field for a tab's main text. When `submit` is set Enter sends
+/// it and Shift+Enter starts a new line.
+pub fn text_area<'a>(
+    content: &'a EditorText,
+    on_action: fn(Action) -> Message,
+    submit: Option<Message>,
+) -> Element<'a, Message> {
+    editor(content.content())
+        .placeholder("Enter text to generate...")
+        .on_action(on_action)
+        .height(Length::Fixed(120.0))
+        .key_binding(move |key_press: KeyPress| {
+            let enter = matches!(key_press.key, Key::Named(Named::Enter))
+                && !key_press.modifiers.shift();
+            match &submit {
+                Some(message) if enter => Some(Binding::Custom(message.clone())),
+                _ => Binding::from_key_press(key_press),
+            }
+        })
+        .into()
+}
+
+/// Message to send when Enter is pressed in a tab's text field.
+///
+/// Only fires with `[ui] enter_to_generate` on and the tab's can-generate
+/// guard passing, so Enter never bypasses a disabled Generate button.
+pub fn submit_message(
+    enter_to_generate: bool,
+    can_generate: bool,
+    generate: Message,
+) -> Option<Message> {
+    (enter_to_generate && can_generate).then_some(generate)
+}
+
+/// Whether `text` is longer than `soft_limit` characters.
+pub fn over_soft_limit(text: &str, soft_limit: usize) -> bool {
+    text.chars().count() > soft_limit
+}
+
+/// Character counter for a generation text field. Past `soft_limit` it is
+/// highlighted and followed by a hint; generation is not blocked.
+pub fn char_counter<'a>(value: &str, soft_limit: usize) -> Element<'a, Message> {
+    let label = format!("{} chars", value.chars().count());
+    if !over_soft_limit(value, soft_limit) {
+        return text(label).size(11).into();
+    }
+    column![
+        text(label).size(11).style(text::danger),
+        text(format!(
+            "Over {soft_limit} characters; long text may not generate cleanly. \
+             Consider splitting it."
+        ))
+        .size(11),
+    ]
+    .into()
+}
+
+/// "Import preset" / "Export preset" buttons for a tab.
+pub fn preset_row<'a>(tab: TabId, can_export: bool) -> Element<'a, Message> {
+    let mut export_btn = button(text("Export preset...").size(12));
+    if can_export {
+        export_btn = export_btn.on_press(Message::PresetExport(tab));
+    }
+    row![
+        button(text("Import preset...").size(12)).on_press(Message::PresetImport),
+        export_btn,
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// `question` with Confirm / Cancel buttons for the deletion awaiting
+/// confirmation.
+pub fn confirm_delete<'a>(question: String) -> Element<'a, Message> {
+    row![
+        text(question).size(12),
+        button(text("Confirm")).on_press(Message::DeleteConfirm),
+        button(text("Cancel")).on_press(Message::DeleteCancel),
+    ]
+    .spacing(4)
+    .into()
+}
+
+/// Server model a tab generates with; `None` for tabs that don't generate.
+pub fn required_model(tab: TabId) -> Option<&'static str> {
+    match tab {
+        TabId::Clone | TabId::Upload | TabId::MultiSpeaker => Some("base"),
+        TabId::VoiceDesign => Some("voice_design"),
+        TabId::CustomVoice => Some("custom_voice"),
+        TabId::Settings => None,
+    }
+}
+
+/// Hint shown next to a Generate button whose model isn't loaded.
+pub fn missing_model_hint(tab: TabId) -> String {
+    let model = required_model(tab).unwrap_or_default();
+    format!("Enable the {model} model in Settings")
+}
+
+/// `tab`'s Generate button. When the tab's model isn't loaded, a tooltip
+/// explains why it is disabled and a hint points at Settings.
+pub fn generate_button(
+    btn: Button<'_, Message>,
+    tab: TabId,
+    model_available: bool,
+) -> Element<'_, Message> {
+    if model_available {
+        return btn.into();
+    }
+    let model = required_model(tab).unwrap_or_default();
+    row![
+        tooltip(
+            btn,
+            container(text(format!("The {model} model isn't loaded on the server")).size(12))
+                .padding(6)
+                .style(container::rounded_box),
+            tooltip::Position::Top,
+        ),
+        text(missing_model_hint(tab)).size(12),
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Progress bar for `task`, with a Cancel button while it is still running.
+pub fn progress_row(task: &ActiveTask) -> Element<'_, Message> {
+    #[allow(clippy::cast_precision_loss)]
+    let progress_value = task.progress as f32;
+    let mut content = row![progress_bar(0.0..=100.0, progress_value)].spacing(8);
+    if task.status == TaskStatus::Processing {
+        content = content.push(
+            button(text("Cancel").size(12)).on_press(Message::TaskCancel(task.task_id.clone())),
+        );
+    }
+    content.into()
+}
+
+/// Format a duration as `mm:ss`.
+fn format_clock(d: std::time::Duration) -> String {
+    let secs = d.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// Playback time as `mm:ss / mm:ss`, or just the position when the clip
+/// length is unknown.
+pub fn format_playback_time(
+    position: std::time::Duration,
+    total: Option<std::time::Duration>,
+) -> String {
+    match total {
+        Some(total) => format!("{} / {}", format_clock(position), format_clock(total)),
+        None => format_clock(position),
+    }
+}
+
+/// Elapsed time of `task`, followed by the server's estimate while it runs,
+/// e.g. "Elapsed: 00:05 (~12s estimated)". Past the estimate it says so
+/// rather than counting down below zero.
+pub fn elapsed_label(task: &ActiveTask) -> String {
+    let elapsed = format!(
+        "Elapsed: {:02}:{:02}",
+        task.elapsed_secs / 60,
+        task.elapsed_secs % 60
+    );
+    match task.estimated_secs {
+        Some(estimate) if task.status == TaskStatus::Processing && estimate > 0.0 => {
+            #[allow(clippy::cast_precision_loss)]
+            let overdue = task.elapsed_secs as f64 > estimate;
+            if overdue {
+                format!("{elapsed} (taking longer than expected...)")
+            } else {
+                format!("{elapsed} (~{estimate:.0}s estimated)")
+            }
+        }
+        _ => elapsed,
+    }
+}
+
+/// A server `created_at` (Unix seconds as a string) as a local
+/// "2024-06-01 14:32", or the raw string when it doesn't parse.
+pub fn format_created_at(raw: &str) -> String {
+    format_created_in(raw, &chrono::Local)
+}
+
+fn format_created_in<Tz: chrono::TimeZone>(raw: &str, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    raw.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite())
+        .and_then(|secs| {
+            #[allow(clippy::cast_possible_truncation)]
+            chrono::DateTime::from_timestamp(secs.floor() as i64, 0)
+        })
+        .map_or_else(
+            || raw.to_owned(),
+            |time| time.with_timezone(tz).format("%Y-%m-%d %H:%M").to_string(),
+        )
+}
+
+/// Format a request latency: milliseconds under a second, seconds otherwise.
+pub fn format_latency(d: std::time::Duration) -> String {
+    if d.as_millis() < 1000 {
+        format!("{} ms", d.as_millis())
+    } else {
+        format!("{:.2} s", d.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::reference;
+
+    fn named(name: &str, ref_text: Option<&str>) -> ReferenceAudio {
+        ReferenceAudio {
+            ref_text: ref_text.map(str::to_owned),
+            ..reference(&format!("{name}-id"), name)
+        }
+    }
+
+    #[test]
+    fn reference_options_flag_missing_transcripts() {
+        let options = reference_options(&[
+            named("Alice", Some("Hello there")),
+            named("Bob", None),
+            named("Carol", Some("  ")),
+        ]);
+        let labels: Vec<String> = options.iter().map(ToString::to_string).collect();
+        assert_eq!(labels, vec!["Alice", "Bob (no transcript)", "Carol (no transcript)"]);
+    }
+
+    #[test]
+    fn selected_option_matches_by_name() {
+        let options = reference_options(&[named("Alice", None)]);
+        let selected = selected_option(&options, Some(&"Alice".to_owned()));
+        assert_eq!(selected.as_ref().map(|o| o.name.as_str()), Some("Alice"));
+        assert_eq!(selected.map(|o| o.id), Some("Alice-id".to_owned()));
+        assert!(selected_option(&options, Some(&"Zed".to_owned())).is_none());
+        assert!(selected_option(&options, None).is_none());
+    }
+
+    #[test]
+    fn over_soft_limit_counts_chars() {
+        assert!(!over_soft_limit("", 0));
+        assert!(!over_soft_limit("hello", 5));
+        assert!(over_soft_limit("hello!", 5));
+        // Five characters, fifteen bytes.
+        assert!(!over_soft_limit("こんにちは", 5));
+    }
+
+    #[test]
+    fn format_playback_time_with_total() {
+        assert_eq!(
+            format_playback_time(
+                std::time::Duration::from_secs(65),
+                Some(std::time::Duration::from_secs(200))
+            ),
+            "01:05 / 03:20"
+        );
+    }
+
+    #[test]
+    fn format_playback_time_unknown_total() {
+        assert_eq!(
+            format_playback_time(std::time::Duration::from_millis(9_900), None),
+            "00:09"
+        );
+    }
+
+    #[test]
+    fn missing_model_hint_names_model() {
+        assert_eq!(
+            missing_model_hint(TabId::CustomVoice),
+            "Enable the custom_voice model in Settings"
+        );
+    }
+
+    #[test]
+    fn required_model_per_tab() {
+        assert_eq!(required_model(TabId::Upload), Some("base"));
+        assert_eq!(required_model(TabId::VoiceDesign), Some("voice_design"));
+        assert_eq!(required_model(TabId::CustomVoice), Some("custom_voice"));
+        assert_eq!(required_model(TabId::Settings), None);
+    }
+
+    #[test]
+    fn elapsed_label_shows_estimate_until_exceeded() {
+        let mut task = ActiveTask::new("t".to_owned(), TabId::Clone);
+        task.elapsed_secs = 5;
+        assert_eq!(elapsed_label(&task), "Elapsed: 00:05");
+
+        task.estimated_secs = Some(12.4);
+        assert_eq!(elapsed_label(&task), "Elapsed: 00:05 (~12s estimated)");
+
+        task.elapsed_secs = 13;
+        assert_eq!(elapsed_label(&task), "Elapsed: 00:13 (taking longer than expected...)");
+
+        task.status = TaskStatus::Completed;
+        assert_eq!(elapsed_label(&task), "Elapsed: 00:13");
+    }
+
+    #[test]
+    fn created_at_formats_as_date() {
+        assert_eq!(format_created_in("1717252320.5", &chrono::Utc), "2024-06-01 14:32");
+        assert_eq!(format_created_in(" 0 ", &chrono::Utc), "1970-01-01 00:00");
+    }
+
+    #[test]
+    fn malformed_created_at_is_shown_raw() {
+        for raw in ["yesterday", "2026-01-01T00:00:00", "NaN", "1e300", ""] {
+            assert_eq!(format_created_in(raw, &chrono::Utc), raw);
+        }
+    }
+
+    #[test]
+    fn format_latency_millis() {
+        assert_eq!(format_latency(std::time::Duration::from_millis(0)), "0 ms");
+        assert_eq!(format_latency(std::time::Duration::from_millis(245)), "245 ms");
+        assert_eq!(format_latency(std::time::Duration::from_millis(999)), "999 ms");
+    }
+
+    #[test]
+    fn format_latency_seconds() {
+        assert_eq!(format_latency(std::time::Duration::from_millis(1000)), "1.00 s");
+        assert_eq!(format_latency(std::time::Duration::from_millis(2345)), "2.35 s");
+    }
+
+    #[test]
+    fn submit_generates_when_enabled_and_allowed() {
+        assert!(matches!(
+            submit_message(true, true, Message::CloneGenerate),
+            Some(Message::CloneGenerate)
+        ));
+    }
+
+    #[test]
+    fn submit_ignored_when_flag_off() {
+        assert!(submit_message(false, true, Message::CloneGenerate).is_none());
+    }
+
+    #[test]
+    fn submit_ignored_when_guard_fails() {
+        assert!(submit_message(true, false, Message::UploadGenerate).is_none());
+    }
+
+    #[test]
+    fn truncate_short_text_unchanged() {
+        assert_eq!(truncate_text("hello", 60), "hello");
+    }
+
+    #[test]
+    fn truncate_ascii() {
+        assert_eq!(truncate_text("abcdef", 3), "abc...");
+    }
+
+    #[test]
+    fn truncate_multibyte_boundary() {
+        // A one-byte prefix puts byte 60 in the middle of a 3-byte char.
+        let text = format!("a{}", "日本語".repeat(30));
+        assert!(!text.is_char_boundary(60));
+
+        let truncated = truncate_text(&text, 60);
+        assert_eq!(truncated.chars().count(), 63);
+        assert!(truncated.ends_with("..."));
+        assert!(truncated.starts_with("a日本語"));
+    }
+
+    #[test]
+    fn truncate_exact_length_multibyte() {
+        let text = "こんにちは";
+        assert_eq!(truncate_text(text, 5), text);
+        assert_eq!(truncate_text(text, 2), "こん...");
+    }
+
+    #[test]
+    fn editor_string_drops_only_the_final_line_break() {
+        assert_eq!(editor_string("Hello\nworld\n".to_owned()), "Hello\nworld");
+        assert_eq!(editor_string("Hello\n\n".to_owned()), "Hello\n");
+        assert_eq!(editor_string("\n".to_owned()), "");
+        assert_eq!(editor_string(String::new()), "");
+    }
+}
@@ -1,14 +1,18 @@
-use iced::widget::{button, column, pick_list, progress_bar, row, text, text_input};
+use iced::widget::{button, column, pick_list, row, text, text_input};
 use iced::{Element, Length};
 
 use crate::api::types::TaskStatus;
-use crate::audio::player::PlaybackState;
-use crate::message::{ActiveTask, Message};
+use crate::audio::player::{PlaybackState, PlaybackStatus};
+use crate::message::{ActiveTask, Message, TabId, summarize_request};
+use crate::views::common::{
+    EditorText, char_counter, elapsed_label, generate_button, preset_row, progress_row,
+    submit_message, text_area,
+};
 
 /// State specific to the Custom Voice tab.
 #[derive(Debug, Clone, Default)]
 pub struct CustomTabState {
-    pub text: String,
+    pub editor: EditorText,
     pub selected_speaker: Option<String>,
     pub selected_language: String,
     pub instruct: String,
@@ -17,24 +21,42 @@ pub struct CustomTabState {
 impl CustomTabState {
     pub fn new() -> Self {
         Self {
-            text: String::new(),
+            editor: EditorText::default(),
             selected_speaker: None,
             selected_language: "auto".to_owned(),
             instruct: String::new(),
         }
     }
+
+    /// Replace the text in the editor.
+    pub fn set_text(&mut self, text: &str) {
+        self.editor = EditorText::with_text(text);
+    }
+
+    /// Reset to `new()`, keeping the selected language.
+    pub fn clear(&mut self) {
+        let selected_language = std::mem::take(&mut self.selected_language);
+        *self = Self {
+            selected_language,
+            ..Self::new()
+        };
+    }
 }
 
 // LCOV_EXCL_START
 
 /// Build the Custom Voice tab view.
+#[allow(clippy::too_many_arguments)]
 pub fn view<'a>(
     state: &'a CustomTabState,
     speakers: &'a [String],
     languages: &'a [String],
     active_task: Option<&'a ActiveTask>,
-    playback: PlaybackState,
+    has_result: bool,
+    playback: PlaybackStatus,
     model_available: bool,
+    enter_to_generate: bool,
+    text_soft_limit: usize,
 ) -> Element<'a, Message> {
     let speaker_picker = pick_list(
         speakers.to_vec(),
@@ -50,10 +72,6 @@ pub fn view<'a>(
     )
     .placeholder("Language");
 
-    let text_field = text_input("Enter text to generate...", &state.text)
-        .on_input(Message::CustomTextChanged)
-        .width(Length::Fill);
-
     let instruct_field =
         text_input("Style instructions (optional, e.g. \"Speak slowly and calmly\")", &state.instruct)
             .on_input(Message::CustomInstructChanged)
@@ -63,24 +81,35 @@ pub fn view<'a>(
         .as_ref()
         .is_some_and(|t| t.status == TaskStatus::Processing);
     let can_generate =
-        !state.text.is_empty() && state.selected_speaker.is_some() && !is_generating && model_available;
+        !state.editor.is_empty() && state.selected_speaker.is_some() && model_available;
 
-    let mut generate_btn = button(text("Generate"));
+    let mut generate_btn = button(text(if is_generating { "Queue" } else { "Generate" }));
     if can_generate {
         generate_btn = generate_btn.on_press(Message::CustomGenerate);
     }
+    let text_field = text_area(
+        &state.editor,
+        Message::CustomTextAction,
+        submit_message(enter_to_generate, can_generate, Message::CustomGenerate),
+    );
 
     let mut content = column![
         text("Custom Voice").size(24),
+        preset_row(TabId::CustomVoice, state.selected_speaker.is_some()),
         text("Speaker").size(14),
         speaker_picker,
         text("Language").size(14),
         lang_picker,
         text("Text").size(14),
         text_field,
+        char_counter(&state.editor.text(), text_soft_limit),
         text("Style Instructions").size(14),
         instruct_field,
-        row![generate_btn].spacing(8),
+        row![
+            generate_button(generate_btn, TabId::CustomVoice, model_available),
+            button(text("Clear")).on_press(Message::CustomClear),
+        ]
+        .spacing(8),
     ]
     .spacing(8)
     .padding(20)
@@ -88,28 +117,21 @@ pub fn view<'a>(
 
     // Progress section
     if let Some(task) = active_task {
-        #[allow(clippy::cast_precision_loss)]
-        let progress_value = task.progress as f32;
         content = content
-            .push(progress_bar(0.0..=100.0, progress_value))
+            .push(progress_row(task))
             .push(text(&task.status_text).size(14))
-            .push(
-                text(format!(
-                    "Elapsed: {:02}:{:02}",
-                    task.elapsed_secs / 60,
-                    task.elapsed_secs % 60
-                ))
-                .size(12),
-            );
+            .push(text(elapsed_label(task)).size(12));
 
         if let Some(err) = &task.error {
             content = content.push(text(err).size(14));
         }
 
-        if task.status == TaskStatus::Completed && task.audio_data.is_some() {
-            content = content.push(super::clone_tab::playback_controls(playback));
+        if let Some(request) = &task.request {
+            content = content.push(text(summarize_request(request, &[])).size(11));
         }
-    } else if playback != PlaybackState::Stopped {
+    }
+
+    if has_result || playback.state != PlaybackState::Stopped {
         content = content.push(super::clone_tab::playback_controls(playback));
     }
 
@@ -1,14 +1,18 @@
-use iced::widget::{button, column, pick_list, progress_bar, row, text, text_input};
+use iced::widget::{button, column, pick_list, row, text, text_input};
 use iced::{Element, Length};
 
 use crate::api::types::TaskStatus;
-use crate::audio::player::PlaybackState;
-use crate::message::{ActiveTask, Message};
+use crate::audio::player::{PlaybackState, PlaybackStatus};
+use crate::message::{ActiveTask, Message, TabId, summarize_request};
+use crate::views::common::{
+    EditorText, char_counter, elapsed_label, generate_button, preset_row, progress_row,
+    submit_message, text_area,
+};
 
 /// State specific to the Voice Design tab.
 #[derive(Debug, Clone, Default)]
 pub struct DesignTabState {
-    pub text: String,
+    pub editor: EditorText,
     pub instruct: String,
     pub selected_language: String,
 }
@@ -16,22 +20,40 @@ pub struct DesignTabState {
 impl DesignTabState {
     pub fn new() -> Self {
         Self {
-            text: String::new(),
+            editor: EditorText::default(),
             instruct: String::new(),
             selected_language: "auto".to_owned(),
         }
     }
+
+    /// Replace the text in the editor.
+    pub fn set_text(&mut self, text: &str) {
+        self.editor = EditorText::with_text(text);
+    }
+
+    /// Reset to `new()`, keeping the selected language.
+    pub fn clear(&mut self) {
+        let selected_language = std::mem::take(&mut self.selected_language);
+        *self = Self {
+            selected_language,
+            ..Self::new()
+        };
+    }
 }
 
 // LCOV_EXCL_START
 
 /// Build the Voice Design tab view.
+#[allow(clippy::too_many_arguments)]
 pub fn view<'a>(
     state: &'a DesignTabState,
     languages: &'a [String],
     active_task: Option<&'a ActiveTask>,
-    playback: PlaybackState,
+    has_result: bool,
+    playback: PlaybackStatus,
     model_available: bool,
+    enter_to_generate: bool,
+    text_soft_limit: usize,
 ) -> Element<'a, Message> {
     let instruct_field =
         text_input("Describe the voice (e.g. \"A warm, friendly female voice\")", &state.instruct)
@@ -45,30 +67,37 @@ pub fn view<'a>(
     )
     .placeholder("Language");
 
-    let text_field = text_input("Enter text to generate...", &state.text)
-        .on_input(Message::DesignTextChanged)
-        .width(Length::Fill);
-
     let is_generating = active_task
         .as_ref()
         .is_some_and(|t| t.status == TaskStatus::Processing);
     let can_generate =
-        !state.text.is_empty() && !state.instruct.is_empty() && !is_generating && model_available;
+        !state.editor.is_empty() && !state.instruct.is_empty() && model_available;
 
-    let mut generate_btn = button(text("Generate"));
+    let mut generate_btn = button(text(if is_generating { "Queue" } else { "Generate" }));
     if can_generate {
         generate_btn = generate_btn.on_press(Message::DesignGenerate);
     }
+    let text_field = text_area(
+        &state.editor,
+        Message::DesignTextAction,
+        submit_message(enter_to_generate, can_generate, Message::DesignGenerate),
+    );
 
     let mut content = column![
         text("Voice Design").size(24),
+        preset_row(TabId::VoiceDesign, !state.instruct.is_empty()),
         text("Voice Description").size(14),
         instruct_field,
         text("Language").size(14),
         lang_picker,
         text("Text").size(14),
         text_field,
-        row![generate_btn].spacing(8),
+        char_counter(&state.editor.text(), text_soft_limit),
+        row![
+            generate_button(generate_btn, TabId::VoiceDesign, model_available),
+            button(text("Clear")).on_press(Message::DesignClear),
+        ]
+        .spacing(8),
     ]
     .spacing(8)
     .padding(20)
@@ -76,28 +105,21 @@ pub fn view<'a>(
 
     // Progress section
     if let Some(task) = active_task {
-        #[allow(clippy::cast_precision_loss)]
-        let progress_value = task.progress as f32;
         content = content
-            .push(progress_bar(0.0..=100.0, progress_value))
+            .push(progress_row(task))
             .push(text(&task.status_text).size(14))
-            .push(
-                text(format!(
-                    "Elapsed: {:02}:{:02}",
-                    task.elapsed_secs / 60,
-                    task.elapsed_secs % 60
-                ))
-                .size(12),
-            );
+            .push(text(elapsed_label(task)).size(12));
 
         if let Some(err) = &task.error {
             content = content.push(text(err).size(14));
         }
 
-        if task.status == TaskStatus::Completed && task.audio_data.is_some() {
-            content = content.push(super::clone_tab::playback_controls(playback));
+        if let Some(request) = &task.request {
+            content = content.push(text(summarize_request(request, &[])).size(11));
         }
-    } else if playback != PlaybackState::Stopped {
+    }
+
+    if has_result || playback.state != PlaybackState::Stopped {
         content = content.push(super::clone_tab::playback_controls(playback));
     }
 
@@ -1,14 +1,106 @@
-use iced::widget::{button, column, row, scrollable, text};
-use iced::Element;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use iced::widget::{
+    button, checkbox, column, pick_list, row, scrollable, slider, text, text_input,
+};
+use iced::{Element, Length};
 
 use crate::api::types::GeneratedAudio;
-use crate::message::Message;
+use crate::config::EXPORT_PEAK_RANGE;
+use crate::message::{Message, PendingDelete};
+use crate::views::common::{confirm_delete, format_created_at, truncate_text};
+
+/// Characters of generated text shown before the item is expanded.
+const PREVIEW_CHARS: usize = 60;
+
+/// Order of the generated list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+    #[default]
+    Newest,
+    Oldest,
+    /// Longest generation time first.
+    Slowest,
+    /// Shortest generation time first.
+    Fastest,
+}
+
+impl SortMode {
+    pub const ALL: [Self; 4] = [Self::Newest, Self::Oldest, Self::Slowest, Self::Fastest];
+}
+
+impl std::fmt::Display for SortMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Newest => "Newest first",
+            Self::Oldest => "Oldest first",
+            Self::Slowest => "Slowest first",
+            Self::Fastest => "Fastest first",
+        })
+    }
+}
+
+/// `created_at` as seconds; unparseable timestamps sort as oldest.
+fn created_secs(item: &GeneratedAudio) -> f64 {
+    item.created_at
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|t| t.is_finite())
+        .unwrap_or(f64::NEG_INFINITY)
+}
+
+/// Stable-sort `items` by `mode`. Items without a generation time go last
+/// when sorting by duration.
+pub fn sort_generated(items: &mut [GeneratedAudio], mode: SortMode) {
+    let duration = |item: &GeneratedAudio| item.generation_time_seconds.filter(|t| t.is_finite());
+    match mode {
+        SortMode::Newest => items.sort_by(|a, b| created_secs(b).total_cmp(&created_secs(a))),
+        SortMode::Oldest => items.sort_by(|a, b| created_secs(a).total_cmp(&created_secs(b))),
+        SortMode::Slowest | SortMode::Fastest => items.sort_by(|a, b| {
+            match (duration(a), duration(b)) {
+                (Some(x), Some(y)) if mode == SortMode::Slowest => y.total_cmp(&x),
+                (Some(x), Some(y)) => x.total_cmp(&y),
+                (x, y) => y.is_some().cmp(&x.is_some()),
+            }
+        }),
+    }
+}
+
+/// Whether `item`'s text or source name contains `query`, ignoring case.
+/// An empty query matches everything.
+pub fn matches_filter(item: &GeneratedAudio, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty()
+        || item.generated_text.to_lowercase().contains(&query)
+        || item
+            .ref_audio_name
+            .as_deref()
+            .is_some_and(|name| name.to_lowercase().contains(&query))
+}
 
 // LCOV_EXCL_START
 
-/// Build the generated audio list view.
-pub fn view(items: &[GeneratedAudio]) -> Element<'_, Message> {
-    if items.is_empty() {
+/// Build the generated audio list view. `pending_delete` is the deletion
+/// awaiting confirmation; `last_deleted` is the one that can still be
+/// undone. `export_peak_db` is set when exports are normalized. `playing`
+/// is the clip currently being played.
+#[allow(clippy::too_many_arguments)]
+pub fn view<'a>(
+    items: &'a [GeneratedAudio],
+    expanded: &HashSet<String>,
+    filter: &'a str,
+    sort: SortMode,
+    selected: &HashSet<String>,
+    pending_delete: Option<&PendingDelete>,
+    last_deleted: Option<&'a GeneratedAudio>,
+    include_reference: bool,
+    export_peak_db: Option<f32>,
+    last_export: Option<&'a (usize, PathBuf)>,
+    playing: Option<&str>,
+) -> Element<'a, Message> {
+    if items.is_empty() && last_deleted.is_none() {
         return column![].into();
     }
 
@@ -16,51 +108,270 @@ pub fn view(items: &[GeneratedAudio]) -> Element<'_, Message> {
         row![
             text("Generated Audio").size(18),
             button(text("Refresh")).on_press(Message::RefreshGeneratedList),
+            button(text("Export all...")).on_press(Message::GeneratedExportAll),
+            pick_list(SortMode::ALL, Some(sort), Message::GeneratedSortChanged).text_size(13),
+            checkbox(include_reference)
+                .label("Include reference audio")
+                .on_toggle(Message::ExportIncludeReferenceToggled),
         ]
         .spacing(8),
+        export_options(export_peak_db),
+        text_input("Filter by text or source...", filter)
+            .on_input(Message::GeneratedFilterChanged)
+            .size(13),
     ]
     .spacing(4);
 
-    for item in items {
-        list = list.push(item_row(item));
+    let visible: Vec<&GeneratedAudio> =
+        items.iter().filter(|item| matches_filter(item, filter)).collect();
+    let all_selected = !visible.is_empty() && visible.iter().all(|i| selected.contains(&i.id));
+    let selected_shown = visible.iter().filter(|i| selected.contains(&i.id)).count();
+    let delete_selected: Element<'_, Message> =
+        if pending_delete == Some(&PendingDelete::SelectedGenerated) {
+            confirm_delete(format!("Delete {selected_shown} selected?"))
+        } else {
+            let mut delete_btn =
+                button(text(format!("Delete selected ({selected_shown})")).size(13));
+            if selected_shown > 0 {
+                delete_btn = delete_btn.on_press(Message::GeneratedDeleteSelected);
+            }
+            delete_btn.into()
+        };
+    list = list.push(
+        row![
+            checkbox(all_selected)
+                .label("Select all shown")
+                .on_toggle(|_| Message::GeneratedSelectAllVisible)
+                .text_size(13),
+            delete_selected,
+        ]
+        .spacing(8),
+    );
+
+    if let Some(deleted) = last_deleted {
+        list = list.push(
+            row![
+                text(format!(
+                    "Deleted \"{}\". Undo restores it; it is removed from the server after a \
+                     few seconds.",
+                    truncate_text(&deleted.generated_text, 30)
+                ))
+                .size(12),
+                button(text("Undo").size(12)).on_press(Message::GeneratedUndoDelete),
+            ]
+            .spacing(8),
+        );
+    }
+
+    if let Some((count, path)) = last_export {
+        list = list.push(
+            row![
+                text(format!("Exported {count} file(s) to {}", path.display())).size(11),
+                button(text("Show in folder").size(11))
+                    .on_press(Message::RevealPath(path.clone())),
+            ]
+            .spacing(8),
+        );
+    }
+
+    for item in &visible {
+        list = list.push(item_row(
+            item,
+            expanded.contains(&item.id),
+            selected.contains(&item.id),
+            matches!(pending_delete, Some(PendingDelete::Generated(id)) if *id == item.id),
+            playing == Some(item.id.as_str()),
+        ));
+    }
+    if visible.is_empty() {
+        list = list.push(text("No generations match the filter.").size(12));
     }
 
     scrollable(list).into()
 }
 
-/// Render a single generated audio item.
-fn item_row(item: &GeneratedAudio) -> Element<'_, Message> {
+/// "Normalize on export" toggle, with the target peak level while it is on.
+fn export_options<'a>(peak_db: Option<f32>) -> Element<'a, Message> {
+    let mut options = row![
+        checkbox(peak_db.is_some())
+            .label("Normalize on export")
+            .on_toggle(Message::ExportNormalizeToggled)
+            .text_size(13),
+    ]
+    .spacing(8);
+    if let Some(db) = peak_db {
+        options = options.push(text(format!("Peak: {db:.0} dB")).size(12)).push(
+            slider(EXPORT_PEAK_RANGE, db, Message::ExportPeakChanged)
+                .on_release(Message::ConfigSliderReleased)
+                .step(1.0)
+                .width(Length::Fixed(150.0)),
+        );
+    }
+    options.into()
+}
+
+/// Render a single generated audio item, with its full text when
+/// `expanded`, a confirmation prompt in place of Delete when `confirming`,
+/// and its Play button marked while `playing`.
+fn item_row(
+    item: &GeneratedAudio,
+    expanded: bool,
+    selected: bool,
+    confirming: bool,
+    playing: bool,
+) -> Element<'_, Message> {
     let label = item
         .ref_audio_name
         .as_deref()
         .unwrap_or("Unknown source");
 
-    let truncated_text = if item.generated_text.len() > 60 {
-        format!("{}...", &item.generated_text[..60])
-    } else {
+    let is_long = item.generated_text.chars().count() > PREVIEW_CHARS;
+    let shown_text = if expanded {
         item.generated_text.clone()
+    } else {
+        truncate_text(&item.generated_text, PREVIEW_CHARS)
     };
 
     let time_text = item
         .generation_time_seconds
         .map_or(String::new(), |t| format!("{t:.1}s"));
 
-    let play_btn = button(text("Play")).on_press(Message::GeneratedPlay(item.id.clone()));
-    let delete_btn = button(text("Delete")).on_press(Message::GeneratedDelete(item.id.clone()));
+    let play_label = if playing { "Playing" } else { "Play" };
+    let play_btn = button(text(play_label)).on_press(Message::GeneratedPlay(item.id.clone()));
+    let mut copy_btn = button(text("Copy"));
+    if !item.generated_text.is_empty() {
+        copy_btn = copy_btn.on_press(Message::CopyToClipboard(item.generated_text.clone()));
+    }
+    let mut regenerate_btn = button(text("Regenerate"));
+    if item.ref_audio_id.is_some() {
+        regenerate_btn = regenerate_btn.on_press(Message::GeneratedRegenerate(item.id.clone()));
+    }
+    let export_btn = button(text("Export")).on_press(Message::GeneratedExport(item.id.clone()));
+    let delete: Element<'_, Message> = if confirming {
+        confirm_delete(format!(
+            "Delete \"{}\"?",
+            truncate_text(&item.generated_text, 20)
+        ))
+    } else {
+        button(text("Delete"))
+            .on_press(Message::GeneratedDelete(item.id.clone()))
+            .into()
+    };
+
+    let mut text_col = column![
+        text(label).size(13),
+        text(shown_text).size(11),
+    ]
+    .spacing(2)
+    .width(Length::Fill);
+    if is_long {
+        text_col = text_col.push(
+            button(text(if expanded { "Show less" } else { "Show more" }).size(11))
+                .on_press(Message::GeneratedToggleExpand(item.id.clone())),
+        );
+    }
 
+    let id = item.id.clone();
     row![
-        column![
-            text(label).size(13),
-            text(truncated_text).size(11),
-        ]
-        .spacing(2)
-        .width(iced::Length::Fill),
+        checkbox(selected).on_toggle(move |_| Message::GeneratedToggleSelect(id.clone())),
+        text_col,
+        text(format_created_at(&item.created_at)).size(11),
         text(time_text).size(11),
         play_btn,
-        delete_btn,
+        copy_btn,
+        regenerate_btn,
+        export_btn,
+        delete,
     ]
     .spacing(8)
     .into()
 }
 
 // LCOV_EXCL_STOP
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, source: Option<&str>) -> GeneratedAudio {
+        GeneratedAudio {
+            id: "gen-1".to_owned(),
+            filename: "gen-1.wav".to_owned(),
+            ref_audio_id: None,
+            ref_audio_name: source.map(str::to_owned),
+            generated_text: text.to_owned(),
+            created_at: "1700000000.0".to_owned(),
+            generation_time_seconds: None,
+        }
+    }
+
+    #[test]
+    fn filter_matches_text_case_insensitively() {
+        let item = item("Hello World", None);
+        assert!(matches_filter(&item, "world"));
+        assert!(matches_filter(&item, "  HELLO "));
+        assert!(!matches_filter(&item, "goodbye"));
+    }
+
+    #[test]
+    fn filter_matches_source_name() {
+        let item = item("こんにちは", Some("Alice.wav"));
+        assert!(matches_filter(&item, "alice"));
+        assert!(matches_filter(&item, "こんにちは"));
+        assert!(!matches_filter(&item, "bob"));
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        assert!(matches_filter(&item("", None), ""));
+        assert!(matches_filter(&item("text", None), "   "));
+    }
+
+    fn timed(id: &str, created_at: &str, secs: Option<f64>) -> GeneratedAudio {
+        GeneratedAudio {
+            id: id.to_owned(),
+            created_at: created_at.to_owned(),
+            generation_time_seconds: secs,
+            ..item("", None)
+        }
+    }
+
+    fn ids(items: &[GeneratedAudio]) -> Vec<&str> {
+        items.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    #[test]
+    fn sort_by_creation_time() {
+        let mut items = vec![
+            timed("a", "1700000000.5", None),
+            timed("bad", "yesterday", None),
+            timed("b", "1700000100.0", None),
+        ];
+        sort_generated(&mut items, SortMode::Newest);
+        assert_eq!(ids(&items), vec!["b", "a", "bad"]);
+        sort_generated(&mut items, SortMode::Oldest);
+        assert_eq!(ids(&items), vec!["bad", "a", "b"]);
+    }
+
+    #[test]
+    fn sort_by_duration_puts_unknown_last() {
+        let mut items = vec![
+            timed("none", "1", None),
+            timed("fast", "2", Some(1.5)),
+            timed("slow", "3", Some(9.0)),
+        ];
+        sort_generated(&mut items, SortMode::Slowest);
+        assert_eq!(ids(&items), vec!["slow", "fast", "none"]);
+        sort_generated(&mut items, SortMode::Fastest);
+        assert_eq!(ids(&items), vec!["fast", "slow", "none"]);
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let mut items = vec![timed("x", "5", Some(2.0)), timed("y", "5", Some(2.0))];
+        sort_generated(&mut items, SortMode::Newest);
+        assert_eq!(ids(&items), vec!["x", "y"]);
+        sort_generated(&mut items, SortMode::Slowest);
+        assert_eq!(ids(&items), vec!["x", "y"]);
+    }
+}
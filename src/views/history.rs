@@ -0,0 +1,55 @@
+use iced::widget::{button, column, row, text};
+use iced::{Element, Length};
+
+use crate::history::HistoryEntry;
+use crate::message::{Message, TabId};
+use crate::views::common::truncate_text;
+
+/// How many of the most recent entries the panel lists.
+const VISIBLE_ENTRIES: usize = 20;
+
+// LCOV_EXCL_START
+
+/// Build the generation history panel, newest first.
+pub fn view(history: &[HistoryEntry]) -> Element<'_, Message> {
+    if history.is_empty() {
+        return column![].into();
+    }
+
+    let mut list = column![text("History").size(18)].spacing(4);
+    for (index, entry) in history.iter().enumerate().rev().take(VISIBLE_ENTRIES) {
+        list = list.push(entry_row(index, entry));
+    }
+    list.into()
+}
+
+/// Render one history entry with its Re-run button.
+fn entry_row(index: usize, entry: &HistoryEntry) -> Element<'_, Message> {
+    let source = match entry.tab {
+        TabId::Clone => "Clone",
+        TabId::Upload => "Upload",
+        TabId::MultiSpeaker => "Multi-Speaker",
+        TabId::VoiceDesign => "Voice Design",
+        TabId::CustomVoice => "Custom Voice",
+        TabId::Settings => "Settings",
+    };
+    let label = match &entry.ref_name {
+        Some(name) => format!("{source} | {name} | lang: {}", entry.language),
+        None => format!("{source} | lang: {}", entry.language),
+    };
+
+    row![
+        column![
+            text(label).size(13),
+            text(truncate_text(&entry.text, 60)).size(11),
+        ]
+        .spacing(2)
+        .width(Length::Fill),
+        text(format!("{}s", entry.generation_secs)).size(11),
+        button(text("Re-run")).on_press(Message::HistoryRerun(index)),
+    ]
+    .spacing(8)
+    .into()
+}
+
+// LCOV_EXCL_STOP
@@ -4,5 +4,8 @@ pub mod multispeaker_tab;
 pub mod design_tab;
 pub mod custom_tab;
 pub mod generated_list;
+pub mod history;
+pub mod references;
 pub mod settings;
 pub mod common;
+pub mod waveform;
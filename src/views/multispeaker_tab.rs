@@ -1,9 +1,15 @@
-use iced::widget::{button, column, pick_list, progress_bar, row, text, text_input};
+use iced::widget::{button, checkbox, column, pick_list, row, text, text_input};
 use iced::{Element, Length};
 
 use crate::api::types::{ReferenceAudio, TaskStatus};
-use crate::audio::player::PlaybackState;
-use crate::message::{ActiveTask, Message};
+use crate::audio::player::{PlaybackState, PlaybackStatus};
+use crate::message::{
+    ActiveTask, Message, SegmentStatus, TabId, TaskRequest, segment_statuses, summarize_request,
+};
+use crate::views::common::{
+    ReferenceOption, elapsed_label, generate_button, missing_ref_text_hint, progress_row,
+    reference_options, selected_option, submit_message,
+};
 
 /// A single segment in the multi-speaker list.
 #[derive(Debug, Clone)]
@@ -11,6 +17,8 @@ pub struct SegmentState {
     pub selected_ref: Option<String>,
     pub text: String,
     pub selected_language: String,
+    /// Speaker from an imported script that matched no reference name.
+    pub unmatched_speaker: Option<String>,
 }
 
 impl Default for SegmentState {
@@ -19,10 +27,70 @@ impl Default for SegmentState {
             selected_ref: None,
             text: String::new(),
             selected_language: "auto".to_owned(),
+            unmatched_speaker: None,
         }
     }
 }
 
+/// Parse a dialogue script with one `Speaker: text` line per segment.
+///
+/// Speakers are matched case-insensitively against `ref_names`; unknown ones
+/// leave `selected_ref` empty and are kept in `unmatched_speaker`. Lines
+/// without a speaker continue the previous segment, and blank lines are
+/// skipped.
+pub fn parse_script(contents: &str, ref_names: &[String]) -> Vec<SegmentState> {
+    let mut segments: Vec<SegmentState> = Vec::new();
+    for line in contents.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Some((speaker, text)) = split_speaker(line, ref_names) else {
+            match segments.last_mut() {
+                Some(last) => {
+                    last.text.push(' ');
+                    last.text.push_str(line);
+                }
+                None => segments.push(SegmentState {
+                    text: line.to_owned(),
+                    ..SegmentState::default()
+                }),
+            }
+            continue;
+        };
+        let selected_ref = ref_names
+            .iter()
+            .find(|name| name.eq_ignore_ascii_case(speaker))
+            .cloned();
+        segments.push(SegmentState {
+            unmatched_speaker: selected_ref.is_none().then(|| speaker.to_owned()),
+            selected_ref,
+            text: text.trim().to_owned(),
+            ..SegmentState::default()
+        });
+    }
+    segments
+}
+
+/// Split `line` into speaker and text at its first colon, if the part before
+/// it is a reference name or looks like one: a few words starting with a
+/// letter, followed by a space or the end of the line. Colons in ordinary
+/// text ("Note: ...", "10:30", URLs) then stay part of the text.
+fn split_speaker<'a>(line: &'a str, ref_names: &[String]) -> Option<(&'a str, &'a str)> {
+    const MAX_SPEAKER_CHARS: usize = 24;
+    const MAX_SPEAKER_WORDS: usize = 3;
+
+    let (speaker, text) = line.split_once(':')?;
+    let speaker = speaker.trim();
+    if ref_names.iter().any(|name| name.eq_ignore_ascii_case(speaker)) {
+        return Some((speaker, text));
+    }
+    let name_char =
+        |c: char| c.is_alphanumeric() || c.is_whitespace() || matches!(c, '-' | '_' | '.' | '\'');
+    let name_like = speaker.chars().next().is_some_and(char::is_alphabetic)
+        && speaker.chars().count() <= MAX_SPEAKER_CHARS
+        && speaker.split_whitespace().count() <= MAX_SPEAKER_WORDS
+        && speaker.chars().all(name_char);
+    let text_follows = text.is_empty() || text.starts_with(char::is_whitespace);
+    (name_like && text_follows).then_some((speaker, text))
+}
+
 /// State specific to the Multi-Speaker tab.
 #[derive(Debug, Clone)]
 pub struct MultiSpeakerTabState {
@@ -43,78 +111,121 @@ impl MultiSpeakerTabState {
     }
 }
 
+/// Checklist label for segment `index` (0-based) during generation.
+fn segment_label(index: usize, status: SegmentStatus, speaker: Option<&str>) -> String {
+    let speaker = speaker.map_or_else(String::new, |s| format!(" ({s})"));
+    let state = if status == SegmentStatus::Active { ": generating..." } else { "" };
+    format!("Segment {}{speaker}{state}", index + 1)
+}
+
+/// Display name of each segment's reference in the submitted `request`, so
+/// edits made to the tab after submitting don't relabel the checklist.
+fn request_speakers<'a>(
+    request: Option<&TaskRequest>,
+    references: &'a [ReferenceAudio],
+) -> Vec<Option<&'a str>> {
+    let Some(TaskRequest::MultiSpeaker(request)) = request else {
+        return Vec::new();
+    };
+    request
+        .segments
+        .iter()
+        .map(|s| {
+            references
+                .iter()
+                .find(|r| r.id == s.ref_audio_id)
+                .map(ReferenceAudio::display_name)
+        })
+        .collect()
+}
+
 // LCOV_EXCL_START
 
 /// Build the Multi-Speaker tab view.
+#[allow(clippy::too_many_arguments)]
 pub fn view<'a>(
     state: &'a MultiSpeakerTabState,
     references: &'a [ReferenceAudio],
     languages: &'a [String],
     active_task: Option<&'a ActiveTask>,
-    playback: PlaybackState,
+    has_result: bool,
+    playback: PlaybackStatus,
     model_available: bool,
+    enter_to_generate: bool,
 ) -> Element<'a, Message> {
-    let ref_names: Vec<String> = references
-        .iter()
-        .map(|r| {
-            r.name
-                .clone()
-                .unwrap_or_else(|| r.original_name.clone())
-        })
-        .collect();
+    let ref_options = reference_options(references);
 
     let mut content = column![text("Multi-Speaker").size(24),]
         .spacing(8)
         .padding(20)
         .width(Length::Fill);
 
-    for (i, segment) in state.segments.iter().enumerate() {
-        let segment_col = segment_view(i, segment, &ref_names, languages, state.segments.len());
-        content = content.push(segment_col);
-    }
-
-    let add_btn = button(text("+ Add Segment")).on_press(Message::MultiAddSegment);
-    content = content.push(add_btn);
-
     let is_generating = active_task
         .as_ref()
         .is_some_and(|t| t.status == TaskStatus::Processing);
     let can_generate = state.segments.iter().all(|s| {
         !s.text.is_empty() && s.selected_ref.is_some()
     }) && !state.segments.is_empty()
-        && !is_generating
         && model_available;
+    let on_submit = submit_message(enter_to_generate, can_generate, Message::MultiGenerate);
 
-    let mut generate_btn = button(text("Generate"));
+    for (i, segment) in state.segments.iter().enumerate() {
+        let segment_col = segment_view(
+            i,
+            segment,
+            &ref_options,
+            languages,
+            state.segments.len(),
+            on_submit.clone(),
+            playback.state == PlaybackState::Stopped,
+        );
+        content = content.push(segment_col);
+    }
+
+    let add_btn = button(text("+ Add Segment")).on_press(Message::MultiAddSegment);
+    let import_btn = button(text("Import script...")).on_press(Message::MultiImportScript);
+    content = content.push(row![add_btn, import_btn].spacing(8));
+
+    let mut generate_btn = button(text(if is_generating { "Queue" } else { "Generate" }));
     if can_generate {
         generate_btn = generate_btn.on_press(Message::MultiGenerate);
     }
-    content = content.push(row![generate_btn].spacing(8));
+    content = content.push(
+        row![generate_button(generate_btn, TabId::MultiSpeaker, model_available)].spacing(8),
+    );
 
     // Progress section
     if let Some(task) = active_task {
-        #[allow(clippy::cast_precision_loss)]
-        let progress_value = task.progress as f32;
         content = content
-            .push(progress_bar(0.0..=100.0, progress_value))
+            .push(progress_row(task))
             .push(text(&task.status_text).size(14))
-            .push(
-                text(format!(
-                    "Elapsed: {:02}:{:02}",
-                    task.elapsed_secs / 60,
-                    task.elapsed_secs % 60
-                ))
-                .size(12),
-            );
+            .push(text(elapsed_label(task)).size(12));
+
+        if task.status == TaskStatus::Processing
+            && let (Some(current), Some(total)) = (task.current_segment, task.total_segments)
+        {
+            content = content.push(text(format!("Segment {current}/{total}")).size(14));
+            let speakers = request_speakers(task.request.as_ref(), references);
+            for (i, status) in segment_statuses(current, total).into_iter().enumerate() {
+                let speaker = speakers.get(i).copied().flatten();
+                content = content.push(
+                    checkbox(status == SegmentStatus::Done)
+                        .label(segment_label(i, status, speaker))
+                        .text_size(12),
+                );
+            }
+        }
 
         if let Some(err) = &task.error {
             content = content.push(text(err).size(14));
         }
 
-        if task.status == TaskStatus::Completed && task.audio_data.is_some() {
-            content = content.push(super::clone_tab::playback_controls(playback));
+        if let Some(request) = &task.request {
+            content = content.push(text(summarize_request(request, references)).size(11));
         }
-    } else if playback != PlaybackState::Stopped {
+    }
+
+    if has_result || playback.state != PlaybackState::Stopped {
         content = content.push(super::clone_tab::playback_controls(playback));
     }
 
@@ -125,15 +236,22 @@ pub fn view<'a>(
 fn segment_view<'a>(
     index: usize,
     segment: &'a SegmentState,
-    ref_names: &[String],
+    ref_options: &[ReferenceOption],
     languages: &[String],
     total_segments: usize,
+    on_submit: Option<Message>,
+    can_preview: bool,
 ) -> Element<'a, Message> {
-    let ref_picker = pick_list(
-        ref_names.to_vec(),
-        segment.selected_ref.clone(),
-        move |name| Message::MultiRefSelected(index, name),
-    )
+    let selected = selected_option(ref_options, segment.selected_ref.as_ref());
+    let missing_ref_text = selected.clone().filter(|o| !o.has_ref_text);
+    // Audition the chosen reference, as the Clone tab's Preview does.
+    let mut preview_btn = button(text("Preview"));
+    if can_preview && let Some(option) = &selected {
+        preview_btn = preview_btn.on_press(Message::PlayReference(option.id.clone()));
+    }
+    let ref_picker = pick_list(ref_options.to_vec(), selected, move |option: ReferenceOption| {
+        Message::MultiRefSelected(index, option.name)
+    })
     .placeholder("Select reference audio...");
 
     let lang_picker = pick_list(
@@ -145,23 +263,129 @@ fn segment_view<'a>(
 
     let text_field = text_input("Enter text for this segment...", &segment.text)
         .on_input(move |t| Message::MultiTextChanged(index, t))
+        .on_submit_maybe(on_submit)
         .width(Length::Fill);
 
     let header_text = format!("Segment {}", index + 1);
 
-    let mut header_row = row![text(header_text).size(16)].spacing(8);
+    let mut up_btn = button(text("Up"));
+    if index > 0 {
+        up_btn = up_btn.on_press(Message::MultiMoveSegmentUp(index));
+    }
+    let mut down_btn = button(text("Down"));
+    if index + 1 < total_segments {
+        down_btn = down_btn.on_press(Message::MultiMoveSegmentDown(index));
+    }
+
+    let duplicate_btn = button(text("Duplicate")).on_press(Message::MultiDuplicateSegment(index));
+
+    let mut header_row =
+        row![text(header_text).size(16), up_btn, down_btn, duplicate_btn].spacing(8);
     if total_segments > 1 {
         header_row = header_row
             .push(button(text("Remove")).on_press(Message::MultiRemoveSegment(index)));
     }
 
-    column![
+    let mut segment_col = column![
         header_row,
-        row![ref_picker, lang_picker].spacing(8),
+        row![ref_picker, preview_btn, lang_picker].spacing(8),
         text_field,
     ]
-    .spacing(4)
-    .into()
+    .spacing(4);
+    if let Some(option) = &missing_ref_text {
+        segment_col = segment_col.push(missing_ref_text_hint(option));
+    }
+    if let Some(speaker) = &segment.unmatched_speaker {
+        segment_col = segment_col.push(
+            text(format!("No reference named \"{speaker}\"; pick one above."))
+                .size(12)
+                .style(text::danger),
+        );
+    }
+    segment_col.into()
 }
 
 // LCOV_EXCL_STOP
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names() -> Vec<String> {
+        vec!["Alice".to_owned(), "Bob".to_owned()]
+    }
+
+    #[test]
+    fn parse_script_matches_speakers() {
+        let segments = parse_script("Alice: Hi there.\nbob:  Hello!\n", &names());
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].selected_ref.as_deref(), Some("Alice"));
+        assert_eq!(segments[0].text, "Hi there.");
+        assert_eq!(segments[1].selected_ref.as_deref(), Some("Bob"));
+        assert_eq!(segments[1].text, "Hello!");
+        assert!(segments.iter().all(|s| s.unmatched_speaker.is_none()));
+    }
+
+    #[test]
+    fn parse_script_flags_unknown_speakers() {
+        let segments = parse_script("Carol: Who am I?", &names());
+        assert_eq!(segments[0].selected_ref, None);
+        assert_eq!(segments[0].unmatched_speaker.as_deref(), Some("Carol"));
+    }
+
+    #[test]
+    fn parse_script_joins_continuation_lines() {
+        let segments = parse_script("Alice: First part\n\nsecond part\nBob: Reply", &names());
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "First part second part");
+    }
+
+    #[test]
+    fn parse_script_keeps_colons_in_text() {
+        let script = "Alice: Meet at 10:30.\nSee https://example.com\n\
+                      A very long sentence that happens to have: a colon in it";
+        let segments = parse_script(script, &names());
+        assert_eq!(segments.len(), 1);
+        assert_eq!(
+            segments[0].text,
+            "Meet at 10:30. See https://example.com \
+             A very long sentence that happens to have: a colon in it"
+        );
+    }
+
+    #[test]
+    fn parse_script_empty() {
+        assert!(parse_script("\n  \n", &names()).is_empty());
+    }
+
+    #[test]
+    fn segment_label_names_speaker_and_active_segment() {
+        assert_eq!(segment_label(0, SegmentStatus::Done, Some("Alice")), "Segment 1 (Alice)");
+        assert_eq!(
+            segment_label(1, SegmentStatus::Active, Some("Bob")),
+            "Segment 2 (Bob): generating..."
+        );
+        assert_eq!(segment_label(2, SegmentStatus::Pending, None), "Segment 3");
+    }
+
+    #[test]
+    fn request_speakers_follow_the_submitted_request() {
+        let mut renamed = crate::test_fixtures::reference("ref-b", "bob.wav");
+        renamed.name = Some("Bob".to_owned());
+        let references = vec![crate::test_fixtures::reference("ref-a", "alice.wav"), renamed];
+        let segment = |id: &str| crate::api::types::MultiSpeakerSegment {
+            text: "Hi".to_owned(),
+            ref_audio_id: id.to_owned(),
+            ref_text: None,
+            language: "auto".to_owned(),
+        };
+        let request = TaskRequest::MultiSpeaker(crate::api::types::MultiSpeakerRequest {
+            segments: vec![segment("ref-b"), segment("ref-a"), segment("gone")],
+        });
+        assert_eq!(
+            request_speakers(Some(&request), &references),
+            vec![Some("Bob"), Some("alice.wav"), None]
+        );
+        assert!(request_speakers(None, &references).is_empty());
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use iced::widget::{button, column, row, text, text_input};
+use iced::{Element, Length};
+
+use crate::api::types::ReferenceAudio;
+use crate::message::{Message, PendingDelete};
+use crate::views::common::{confirm_delete, format_created_at};
+
+// LCOV_EXCL_START
+
+/// Build the reference audio management panel.
+///
+/// `name_drafts` holds in-progress edits keyed by reference id; references
+/// without a draft show their current name. A reference whose deletion is
+/// `pending_delete` asks for confirmation instead of showing Delete.
+pub fn view<'a>(
+    references: &'a [ReferenceAudio],
+    name_drafts: &'a HashMap<String, String>,
+    pending_delete: Option<&PendingDelete>,
+) -> Element<'a, Message> {
+    if references.is_empty() {
+        return column![].into();
+    }
+
+    let mut list = column![text("Reference Audio").size(18)].spacing(4);
+
+    for reference in references {
+        let confirm =
+            matches!(pending_delete, Some(PendingDelete::Reference(id)) if *id == reference.id);
+        list = list.push(reference_row(reference, name_drafts.get(&reference.id), confirm));
+    }
+
+    list.into()
+}
+
+/// Render a single reference with its rename field and delete button.
+fn reference_row<'a>(
+    reference: &'a ReferenceAudio,
+    draft: Option<&'a String>,
+    confirming: bool,
+) -> Element<'a, Message> {
+    let current = reference.display_name();
+    let value = draft.map_or(current, String::as_str);
+
+    let id = reference.id.clone();
+    let name_field = text_input("Reference name", value)
+        .on_input(move |name| Message::ReferenceNameChanged(id.clone(), name))
+        .on_submit(Message::ReferenceRename(reference.id.clone(), value.to_owned()))
+        .width(Length::Fill);
+
+    let mut rename_btn = button(text("Rename").size(12));
+    if value != current {
+        rename_btn =
+            rename_btn.on_press(Message::ReferenceRename(reference.id.clone(), value.to_owned()));
+    }
+
+    let delete: Element<'_, Message> = if confirming {
+        confirm_delete(format!("Delete \"{current}\"?"))
+    } else {
+        button(text("Delete").size(12))
+            .on_press(Message::ReferenceDelete(reference.id.clone()))
+            .into()
+    };
+
+    let mut fields = row![name_field, text(format_created_at(&reference.created_at)).size(12)]
+        .spacing(8);
+    if reference.ref_text.as_deref().is_none_or(|t| t.trim().is_empty()) {
+        fields = fields.push(text("No transcript").size(12));
+    }
+    fields.push(rename_btn).push(delete).into()
+}
+
+// LCOV_EXCL_STOP
@@ -1,13 +1,90 @@
-use iced::widget::{button, checkbox, column, row, text, text_input};
+use std::path::{Path, PathBuf};
+
+use iced::widget::{
+    button, checkbox, column, pick_list, progress_bar, row, slider, text, text_input,
+};
 use iced::{Element, Length};
 
-use crate::config::AppConfig;
+use crate::config::{AppConfig, MAX_REFERENCE_SECS_RANGE, MODEL_SIZES, SILENCE_THRESHOLD_RANGE};
+use crate::diagnostics::DiagnosticResult;
 use crate::message::Message;
+use crate::transcribe::whisper::{self, WhisperModel};
+
+/// Parse the port field, rejecting anything that isn't a usable TCP port.
+pub fn parse_port(input: &str) -> Result<u16, &'static str> {
+    match input.trim().parse::<u16>() {
+        Ok(0) => Err("Port must be between 1 and 65535"),
+        Ok(port) => Ok(port),
+        Err(_) => Err("Port must be a number between 1 and 65535"),
+    }
+}
+
+/// Warning for ports below 1024, which the server usually can't bind
+/// without elevated privileges.
+pub fn port_warning(port: u16) -> Option<&'static str> {
+    (port < 1024).then_some("Ports below 1024 usually need administrator rights to bind")
+}
+
+/// Disk space used by Whisper models and cached transcriptions.
+#[derive(Debug, Clone, Default)]
+pub struct StorageUsage {
+    pub cache_bytes: u64,
+    pub models: Vec<(WhisperModel, u64)>,
+    /// "Delete downloaded models" was clicked and awaits confirmation.
+    pub confirm_delete_models: bool,
+}
+
+impl StorageUsage {
+    /// Measure the current usage on disk under `data_dir`.
+    pub fn scan(data_dir: &Path) -> Self {
+        Self {
+            cache_bytes: whisper::cache_size(data_dir),
+            models: whisper::downloaded_models(data_dir),
+            confirm_delete_models: false,
+        }
+    }
+
+    /// Combined size of the downloaded models.
+    pub fn model_bytes(&self) -> u64 {
+        self.models.iter().map(|(_, size)| size).sum()
+    }
+}
+
+/// Format a byte count for display, e.g. "141.1 MB".
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
 
 // LCOV_EXCL_START
 
-/// Build the settings view.
-pub fn view(config: &AppConfig, dirty: bool) -> Element<'_, Message> {
+/// Build the settings view. `port_input` is the port field's text while it
+/// doesn't parse; Save is disabled until it does. `model_download` is the
+/// progress of a running Whisper model download.
+#[allow(clippy::too_many_arguments)]
+pub fn view<'a>(
+    config: &'a AppConfig,
+    port_input: Option<&'a str>,
+    dirty: bool,
+    device_hint: Option<String>,
+    device_options: &'a [String],
+    diagnostics: Option<&'a [DiagnosticResult]>,
+    diagnostics_running: bool,
+    paths: Vec<(String, PathBuf)>,
+    output_devices: &'a [String],
+    storage: &'a StorageUsage,
+    model_download: Option<(u64, u64)>,
+) -> Element<'a, Message> {
     let models = &config.server.models;
     let base_check = checkbox(models.contains(&"base".to_owned()))
         .label("base")
@@ -20,45 +97,372 @@ pub fn view(config: &AppConfig, dirty: bool) -> Element<'_, Message> {
         .on_toggle(|_| Message::SettingsModelToggled("custom_voice".to_owned()));
     let models_row = row![base_check, design_check, custom_check].spacing(16);
 
-    let device_field = text_input("auto", &config.server.device)
+    let mut sizes: Vec<String> = MODEL_SIZES.iter().map(|&s| s.to_owned()).collect();
+    if !sizes.contains(&config.server.model_size) {
+        // Keep a hand-edited size selectable rather than hiding it.
+        sizes.push(config.server.model_size.clone());
+    }
+    let size_picker = pick_list(
+        sizes,
+        Some(config.server.model_size.clone()),
+        Message::SettingsModelSizeChanged,
+    );
+
+    let device = &config.server.device;
+    let device_picker = pick_list(
+        device_options.to_vec(),
+        device_options.contains(device).then(|| device.clone()),
+        Message::SettingsDeviceChanged,
+    )
+    .placeholder("Custom");
+    // Free text stays available for devices the picker doesn't list.
+    let device_field = text_input("auto", device)
         .on_input(Message::SettingsDeviceChanged)
-        .width(Length::Fixed(200.0));
+        .width(Length::Fixed(120.0));
 
-    let port_field = text_input("8000", &config.server.port.to_string())
+    let port_text = port_input.map_or_else(|| config.server.port.to_string(), str::to_owned);
+    let port_error = port_input.and_then(|input| parse_port(input).err());
+    let port_field = text_input("8000", &port_text)
         .on_input(Message::SettingsPortChanged)
         .width(Length::Fixed(100.0));
+    let mut port_col = column![text("Port").size(14), port_field].spacing(4);
+    if let Some(err) = port_error {
+        port_col = port_col.push(text(err).size(12).style(text::danger));
+    } else if let Some(warning) = port_warning(config.server.port) {
+        port_col = port_col.push(text(warning).size(12));
+    }
+
+    let existing_server_toggle = checkbox(config.server.use_existing_server)
+        .label("Use a server already running on this port")
+        .on_toggle(Message::SettingsUseExistingServerToggled);
 
     let script_field = text_input("python/start_server.py", &config.server.script_path)
         .on_input(Message::SettingsScriptPathChanged)
         .width(Length::Fill);
 
+    let python_field = text_input(
+        "Leave empty to run via uv",
+        config.server.python.as_deref().unwrap_or_default(),
+    )
+    .on_input(Message::SettingsPythonPathChanged)
+    .width(Length::Fill);
+
     let dark_mode_toggle = checkbox(config.ui.dark_mode)
         .label("Dark Mode")
         .on_toggle(Message::SettingsDarkModeToggled);
 
+    let loudness_toggle = checkbox(config.ui.consistent_loudness)
+        .label("Consistent loudness for generated clips")
+        .on_toggle(Message::SettingsConsistentLoudnessToggled);
+
+    let normalize_preview_toggle = checkbox(config.audio.normalize_preview)
+        .label("Normalize reference previews")
+        .on_toggle(Message::SettingsNormalizePreviewToggled);
+
+    let convert_uploads_toggle = checkbox(config.audio.convert_uploads_to_wav)
+        .label("Convert uploads to WAV")
+        .on_toggle(Message::SettingsConvertUploadsToggled);
+
+    let enter_toggle = checkbox(config.ui.enter_to_generate)
+        .label("Press Enter to generate (Shift+Enter for a new line)")
+        .on_toggle(Message::SettingsEnterToGenerateToggled);
+
+    let stop_on_switch_toggle = checkbox(config.ui.stop_on_tab_switch)
+        .label("Stop playback when switching tabs")
+        .on_toggle(Message::SettingsStopOnTabSwitchToggled);
+
+    let auto_normalize_toggle = checkbox(config.ui.auto_normalize)
+        .label("Normalize recordings")
+        .on_toggle(Message::SettingsAutoNormalizeToggled);
+
+    let auto_trim_toggle = checkbox(config.ui.auto_trim_silence)
+        .label("Trim trailing silence from recordings")
+        .on_toggle(Message::SettingsAutoTrimSilenceToggled);
+
+    // Noisy rooms need a higher threshold so their hiss counts as silence.
+    let threshold_db = config.ui.silence_threshold_db();
+    let mut threshold_row =
+        row![text(format!("Silence threshold: {threshold_db:.0} dB")).size(12)].spacing(8);
+    if config.ui.auto_trim_silence {
+        threshold_row = threshold_row.push(
+            slider(SILENCE_THRESHOLD_RANGE, threshold_db, Message::SettingsSilenceThresholdChanged)
+                .on_release(Message::ConfigSliderReleased)
+                .step(1.0)
+                .width(Length::Fixed(200.0)),
+        );
+    }
+
+    let trim_refs_toggle = checkbox(config.ui.trim_long_references)
+        .label("Trim long references")
+        .on_toggle(Message::SettingsTrimLongReferencesToggled);
+    let mut max_ref_row = row![].spacing(8);
+    if let Some(max_secs) = config.ui.reference_trim_secs() {
+        max_ref_row = max_ref_row
+            .push(text(format!("Keep the first {max_secs:.0}s")).size(12))
+            .push(
+                slider(MAX_REFERENCE_SECS_RANGE, max_secs, Message::SettingsMaxReferenceSecsChanged)
+                    .on_release(Message::ConfigSliderReleased)
+                    .step(1.0)
+                    .width(Length::Fixed(200.0)),
+            );
+    }
+
+    let whisper_picker = pick_list(
+        WhisperModel::ALL,
+        Some(config.audio.whisper_model),
+        Message::SettingsWhisperModelSelected,
+    );
+
+    let output_picker = pick_list(
+        output_devices.to_vec(),
+        config.audio.output_device.clone(),
+        Message::SettingsOutputDeviceSelected,
+    )
+    .placeholder("Default output device");
+
     let mut save_btn = button(text("Save & Restart"));
-    if dirty {
+    if dirty && port_error.is_none() {
         save_btn = save_btn.on_press(Message::SettingsSave);
     }
 
-    column![
+    let mut content = column![
         text("Settings").size(24),
         text("Models").size(14),
         models_row,
+        row![text("Model size").size(14), size_picker].spacing(8),
         row![
-            column![text("Device").size(14), device_field].spacing(4),
-            column![text("Port").size(14), port_field].spacing(4),
+            column![
+                text("Device").size(14),
+                row![device_picker, device_field].spacing(8),
+            ]
+            .spacing(4),
+            port_col,
         ]
         .spacing(16),
-        text("Server Script Path").size(14),
-        script_field,
-        dark_mode_toggle,
-        row![save_btn].spacing(8),
     ]
     .spacing(8)
     .padding(20)
-    .width(Length::Fill)
+    .width(Length::Fill);
+
+    if let Some(hint) = device_hint {
+        content = content.push(text(hint).size(12));
+    }
+    content = content.push(existing_server_toggle);
+
+    let mut paths_section = column![
+        row![
+            text("Paths").size(14),
+            button(text("Open data folder").size(12))
+                .on_press(Message::RevealPath(crate::config::data_dir())),
+        ]
+        .spacing(8),
+    ]
+    .spacing(4);
+    for (label, path) in paths {
+        paths_section = paths_section.push(path_row(label, path));
+    }
+
+    content
+        .push(text("Server Script Path").size(14))
+        .push(script_field)
+        .push(text("Python Interpreter").size(14))
+        .push(python_field)
+        .push(dark_mode_toggle)
+        .push(loudness_toggle)
+        .push(enter_toggle)
+        .push(stop_on_switch_toggle)
+        .push(normalize_preview_toggle)
+        .push(convert_uploads_toggle)
+        .push(auto_normalize_toggle)
+        .push(auto_trim_toggle)
+        .push(threshold_row)
+        .push(trim_refs_toggle)
+        .push(max_ref_row)
+        .push(
+            row![
+                text("Whisper model").size(14),
+                whisper_picker,
+                model_status(config.audio.whisper_model, storage, model_download),
+            ]
+            .spacing(8),
+        )
+        .push(row![text("Output device").size(14), output_picker].spacing(8))
+        .push(paths_section)
+        .push(storage_section(storage))
+        .push(
+            row![
+                save_btn,
+                button(text("Reset to defaults")).on_press(Message::SettingsReset),
+                button(text("Restart Server")).on_press(Message::ServerRestart),
+            ]
+            .spacing(8),
+        )
+        .push(diagnostics_section(diagnostics, diagnostics_running))
+        .into()
+}
+
+/// Render the "Run Diagnostics" button and the latest results.
+fn diagnostics_section(
+    results: Option<&[DiagnosticResult]>,
+    running: bool,
+) -> Element<'_, Message> {
+    let mut run_btn = button(text(if running {
+        "Running diagnostics..."
+    } else {
+        "Run Diagnostics"
+    }));
+    if !running {
+        run_btn = run_btn.on_press(Message::DiagnosticsRun);
+    }
+
+    let mut section = column![text("Diagnostics").size(14), run_btn].spacing(4);
+    for r in results.unwrap_or_default() {
+        let mark = if r.passed { "PASS" } else { "FAIL" };
+        section = section.push(text(format!("[{mark}] {}: {}", r.name, r.detail)).size(12));
+        if let Some(fix) = r.remediation {
+            section = section.push(text(format!("    {fix}")).size(11));
+        }
+    }
+    section.into()
+}
+
+/// Render the selected Whisper model's download state: progress while
+/// downloading, "Model ready" once on disk, otherwise a download button.
+fn model_status<'a>(
+    model: WhisperModel,
+    storage: &StorageUsage,
+    download: Option<(u64, u64)>,
+) -> Element<'a, Message> {
+    if let Some((downloaded, total)) = download {
+        let label = if total > 0 {
+            format!("{} / {}", format_bytes(downloaded), format_bytes(total))
+        } else {
+            format_bytes(downloaded)
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let fraction = if total > 0 { downloaded as f32 / total as f32 } else { 0.0 };
+        row![
+            progress_bar(0.0..=1.0, fraction).length(Length::Fixed(160.0)),
+            text(label).size(12),
+        ]
+        .spacing(8)
+        .into()
+    } else if storage.models.iter().any(|(m, _)| *m == model) {
+        text("Model ready").size(12).into()
+    } else {
+        button(text("Download model"))
+            .on_press(Message::DownloadWhisperModel)
+            .into()
+    }
+}
+
+/// Render disk usage with buttons to reclaim it.
+fn storage_section(storage: &StorageUsage) -> Element<'_, Message> {
+    let mut clear_btn = button(text("Clear transcription cache"));
+    if storage.cache_bytes > 0 {
+        clear_btn = clear_btn.on_press(Message::ClearTranscriptionCache);
+    }
+    let cache_row = row![
+        text(format!("Transcription cache: {}", format_bytes(storage.cache_bytes)))
+            .size(12)
+            .width(Length::Fill),
+        clear_btn,
+    ]
+    .spacing(8);
+
+    let models_label = if storage.models.is_empty() {
+        "Whisper models: none downloaded".to_owned()
+    } else {
+        let names: Vec<String> = storage
+            .models
+            .iter()
+            .map(|(model, size)| format!("{model} ({})", format_bytes(*size)))
+            .collect();
+        format!("Whisper models: {}", names.join(", "))
+    };
+    let models_action: Element<'_, Message> = if storage.confirm_delete_models {
+        row![
+            text(format!(
+                "Delete {}? Models are re-downloaded on next use.",
+                format_bytes(storage.model_bytes())
+            ))
+            .size(12),
+            button(text("Delete")).on_press(Message::DeleteModelsConfirm),
+            button(text("Cancel")).on_press(Message::DeleteModelsCancel),
+        ]
+        .spacing(8)
+        .into()
+    } else {
+        let mut delete_btn = button(text("Delete downloaded models"));
+        if !storage.models.is_empty() {
+            delete_btn = delete_btn.on_press(Message::DeleteModels);
+        }
+        delete_btn.into()
+    };
+    let models_row = row![text(models_label).size(12).width(Length::Fill), models_action]
+        .spacing(8);
+
+    column![text("Storage").size(14), cache_row, models_row]
+        .spacing(4)
+        .into()
+}
+
+/// Render a labeled path with "Copy" and "Show in folder" buttons.
+fn path_row<'a>(label: String, path: PathBuf) -> Element<'a, Message> {
+    row![
+        text(format!("{label}: {}", path.display()))
+            .size(12)
+            .width(Length::Fill),
+        button(text("Copy").size(12))
+            .on_press(Message::CopyToClipboard(path.display().to_string())),
+        button(text("Show in folder").size(12)).on_press(Message::RevealPath(path)),
+    ]
+    .spacing(8)
     .into()
 }
 
 // LCOV_EXCL_STOP
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_port_accepts_valid_ports() {
+        assert_eq!(parse_port("8000"), Ok(8000));
+        assert_eq!(parse_port(" 65535 "), Ok(65535));
+        assert_eq!(parse_port("1"), Ok(1));
+    }
+
+    #[test]
+    fn parse_port_rejects_invalid_input() {
+        assert!(parse_port("0").is_err());
+        assert!(parse_port("65536").is_err());
+        assert!(parse_port("80a").is_err());
+        assert!(parse_port("").is_err());
+    }
+
+    #[test]
+    fn format_bytes_picks_unit() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(147_951_465), "141.1 MB");
+        assert_eq!(format_bytes(1_533_763_059), "1.4 GB");
+    }
+
+    #[test]
+    fn model_bytes_sums_models() {
+        let storage = StorageUsage {
+            models: vec![(WhisperModel::Tiny, 10), (WhisperModel::Base, 32)],
+            ..StorageUsage::default()
+        };
+        assert_eq!(storage.model_bytes(), 42);
+    }
+
+    #[test]
+    fn privileged_ports_warn() {
+        assert!(port_warning(80).is_some());
+        assert!(port_warning(1024).is_none());
+    }
+}
@@ -4,9 +4,14 @@ use iced::widget::{button, column, pick_list, progress_bar, row, text, text_inpu
 use iced::{Element, Length};
 
 use crate::api::types::TaskStatus;
-use crate::audio::player::PlaybackState;
+use crate::audio::player::{PlaybackState, PlaybackStatus};
 use crate::audio::recorder::RecordingState;
-use crate::message::{ActiveTask, Message};
+use crate::audio::wav::WavInfo;
+use crate::message::{ActiveTask, Message, TabId};
+use crate::views::common::{
+    EditorText, char_counter, elapsed_label, generate_button, progress_row, submit_message,
+    text_area,
+};
 
 /// State specific to the Upload & Clone tab.
 #[derive(Debug, Clone, Default)]
@@ -15,10 +20,27 @@ pub struct UploadTabState {
     pub file_bytes: Option<Vec<u8>>,
     pub file_name: Option<String>,
     pub file_hash: Option<String>,
-    pub text: String,
+    /// Header details of the selected audio; `None` for non-WAV files.
+    pub wav_info: Option<WavInfo>,
+    /// Min/max envelope of the selected audio; empty for non-WAV files.
+    pub waveform: Vec<(f32, f32)>,
+    pub editor: EditorText,
     pub selected_language: String,
     pub ref_text: Option<String>,
+    /// `ref_text` was edited since it was last cached.
+    pub ref_text_edited: bool,
     pub transcribing: bool,
+    /// Percent of the current transcription done.
+    pub transcription_progress: u8,
+    /// A "Save as reference" upload is in flight.
+    pub saving_reference: bool,
+    /// `file_bytes` holds a microphone take rather than a picked file.
+    pub recorded: bool,
+    /// Length in seconds of the selected audio before it was cut to
+    /// `[ui] max_reference_secs`.
+    pub trimmed_from_secs: Option<f32>,
+    /// Shown when the saved input device is missing and the default is used.
+    pub input_device_notice: Option<String>,
 }
 
 impl UploadTabState {
@@ -28,35 +50,114 @@ impl UploadTabState {
             file_bytes: None,
             file_name: None,
             file_hash: None,
-            text: String::new(),
+            wav_info: None,
+            waveform: Vec::new(),
+            editor: EditorText::default(),
             selected_language: "auto".to_owned(),
             ref_text: None,
+            ref_text_edited: false,
             transcribing: false,
+            transcription_progress: 0,
+            saving_reference: false,
+            recorded: false,
+            trimmed_from_secs: None,
+            input_device_notice: None,
         }
     }
+
+    /// Replace the text in the editor.
+    pub fn set_text(&mut self, text: &str) {
+        self.editor = EditorText::with_text(text);
+    }
+
+    /// Reset to `new()`, dropping the selected audio and its transcription
+    /// but keeping the selected language and any device notice.
+    pub fn clear(&mut self) {
+        let selected_language = std::mem::take(&mut self.selected_language);
+        let input_device_notice = self.input_device_notice.take();
+        *self = Self {
+            selected_language,
+            input_device_notice,
+            ..Self::new()
+        };
+    }
+
+    /// Whether the tab can be cleared: not while a transcription or
+    /// reference upload for the current file is still running.
+    pub fn can_clear(&self) -> bool {
+        !self.transcribing && !self.saving_reference
+    }
+
+    /// Whether the selected file can be saved as a reference: it needs a
+    /// finished transcription, and only one upload runs at a time.
+    pub fn can_save_reference(&self) -> bool {
+        self.file_bytes.is_some()
+            && self.ref_text.is_some()
+            && !self.transcribing
+            && !self.saving_reference
+    }
+}
+
+/// References shorter than this tend to clone poorly.
+const MIN_REFERENCE_SECS: f32 = 3.0;
+
+/// Summarize a clip as e.g. "3.2s, 44.1kHz, stereo".
+pub fn describe_wav(info: &WavInfo) -> String {
+    let channels = match info.channels {
+        1 => "mono".to_owned(),
+        2 => "stereo".to_owned(),
+        n => format!("{n} channels"),
+    };
+    format!(
+        "{:.1}s, {}kHz, {channels}",
+        info.duration_secs,
+        f64::from(info.sample_rate) / 1000.0
+    )
+}
+
+/// Warning shown for a reference too short to clone well.
+pub fn short_clip_warning(info: &WavInfo) -> Option<String> {
+    (info.duration_secs < MIN_REFERENCE_SECS).then(|| {
+        format!(
+            "Clips shorter than {MIN_REFERENCE_SECS:.0}s usually clone poorly; \
+             try a longer recording."
+        )
+    })
 }
 
 // LCOV_EXCL_START
 
 /// Build the Upload & Clone tab view.
+#[allow(clippy::too_many_arguments)]
 pub fn view<'a>(
     state: &'a UploadTabState,
     languages: &'a [String],
     active_task: Option<&'a ActiveTask>,
-    playback: PlaybackState,
+    submitting: bool,
+    has_result: bool,
+    playback: PlaybackStatus,
     recording: RecordingState,
     recording_elapsed: f32,
+    recording_level: f32,
+    input_devices: &'a [String],
+    input_device: Option<&'a String>,
     model_available: bool,
+    enter_to_generate: bool,
+    text_soft_limit: usize,
 ) -> Element<'a, Message> {
     let file_label = state
         .file_name
         .as_deref()
         .unwrap_or("No file selected");
 
-    let choose_btn = button(text("Choose File")).on_press(Message::UploadPickFile);
+    let mut choose_btn = button(text("Choose File"));
+    if !state.transcribing {
+        choose_btn = choose_btn.on_press(Message::UploadPickFile);
+    }
 
     // Record button
     let record_btn = match recording {
+        RecordingState::Idle if state.transcribing => button(text("Record")),
         RecordingState::Idle => button(text("Record")).on_press(Message::RecordStart),
         RecordingState::Recording => button(text("Stop Recording")).on_press(Message::RecordStop),
     };
@@ -68,14 +169,11 @@ pub fn view<'a>(
     )
     .placeholder("Language");
 
-    let text_field = text_input("Enter text to generate...", &state.text)
-        .on_input(Message::UploadTextChanged)
-        .width(Length::Fill);
-
-    let is_generating = active_task
-        .as_ref()
-        .is_some_and(|t| t.status == TaskStatus::Processing);
-    let can_generate = !state.text.is_empty()
+    let is_generating = submitting
+        || active_task
+            .as_ref()
+            .is_some_and(|t| t.status == TaskStatus::Processing);
+    let can_generate = !state.editor.is_empty()
         && state.file_bytes.is_some()
         && !is_generating
         && !state.transcribing
@@ -86,85 +184,175 @@ pub fn view<'a>(
     if can_generate {
         generate_btn = generate_btn.on_press(Message::UploadGenerate);
     }
+    let text_field = text_area(
+        &state.editor,
+        Message::UploadTextAction,
+        submit_message(enter_to_generate, can_generate, Message::UploadGenerate),
+    );
+
+    let mut clear_btn = button(text("Clear"));
+    if state.can_clear() && recording == RecordingState::Idle {
+        clear_btn = clear_btn.on_press(Message::UploadClear);
+    }
 
-    let mut file_row = row![choose_btn, record_btn, text(file_label).size(14)].spacing(8);
+    let mut save_ref_btn = button(text(if state.saving_reference {
+        "Saving..."
+    } else {
+        "Save as reference"
+    }));
+    if state.can_save_reference() {
+        save_ref_btn = save_ref_btn.on_press(Message::UploadSaveReference);
+    }
+
+    let mut file_row = row![choose_btn, record_btn].spacing(8);
+    if state.recorded && state.file_bytes.is_some() {
+        let mut preview_btn = button(text("Preview recording"));
+        if recording == RecordingState::Idle {
+            preview_btn = preview_btn.on_press(Message::PlayRecording);
+        }
+        file_row = file_row.push(preview_btn);
+    }
+    file_row = file_row.push(text(file_label).size(14));
+    if let Some(info) = &state.wav_info {
+        file_row = file_row.push(text(describe_wav(info)).size(12));
+    }
 
     if let Some(hash) = &state.file_hash {
         file_row = file_row.push(text(format!("SHA256: {}...", &hash[..8])).size(10));
     }
 
+    let device_picker = pick_list(
+        input_devices.to_vec(),
+        input_device.cloned(),
+        Message::RecordDeviceSelected,
+    )
+    .placeholder("Default input device");
+
     let mut content = column![
         text("Upload & Clone").size(24),
         text("Audio File").size(14),
         file_row,
+        row![text("Microphone").size(14), device_picker].spacing(8),
     ]
     .spacing(8)
     .padding(20)
     .width(Length::Fill);
 
+    if !state.waveform.is_empty() {
+        content = content.push(super::waveform::view(&state.waveform));
+    }
+
+    if let Some(warning) = state.wav_info.as_ref().and_then(short_clip_warning) {
+        content = content.push(text(warning).size(12).style(text::danger));
+    }
+
+    if let (Some(original), Some(info)) = (state.trimmed_from_secs, &state.wav_info) {
+        content = content.push(
+            text(format!(
+                "Trimmed from {original:.1}s to the first {:.1}s; the original file is \
+                 unchanged.",
+                info.duration_secs
+            ))
+            .size(12),
+        );
+    }
+
+    if let Some(notice) = &state.input_device_notice {
+        content = content.push(text(notice).size(12));
+    }
+
     // Recording elapsed time
     if recording == RecordingState::Recording {
         #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
         let secs = recording_elapsed as u64;
-        content = content.push(
-            text(format!("Recording... {:02}:{:02}", secs / 60, secs % 60)).size(12),
-        );
+        content = content
+            .push(text(format!("Recording... {:02}:{:02}", secs / 60, secs % 60)).size(12))
+            .push(progress_bar(0.0..=1.0, recording_level));
     }
 
     // Transcription status
     if state.transcribing {
-        content = content.push(text("Transcribing audio...").size(12));
+        content = content
+            .push(text(format!("Transcribing audio... {}%", state.transcription_progress)).size(12))
+            .push(progress_bar(0.0..=100.0, f32::from(state.transcription_progress)))
+            .push(
+                button(text("Cancel transcription").size(12))
+                    .on_press(Message::CancelTranscription),
+            );
     } else if let Some(ref_text) = &state.ref_text {
+        content = content.push(text("Transcription").size(14)).push(
+            text_input("Transcript of the audio above", ref_text)
+                .on_input(Message::UploadRefTextChanged)
+                .size(12)
+                .width(Length::Fill),
+        );
+    } else if state.file_bytes.is_some() {
         content = content.push(
-            text(format!("Transcription: {}", truncate_text(ref_text, 80))).size(12),
+            button(text("Retry transcription").size(12)).on_press(Message::RetryTranscription),
         );
     }
+    if state.file_bytes.is_some() {
+        content = content.push(save_ref_btn);
+    }
 
     content = content
         .push(text("Language").size(14))
         .push(lang_picker)
         .push(text("Text").size(14))
         .push(text_field)
-        .push(row![generate_btn].spacing(8));
+        .push(char_counter(&state.editor.text(), text_soft_limit))
+        .push(
+            row![
+                generate_button(generate_btn, TabId::Upload, model_available),
+                clear_btn,
+            ]
+            .spacing(8),
+        );
 
     // Progress section
     if let Some(task) = active_task {
-        #[allow(clippy::cast_precision_loss)]
-        let progress_value = task.progress as f32;
         content = content
-            .push(progress_bar(0.0..=100.0, progress_value))
+            .push(progress_row(task))
             .push(text(&task.status_text).size(14))
-            .push(
-                text(format!(
-                    "Elapsed: {:02}:{:02}",
-                    task.elapsed_secs / 60,
-                    task.elapsed_secs % 60
-                ))
-                .size(12),
-            );
+            .push(text(elapsed_label(task)).size(12));
 
         if let Some(err) = &task.error {
             content = content.push(text(err).size(14));
         }
+    }
 
-        if task.status == TaskStatus::Completed && task.audio_data.is_some() {
-            content = content.push(super::clone_tab::playback_controls(playback));
-        }
-    } else if playback != PlaybackState::Stopped {
+    if has_result || playback.state != PlaybackState::Stopped {
         content = content.push(super::clone_tab::playback_controls(playback));
     }
 
     content.into()
 }
 
-/// Truncate text for display, adding ellipsis if needed.
-fn truncate_text(s: &str, max_chars: usize) -> String {
-    if s.chars().count() <= max_chars {
-        s.to_owned()
-    } else {
-        let truncated: String = s.chars().take(max_chars).collect();
-        format!("{truncated}...")
+// LCOV_EXCL_STOP
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(duration_secs: f32, sample_rate: u32, channels: u16) -> WavInfo {
+        WavInfo {
+            sample_rate,
+            channels,
+            duration_secs,
+            rms: 0.1,
+        }
     }
-}
 
-// LCOV_EXCL_STOP
+    #[test]
+    fn describe_wav_formats_details() {
+        assert_eq!(describe_wav(&info(3.24, 44_100, 2)), "3.2s, 44.1kHz, stereo");
+        assert_eq!(describe_wav(&info(10.0, 16_000, 1)), "10.0s, 16kHz, mono");
+        assert_eq!(describe_wav(&info(1.0, 48_000, 6)), "1.0s, 48kHz, 6 channels");
+    }
+
+    #[test]
+    fn short_clips_warn() {
+        assert!(short_clip_warning(&info(2.5, 44_100, 1)).is_some());
+        assert!(short_clip_warning(&info(3.0, 44_100, 1)).is_none());
+    }
+}
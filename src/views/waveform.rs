@@ -0,0 +1,61 @@
+use iced::widget::canvas::{self, Canvas, Frame, Geometry};
+use iced::{Element, Length, Point, Rectangle, Renderer, Size, Theme, mouse};
+
+use crate::message::Message;
+
+/// Columns kept for a waveform thumbnail; wider than the tab usually is,
+/// so drawing never needs more detail.
+pub const WAVEFORM_COLUMNS: usize = 600;
+
+/// Height of the waveform thumbnail, in pixels.
+const HEIGHT: f32 = 48.0;
+
+/// Draws a clip's min/max envelope, one vertical bar per column.
+struct Waveform<'a> {
+    columns: &'a [(f32, f32)],
+}
+
+impl canvas::Program<Message> for Waveform<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        if self.columns.is_empty() {
+            return vec![frame.into_geometry()];
+        }
+        let color = theme.palette().primary;
+        let mid = bounds.height / 2.0;
+        #[allow(clippy::cast_precision_loss)]
+        let step = bounds.width / self.columns.len() as f32;
+        for (i, &(lo, hi)) in self.columns.iter().enumerate() {
+            let top = mid - hi.clamp(-1.0, 1.0) * mid;
+            let bottom = mid - lo.clamp(-1.0, 1.0) * mid;
+            #[allow(clippy::cast_precision_loss)]
+            let x = i as f32 * step;
+            // Keep silent stretches visible as a centre line.
+            let height = (bottom - top).max(1.0);
+            frame.fill_rectangle(Point::new(x, top), Size::new(step.max(1.0), height), color);
+        }
+        vec![frame.into_geometry()]
+    }
+}
+
+// LCOV_EXCL_START
+
+/// Waveform thumbnail of `columns` (see [`crate::audio::wav::analyze`]),
+/// stretched to the available width.
+pub fn view(columns: &[(f32, f32)]) -> Element<'_, Message> {
+    Canvas::new(Waveform { columns })
+        .width(Length::Fill)
+        .height(Length::Fixed(HEIGHT))
+        .into()
+}
+
+// LCOV_EXCL_STOP